@@ -0,0 +1,19 @@
+// 번들 정적 타입 테이블(resources/static_types.json) 생성
+// cargo run --example generate_static_types -- <SDE JSONL 폴더>
+use std::path::Path;
+use anyhow::anyhow;
+use eve_abyssal_tracker_tauri_lib::static_types;
+
+fn main() -> anyhow::Result<()> {
+    let sde_dir = std::env::args().nth(1)
+        .ok_or_else(|| anyhow!("usage: generate_static_types <SDE JSONL directory>"))?;
+
+    // 시세가 있는 타입만 포함해 크기를 줄임
+    let mut table = static_types::build_from_sde(Path::new(&sde_dir), true)?;
+    table.source = String::from("EVE SDE (JSONL)"); // 빌드 머신 경로는 남기지 않음
+
+    let resources_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("resources");
+    static_types::save_table(&resources_dir, &table)?;
+    println!("{} types written to {}", table.types.len(), resources_dir.join(static_types::STATIC_TYPES_FILE_NAME).display());
+    Ok(())
+}
@@ -1,19 +1,26 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::{BTreeMap, HashMap, HashSet}, sync::Arc, time::{SystemTime, UNIX_EPOCH}};
+use chrono::{Datelike, NaiveDate};
 use tokio::sync::Mutex;
 use serde::{Deserialize, Serialize};
-use polars::prelude::*;
 use tauri::{AppHandle, Emitter};
-use crate::{eve_api::EVEApi, abyssal_data_manager::AbyssalDataManager};
+use crate::{eve_api::EVEApi, abyssal_data_manager::{AbyssalDataManager, AbyssalResult}};
+use crate::price_provider::{self, PriceBasis, PriceQuote, ValuationMode};
 use log::*;
 
 // Implement From<String> for anyhow::Error to allow using `?` with String errors
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RunData {
-    #[serde(rename = "시작시각(KST)")]
+    #[serde(rename = "런 ID")]
+    pub run_id: String,
+    #[serde(rename = "시작시각")]
     pub start_time: String,
-    #[serde(rename = "종료시각(KST)")]
+    #[serde(rename = "종료시각")]
     pub end_time: String,
+    #[serde(rename = "시작시각(UTC)")]
+    pub start_time_utc: String,
+    #[serde(rename = "종료시각(UTC)")]
+    pub end_time_utc: String,
     #[serde(rename = "런 소요(분)")]
     pub run_time_minutes: f64,
     #[serde(rename = "어비셜 종류")]
@@ -32,17 +39,48 @@ pub struct RunData {
     pub drop_value: f64,
     #[serde(rename = "입장료")]
     pub entry_cost: f64,
+    // 위 실수익/ISK/h/드롭/입장료에 사용한 시세 (current 또는 historical)
+    #[serde(rename = "평가 기준")]
+    pub valuation: String,
+    #[serde(rename = "실수익(현재가)")]
+    pub net_profit_current: f64,
+    #[serde(rename = "ISK/h(현재가)")]
+    pub isk_per_hour_current: f64,
+    #[serde(rename = "실수익(당시가)")]
+    pub net_profit_historical: f64,
+    #[serde(rename = "ISK/h(당시가)")]
+    pub isk_per_hour_historical: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+// 분포 통계 (백분위는 선형 보간, 표준편차는 표본 기준)
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct Distribution {
+    pub median: f64,
+    pub std_dev: f64,
+    pub p10: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct RunDistributions {
+    pub profit: Distribution,
+    pub time: Distribution, // 분
+    pub iskph: Distribution,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DailyStats {
-    pub runs: Vec<RunData>,
+    pub runs: Vec<Arc<RunData>>,
     pub avg_isk: f64,
     pub avg_time: f64,
-    pub avg_iskph: f64,
+    pub avg_iskph: f64, // 런별 ISK/h의 평균
+    pub weighted_iskph: f64, // 총 수익 / 총 시간
+    pub distributions: RunDistributions,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TierWeatherStats {
     pub tier: String,
     pub weather: String,
@@ -50,29 +88,225 @@ pub struct TierWeatherStats {
     pub avg_isk: f64,
     pub avg_time: f64,
     pub avg_iskph: f64,
+    pub weighted_iskph: f64,
     pub total_entry_cost: f64,
+    pub distributions: RunDistributions,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OverallStats {
     pub avg_isk: f64,
     pub avg_time: f64,
     pub avg_iskph: f64,
+    pub weighted_iskph: f64,
+    pub distributions: RunDistributions,
     pub tier_weather_stats: Vec<TierWeatherStats>,
 }
 
+// 주/월/지정 기간 집계
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PeriodStats {
+    pub start_date: String,
+    pub end_date: String,
+    pub runs_count: usize,
+    pub total_profit: f64,
+    pub total_time: f64, // 분
+    pub weighted_iskph: f64, // 총 수익 / 총 시간
+    pub distributions: RunDistributions,
+    pub tier_weather_stats: Vec<TierWeatherStats>,
+}
+
+// 아이템 시세의 출처와 조회 시각 (오래된 시세 확인용)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceFreshness {
+    pub source: String, // fuzzwork, esi_orders, esi_average, static 또는 override(수동 가격)
+    pub fetched_at: Option<u64>, // 조회 시각 (unix 초, 가격 캐시에 없으면 None)
+    pub age_seconds: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AnalysisResult {
-    pub df: Vec<RunData>,
+    pub df: Vec<Arc<RunData>>,
     pub daily_stats: HashMap<String, DailyStats>,
+    pub weekly_stats: HashMap<String, PeriodStats>, // "2024-W05" (ISO 주)
+    pub monthly_stats: HashMap<String, PeriodStats>, // "2024-01"
     pub overall_stats: OverallStats,
     pub item_buy_price_cache: HashMap<String, f64>,
+    pub offline: bool, // 오프라인 모드로 캐시된 시세만 사용했는지
+    pub price_freshness: HashMap<String, PriceFreshness>, // 아이템 이름별
+    pub unpriced_items: Vec<String>, // type_id나 시세가 없어 0 ISK로 계산된 아이템
+    pub item_english_names: HashMap<String, String>, // 현지화된 아이템 이름 → 영어 이름
 }
 
 pub struct AbyssalDataAnalyzer {
     eve_api: Arc<Mutex<EVEApi>>,
     data_manager: Arc<Mutex<AbyssalDataManager>>,
     pub app_handle: Option<AppHandle>,
+    loaded: bool,
+    // 시세가 적용된 런 (시작시각 순이므로 통계 날짜도 오름차순), 저장/수정/삭제 시 바뀐 런만 다시 계산
+    runs: Vec<Arc<RunData>>,
+    // 아이템 이름별 시세 (시세가 없는 아이템은 None)
+    item_quotes: HashMap<String, Option<PriceQuote>>,
+    // 아이템 이름별 일별 시세 ("YYYY-MM-DD" → 시세)
+    item_history: HashMap<String, BTreeMap<String, PriceQuote>>,
+    valuation_mode: ValuationMode,
+    // 아이템 이름별 시세 조회 시각 (unix 초)
+    item_fetched_at: HashMap<String, u64>,
+    // 현지화된 아이템 이름별 영어 이름
+    item_english_names: HashMap<String, String>,
+    offline: bool,
+    loot_basis: PriceBasis,
+    filament_basis: PriceBasis,
+    price_overrides: HashMap<String, f64>,
+    // 기준/수동 가격이 적용된 드롭(buy)과 필라멘트(sell) 가격
+    item_buy_price_cache: HashMap<String, f64>,
+    item_sell_price_cache: HashMap<String, f64>,
+    // 시세 기준이 바뀌어 다음 분석에서 모든 런을 다시 계산해야 함
+    revalue_all: bool,
+    daily_stats: HashMap<String, DailyStats>,
+    weekly_stats: HashMap<String, PeriodStats>,
+    monthly_stats: HashMap<String, PeriodStats>,
+    // 런 추가/삭제로 갱신하는 전체 통계 누적값
+    overall: OverallAggregate,
+    overall_stats: OverallStats,
+}
+
+// 런을 하나씩 더하고 빼서 평균/분포를 갱신하는 누적값 (분포용 값은 정렬 상태 유지)
+#[derive(Debug, Default)]
+struct RunAggregate {
+    count: usize,
+    total_profit: f64,
+    total_time: f64,
+    total_iskph: f64,
+    total_entry_cost: f64,
+    profits: Vec<f64>,
+    times: Vec<f64>,
+    iskphs: Vec<f64>,
+}
+
+impl RunAggregate {
+    fn add(&mut self, run: &RunData) {
+        self.count += 1;
+        self.total_profit += run.net_profit;
+        self.total_time += run.run_time_minutes;
+        self.total_iskph += run.isk_per_hour;
+        self.total_entry_cost += run.entry_cost;
+        Self::insert_sorted(&mut self.profits, run.net_profit);
+        Self::insert_sorted(&mut self.times, run.run_time_minutes);
+        Self::insert_sorted(&mut self.iskphs, run.isk_per_hour);
+    }
+
+    fn remove(&mut self, run: &RunData) {
+        self.count = self.count.saturating_sub(1);
+        self.total_profit -= run.net_profit;
+        self.total_time -= run.run_time_minutes;
+        self.total_iskph -= run.isk_per_hour;
+        self.total_entry_cost -= run.entry_cost;
+        Self::remove_sorted(&mut self.profits, run.net_profit);
+        Self::remove_sorted(&mut self.times, run.run_time_minutes);
+        Self::remove_sorted(&mut self.iskphs, run.isk_per_hour);
+    }
+
+    fn insert_sorted(values: &mut Vec<f64>, value: f64) {
+        if value.is_finite() {
+            let index = values.partition_point(|v| *v < value);
+            values.insert(index, value);
+        }
+    }
+
+    fn remove_sorted(values: &mut Vec<f64>, value: f64) {
+        let index = values.partition_point(|v| *v < value);
+        if values.get(index) == Some(&value) {
+            values.remove(index);
+        }
+    }
+
+    fn average(&self, total: f64) -> f64 {
+        if self.count == 0 { 0.0 } else { total / self.count as f64 }
+    }
+
+    fn weighted_iskph(&self) -> f64 {
+        if self.total_time > 0.0 { self.total_profit / (self.total_time / 60.0) } else { 0.0 }
+    }
+
+    fn distributions(&self) -> RunDistributions {
+        RunDistributions {
+            profit: AbyssalDataAnalyzer::sorted_distribution(&self.profits),
+            time: AbyssalDataAnalyzer::sorted_distribution(&self.times),
+            iskph: AbyssalDataAnalyzer::sorted_distribution(&self.iskphs),
+        }
+    }
+}
+
+// 전체 런과 티어/웨더별 누적값
+#[derive(Debug, Default)]
+struct OverallAggregate {
+    all: RunAggregate,
+    tier_weather: HashMap<(String, String), RunAggregate>,
+}
+
+impl OverallAggregate {
+    fn from_runs<'a>(runs: impl IntoIterator<Item = &'a RunData>) -> Self {
+        let mut aggregate = Self::default();
+        for run in runs {
+            aggregate.add(run);
+        }
+        aggregate
+    }
+
+    // "T5 Dark" 형식이 아닌 종류는 티어/웨더별 통계에서 제외
+    fn tier_weather_key(run: &RunData) -> Option<(String, String)> {
+        let mut parts = run.abyssal_type.split_whitespace();
+        Some((parts.next()?.to_string(), parts.next()?.to_string()))
+    }
+
+    fn add(&mut self, run: &RunData) {
+        self.all.add(run);
+        if let Some(key) = Self::tier_weather_key(run) {
+            self.tier_weather.entry(key).or_default().add(run);
+        }
+    }
+
+    fn remove(&mut self, run: &RunData) {
+        self.all.remove(run);
+        if let Some(key) = Self::tier_weather_key(run) {
+            if let Some(aggregate) = self.tier_weather.get_mut(&key) {
+                aggregate.remove(run);
+                if aggregate.count == 0 {
+                    self.tier_weather.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn tier_weather_stats(&self) -> Vec<TierWeatherStats> {
+        self.tier_weather.iter()
+            .filter(|(_, aggregate)| aggregate.count > 0)
+            .map(|((tier, weather), aggregate)| TierWeatherStats {
+                tier: tier.clone(),
+                weather: weather.clone(),
+                runs_count: aggregate.count,
+                avg_isk: aggregate.average(aggregate.total_profit),
+                avg_time: aggregate.average(aggregate.total_time),
+                avg_iskph: aggregate.average(aggregate.total_iskph),
+                weighted_iskph: aggregate.weighted_iskph(),
+                total_entry_cost: aggregate.total_entry_cost,
+                distributions: aggregate.distributions(),
+            })
+            .collect()
+    }
+
+    fn stats(&self) -> OverallStats {
+        OverallStats {
+            avg_isk: self.all.average(self.all.total_profit),
+            avg_time: self.all.average(self.all.total_time),
+            avg_iskph: self.all.average(self.all.total_iskph),
+            weighted_iskph: self.all.weighted_iskph(),
+            distributions: self.all.distributions(),
+            // 티어/웨더별 통계
+            tier_weather_stats: self.tier_weather_stats(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -89,6 +323,25 @@ impl AbyssalDataAnalyzer {
             eve_api,
             data_manager,
             app_handle: None,
+            loaded: false,
+            runs: Vec::new(),
+            item_quotes: HashMap::new(),
+            item_history: HashMap::new(),
+            valuation_mode: ValuationMode::Historical,
+            item_fetched_at: HashMap::new(),
+            item_english_names: HashMap::new(),
+            offline: false,
+            loot_basis: PriceBasis::BuyMax,
+            filament_basis: PriceBasis::SellMin,
+            price_overrides: HashMap::new(),
+            item_buy_price_cache: HashMap::new(),
+            item_sell_price_cache: HashMap::new(),
+            revalue_all: false,
+            daily_stats: HashMap::new(),
+            weekly_stats: HashMap::new(),
+            monthly_stats: HashMap::new(),
+            overall: OverallAggregate::default(),
+            overall_stats: OverallAggregate::default().stats(),
         }
     }
 
@@ -97,6 +350,17 @@ impl AbyssalDataAnalyzer {
         self
     }
 
+    pub fn with_valuation_mode(mut self, valuation_mode: ValuationMode) -> Self {
+        self.valuation_mode = valuation_mode;
+        self
+    }
+
+    pub fn with_price_basis(mut self, loot_basis: PriceBasis, filament_basis: PriceBasis) -> Self {
+        self.loot_basis = loot_basis;
+        self.filament_basis = filament_basis;
+        self
+    }
+
     fn emit_progress(&self, step: &str, message: &str, progress: Option<f64>, completed: bool) {
         if let Some(ref app_handle) = self.app_handle {
             let progress_data = LoadingProgress {
@@ -109,596 +373,729 @@ impl AbyssalDataAnalyzer {
         }
     }
 
-    pub async fn analyze_data(&self) -> Result<AnalysisResult, anyhow::Error> {
+    // 전체 분석 - 모든 런을 다시 읽고 시세를 새로 조회
+    pub async fn analyze_data(&mut self) -> Result<AnalysisResult, anyhow::Error> {
         let start_total = std::time::Instant::now();
-        
+
         self.emit_progress("csv_load", "CSV 파일 로드 중...", Some(0.0), false);
         info!("📂 [AbyssalDataAnalyzer] CSV 파일 로드 중...");
         let start_csv_load = std::time::Instant::now();
-        
-        let df = self.data_manager.lock().await.load_abyssal_results()
-            .map_err(|e| anyhow::anyhow!("Failed to load abyssal results: {}", e))?;
-        
+
+        // 전체를 다시 읽으므로 쌓여 있던 변경 목록은 필요 없음
+        let rows = {
+            let data_manager = self.data_manager.lock().await;
+            data_manager.take_run_changes();
+            data_manager.load_abyssal_runs()
+                .map_err(|e| anyhow::anyhow!("Failed to load abyssal results: {}", e))?
+        };
+
         let end_csv_load = start_csv_load.elapsed();
         self.emit_progress("csv_load", &format!("CSV 파일 로드 완료 ({:.2}초)", end_csv_load.as_secs_f64()), Some(100.0), true);
                     info!("  ▶️ CSV 파일 로드 완료. 소요 시간: {:.2}초 ✅", end_csv_load.as_secs_f64());
-        
-        if df.is_empty() {
+
+        // 전체 분석은 시세를 새로 받으므로 기존 캐시 폐기
+        // loaded도 내려 두어 시세 조회가 실패하면 다음 refresh가 빈 캐시로 증분 분석하지 않고 전체 분석을 다시 시도
+        self.invalidate_prices();
+        self.price_overrides = self.load_price_overrides().await;
+        self.revalue_all = false;
+
+        if rows.is_empty() {
             self.emit_progress("csv_load", "분석할 데이터가 없습니다", Some(100.0), true);
             warn!("❌ 분석할 데이터가 없습니다.");
-            return Ok(AnalysisResult {
-                df: vec![],
-                daily_stats: HashMap::new(),
-                overall_stats: OverallStats {
-                    avg_isk: 0.0,
-                    avg_time: 0.0,
-                    avg_iskph: 0.0,
-                    tier_weather_stats: vec![],
-                },
-                item_buy_price_cache: HashMap::new(),
-            });
-        }
-
-        info!("  ▶️ 총 {}개의 런 데이터 로드 완료. ✅", df.height());
-        
+            self.runs.clear();
+            self.rebuild_stats();
+            self.loaded = true;
+            return Ok(self.result());
+        }
+
+        info!("  ▶️ 총 {}개의 런 데이터 로드 완료. ✅", rows.len());
+
         self.emit_progress("item_collection", "모든 아이템 이름 수집 중...", Some(0.0), false);
-        println!("  ▶️ 모든 아이템 이름 수집 중... 🔍");
+        info!("  ▶️ 모든 아이템 이름 수집 중... 🔍");
         let start_item_collection = std::time::Instant::now();
-        
-        // 모든 아이템 이름 수집 (Python과 동일한 로직)
-        let mut all_item_names = std::collections::HashSet::new();
-        
-        // 획득 아이템에서 아이템 이름 추출
-        if let Ok(items_column) = df.column("획득 아이템") {
-            for item_str in items_column.str().unwrap().into_iter() {
-                if let Some(items) = item_str {
-                    let parsed_items = self.data_manager.lock().await.parse_items(items);
-                    for (name, _qty) in parsed_items {
-                        all_item_names.insert(name);
-                    }
-                }
+
+        let all_item_names = Self::item_names(&*self.data_manager.lock().await, rows.iter());
+
+        let end_item_collection = start_item_collection.elapsed();
+        self.emit_progress("item_collection", &format!("{}종의 아이템 발견! ({:.2}초)", all_item_names.len(), end_item_collection.as_secs_f64()), Some(100.0), true);
+        info!("  ▶️ {}종의 아이템 발견! 소요 시간: {:.2}초 ✨", all_item_names.len(), end_item_collection.as_secs_f64());
+
+        self.fetch_prices(all_item_names, true).await?;
+
+        self.emit_progress("analysis", "런 지표 계산 및 통계 생성 중...", Some(0.0), false);
+        info!("  ▶️ 런 지표 계산 및 통계 생성 중... 📊");
+        let start_analysis = std::time::Instant::now();
+
+        let data_manager = self.data_manager.clone();
+        let data_manager = data_manager.lock().await;
+        self.runs = rows.iter().map(|row| Arc::new(self.value_run(&data_manager, row))).collect();
+        drop(data_manager); // Mutex 락 해제
+
+        self.rebuild_stats();
+        self.loaded = true;
+
+        let end_analysis = start_analysis.elapsed();
+        self.emit_progress("analysis", &format!("데이터 분석 및 통계 생성 완료 ({:.2}초)", end_analysis.as_secs_f64()), Some(100.0), true);
+        info!("  ▶️ 데이터 분석 및 통계 생성 완료. 소요 시간: {:.2}초 ✅", end_analysis.as_secs_f64());
+
+        let end_total = start_total.elapsed();
+        self.emit_progress("complete", &format!("전체 데이터 로딩 및 분석 완료! (총 {:.2}초)", end_total.as_secs_f64()), Some(100.0), true);
+        info!("✨ [AbyssalDataAnalyzer] 전체 데이터 로딩 및 분석 완료. 총 소요 시간: {:.2}초 ✨", end_total.as_secs_f64());
+
+        Ok(self.result())
+    }
+
+    // 증분 분석 - 저장/수정/삭제된 런만 DB에서 다시 읽어 계산하고 해당 날짜의 통계만 갱신
+    // 캐시에 없는 아이템만 시세를 조회
+    pub async fn refresh(&mut self) -> Result<AnalysisResult, anyhow::Error> {
+        if !self.loaded {
+            return self.analyze_data().await;
+        }
+        let start_total = std::time::Instant::now();
+
+        // 수동 가격 파일이 바뀌었으면 모든 런을 다시 계산
+        let price_overrides = self.load_price_overrides().await;
+        if price_overrides != self.price_overrides {
+            info!("수동 가격 변경 감지 ({}개 아이템)", price_overrides.len());
+            self.price_overrides = price_overrides;
+            self.apply_prices();
+            self.revalue_all = true;
+        }
+
+        let changes = self.data_manager.lock().await.take_run_changes();
+        if std::mem::take(&mut self.revalue_all) || changes.all {
+            self.revalue_all_runs().await?;
+            info!("[AbyssalDataAnalyzer] 전체 런 재계산 완료 ({}건). 소요 시간: {:.2}초",
+                self.runs.len(), start_total.elapsed().as_secs_f64());
+            return Ok(self.result());
+        }
+        if changes.run_ids.is_empty() {
+            return Ok(self.result());
+        }
+
+        let rows = self.data_manager.lock().await.load_abyssal_runs_by_id(&changes.run_ids)
+            .map_err(|e| anyhow::anyhow!("Failed to load abyssal results: {}", e))?;
+        self.fetch_missing_prices(&rows).await?;
+
+        // 바뀐 런을 빼고, 다시 계산한 런을 시작시각 순서에 맞게 삽입
+        let mut affected_dates = HashSet::new();
+        let mut removed_runs = Vec::new();
+        self.runs.retain(|run| {
+            let changed = changes.run_ids.contains(&run.run_id);
+            if changed {
+                removed_runs.push(run.clone());
             }
+            !changed
+        });
+        for run in &removed_runs {
+            affected_dates.insert(run.date.clone());
+            self.overall.remove(run);
+        }
+
+        let data_manager = self.data_manager.clone();
+        let data_manager = data_manager.lock().await;
+        for row in &rows {
+            let run = Arc::new(self.value_run(&data_manager, row));
+            affected_dates.insert(run.date.clone());
+            self.overall.add(&run);
+            let index = self.runs.partition_point(|other| other.start_time_utc <= run.start_time_utc);
+            self.runs.insert(index, run);
+        }
+        drop(data_manager);
+
+        self.update_stats(&affected_dates);
+
+        info!("[AbyssalDataAnalyzer] 증분 분석 완료 (변경 {}건, 삭제 {}건). 소요 시간: {:.2}초",
+            rows.len(), changes.run_ids.len() - rows.len(), start_total.elapsed().as_secs_f64());
+        Ok(self.result())
+    }
+
+    // 표시 시간대/하루 기준, 시세 기준 또는 수동 가격이 바뀐 경우 캐시된 시세로 모든 런을 다시 계산
+    async fn revalue_all_runs(&mut self) -> Result<(), anyhow::Error> {
+        let rows = self.data_manager.lock().await.load_abyssal_runs()
+            .map_err(|e| anyhow::anyhow!("Failed to load abyssal results: {}", e))?;
+        self.fetch_missing_prices(&rows).await?;
+
+        let data_manager = self.data_manager.clone();
+        let data_manager = data_manager.lock().await;
+        self.runs = rows.iter().map(|row| Arc::new(self.value_run(&data_manager, row))).collect();
+        drop(data_manager);
+
+        self.rebuild_stats();
+        Ok(())
+    }
+
+    // 캐시에 없는 아이템만 시세 조회
+    async fn fetch_missing_prices(&mut self, rows: &[AbyssalResult]) -> Result<(), anyhow::Error> {
+        let missing_names: Vec<String> = Self::item_names(&*self.data_manager.lock().await, rows.iter())
+            .into_iter()
+            .filter(|name| !self.item_quotes.contains_key(name))
+            .collect();
+        if missing_names.is_empty() {
+            return Ok(());
         }
-        
-        // 어비셜 종류에서 필라멘트 이름 추출
-        if let Ok(abyssal_types_column) = df.column("어비셜 종류") {
-            for abyssal_type in abyssal_types_column.str().unwrap().into_iter() {
-                if let Some(abyssal_type_str) = abyssal_type {
-                    if let Some(filament) = self.data_manager.lock().await.abyssal_type_to_filament_name(abyssal_type_str) {
-                        all_item_names.insert(filament);
-                    }
+        debug!("{}개의 새로운 아이템에 대해 API 조회 중...", missing_names.len());
+        self.fetch_prices(missing_names, false).await
+    }
+
+    // 시세 출처(제공자, 거래 허브)가 바뀌면 캐시된 시세를 버리고 다음 분석에서 전체 재계산
+    pub fn invalidate_prices(&mut self) {
+        self.loaded = false;
+        self.item_quotes.clear();
+        self.item_history.clear();
+        self.item_fetched_at.clear();
+        self.item_english_names.clear();
+        self.item_buy_price_cache.clear();
+        self.item_sell_price_cache.clear();
+    }
+
+    // 시세 기준 변경은 다시 조회하지 않고 캐시된 시세로 모든 런을 다시 계산
+    pub fn set_price_basis(&mut self, loot_basis: PriceBasis, filament_basis: PriceBasis) {
+        if loot_basis == self.loot_basis && filament_basis == self.filament_basis {
+            return;
+        }
+        self.loot_basis = loot_basis;
+        self.filament_basis = filament_basis;
+        self.apply_prices();
+        self.revalue_all = true;
+        info!("시세 기준 변경: 드롭 {}, 필라멘트 {}", loot_basis.name(), filament_basis.name());
+    }
+
+    // 현재가/당시가 평가 전환 - 두 값 모두 계산되어 있으므로 다시 조회하지 않음
+    pub fn set_valuation_mode(&mut self, valuation_mode: ValuationMode) {
+        if valuation_mode == self.valuation_mode {
+            return;
+        }
+        self.valuation_mode = valuation_mode;
+        self.revalue_all = true;
+        info!("런 평가 기준 변경: {}", valuation_mode.name());
+    }
+
+    async fn load_price_overrides(&self) -> HashMap<String, f64> {
+        let data_dir = self.eve_api.lock().await.data_dir().to_path_buf();
+        price_provider::load_price_overrides(&data_dir)
+    }
+
+    // 시세에 기준과 수동 가격을 적용해서 아이템별 드롭/필라멘트 가격 계산
+    fn apply_prices(&mut self) {
+        self.item_buy_price_cache.clear();
+        self.item_sell_price_cache.clear();
+        for (name, quote) in &self.item_quotes {
+            let (buy, sell) = match self.price_overrides.get(name) {
+                Some(&price) => (price, price),
+                None => (
+                    quote.as_ref().map_or(0.0, |quote| self.loot_basis.price(quote)),
+                    quote.as_ref().map_or(0.0, |quote| self.filament_basis.price(quote)),
+                ),
+            };
+            self.item_buy_price_cache.insert(name.clone(), buy);
+            self.item_sell_price_cache.insert(name.clone(), sell);
+        }
+    }
+
+    fn result(&self) -> AnalysisResult {
+        AnalysisResult {
+            df: self.runs.clone(), // Arc만 복사
+            daily_stats: self.daily_stats.clone(),
+            weekly_stats: self.weekly_stats.clone(),
+            monthly_stats: self.monthly_stats.clone(),
+            overall_stats: self.overall_stats.clone(),
+            item_buy_price_cache: self.item_buy_price_cache.clone(),
+            offline: self.offline,
+            price_freshness: self.price_freshness(),
+            unpriced_items: self.unpriced_items(),
+            item_english_names: self.item_english_names.clone(),
+        }
+    }
+
+    fn price_freshness(&self) -> HashMap<String, PriceFreshness> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        self.item_quotes.iter()
+            .filter_map(|(name, quote)| {
+                if self.price_overrides.contains_key(name) {
+                    return Some((name.clone(), PriceFreshness { source: "override".to_string(), fetched_at: None, age_seconds: None }));
                 }
+                let quote = quote.as_ref()?;
+                let fetched_at = self.item_fetched_at.get(name).copied();
+                Some((name.clone(), PriceFreshness {
+                    source: quote.source.clone(),
+                    fetched_at,
+                    age_seconds: fetched_at.map(|fetched_at| now.saturating_sub(fetched_at)),
+                }))
+            })
+            .collect()
+    }
+
+    fn unpriced_items(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.item_quotes.iter()
+            .filter(|(name, quote)| !self.price_overrides.contains_key(*name) && !quote.as_ref().is_some_and(|quote| quote.has_price()))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    // 드롭 아이템과 입장 필라멘트 이름
+    fn item_names<'a>(data_manager: &AbyssalDataManager, rows: impl Iterator<Item = &'a AbyssalResult>) -> Vec<String> {
+        let mut names = HashSet::new();
+        for row in rows {
+            for (name, _qty) in data_manager.parse_items(&row.acquired_items) {
+                names.insert(name);
+            }
+            if let Some(filament) = data_manager.abyssal_type_to_filament_name(&row.abyssal_type) {
+                names.insert(filament);
             }
         }
-        
-        let all_item_names: Vec<String> = all_item_names.into_iter().collect();
-        let end_item_collection = start_item_collection.elapsed();
-        self.emit_progress("item_collection", &format!("{}종의 아이템 발견! ({:.2}초)", all_item_names.len(), end_item_collection.as_secs_f64()), Some(100.0), true);
-        println!("  ▶️ {}종의 아이템 발견! 소요 시간: {:.2}초 ✨", all_item_names.len(), end_item_collection.as_secs_f64());
+        names.into_iter().collect()
+    }
 
-        self.emit_progress("type_id_fetch", "ESI API로 아이템 type_id 변환 중...", Some(0.0), false);
-        println!("  ▶️ ESI API로 아이템 type_id 변환 중... 🔄");
+    // 아이템 시세를 조회해서 캐시에 추가 (시세가 없는 아이템은 0으로 기록해 다시 조회하지 않음)
+    async fn fetch_prices(&mut self, names: Vec<String>, report_progress: bool) -> Result<(), anyhow::Error> {
+        self.offline = self.eve_api.lock().await.is_offline();
+        let type_id_source = if self.offline { "오프라인 모드: 캐시" } else { "ESI API" };
+        if report_progress {
+            self.emit_progress("type_id_fetch", &format!("{}로 아이템 type_id 변환 중...", type_id_source), Some(0.0), false);
+        }
+        debug!("{}로 아이템 type_id 변환 중...", type_id_source);
         let start_type_id_fetch = std::time::Instant::now();
-        let name_to_id = self.eve_api.lock().await.fetch_type_ids(all_item_names.clone()).await?;
+        let name_to_id = self.eve_api.lock().await.fetch_type_ids(names.clone()).await?;
         let end_type_id_fetch = start_type_id_fetch.elapsed();
-        self.emit_progress("type_id_fetch", &format!("{}종 변환 성공! (미매칭: {}) ({:.2}초)", name_to_id.len(), all_item_names.len() - name_to_id.len(), end_type_id_fetch.as_secs_f64()), Some(100.0), true);
-        println!("  ▶️ {}종 변환 성공! (미매칭: {}) 소요 시간: {:.2}초 💡", 
-            name_to_id.len(), all_item_names.len() - name_to_id.len(), end_type_id_fetch.as_secs_f64());
+        if report_progress {
+            self.emit_progress("type_id_fetch", &format!("{}종 변환 성공! (미매칭: {}) ({:.2}초)", name_to_id.len(), names.len() - name_to_id.len(), end_type_id_fetch.as_secs_f64()), Some(100.0), true);
+        }
+        debug!("{}종 변환 성공! (미매칭: {}) 소요 시간: {:.2}초",
+            name_to_id.len(), names.len() - name_to_id.len(), end_type_id_fetch.as_secs_f64());
 
-        self.emit_progress("price_fetch", "Fuzzwork로 대량 시세 조회 중...", Some(0.0), false);
-        println!("  ▶️ Fuzzwork로 대량 시세 조회 중... 💰");
+        if report_progress {
+            self.emit_progress("price_fetch", "대량 시세 조회 중...", Some(0.0), false);
+        }
+        debug!("대량 시세 조회 중...");
         let start_price_fetch = std::time::Instant::now();
         let ids: Vec<u32> = name_to_id.values().cloned().collect();
         let mut prices = HashMap::new();
-        
-        // 100개씩 청크로 나누어 처리 (Python과 동일)
+
+        // 100개씩 청크로 나누어 처리 (요청 간격은 HTTP 스케줄러가 ESI 헤더에 맞춰 조절)
         let total_chunks = (ids.len() + 99) / 100; // 올림 계산
         for (chunk_index, chunk) in ids.chunks(100).enumerate() {
-            let progress = ((chunk_index as f64 / total_chunks as f64) * 100.0).min(99.0);
-            self.emit_progress("price_fetch", &format!("시세 조회 중... ({}/{})", chunk_index + 1, total_chunks), Some(progress), false);
-            
-            let chunk_prices = self.eve_api.lock().await.fetch_fuzzwork_prices(chunk.to_vec()).await?;
+            if report_progress {
+                let progress = ((chunk_index as f64 / total_chunks as f64) * 100.0).min(99.0);
+                self.emit_progress("price_fetch", &format!("시세 조회 중... ({}/{})", chunk_index + 1, total_chunks), Some(progress), false);
+            }
+
+            // 모든 시세 제공자가 실패하면 0 ISK로 계산하지 않고 오류 반환
+            let chunk_prices = self.eve_api.lock().await.fetch_prices(chunk.to_vec()).await?;
             prices.extend(chunk_prices);
-            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
         }
-        
+
         let end_price_fetch = start_price_fetch.elapsed();
-        self.emit_progress("price_fetch", &format!("Fuzzwork 시세 조회 완료 ({:.2}초)", end_price_fetch.as_secs_f64()), Some(100.0), true);
-        println!("  ▶️ Fuzzwork 시세 조회 완료. 소요 시간: {:.2}초 💸", end_price_fetch.as_secs_f64());
-
-        // 가격 캐시 생성 (Python과 동일한 로직)
-        let mut item_buy_price_cache = HashMap::new();
-        let mut item_sell_price_cache = HashMap::new();
-        
-        for (name, type_id) in &name_to_id {
-            if let Some(price_data) = prices.get(&type_id.to_string()) {
-                // buy.max 가격 추출 (문자열로 반환되므로 parse 필요)
-                if let Some(buy_max_str) = price_data.get("buy")
-                    .and_then(|buy| buy.get("max"))
-                    .and_then(|max| max.as_str()) {
-                    match buy_max_str.parse::<f64>() {
-                        Ok(buy_max) => {
-                            item_buy_price_cache.insert(name.clone(), buy_max);
-                        },
-                        Err(_) => {
-                            item_buy_price_cache.insert(name.clone(), 0.0);
-                        }
-                    }
-                } else {
-                    item_buy_price_cache.insert(name.clone(), 0.0);
-                }
-                
-                // sell.min 가격 추출 (문자열로 반환되므로 parse 필요)
-                if let Some(sell_min_str) = price_data.get("sell")
-                    .and_then(|sell| sell.get("min"))
-                    .and_then(|min| min.as_str()) {
-                    match sell_min_str.parse::<f64>() {
-                        Ok(sell_min) => {
-                            item_sell_price_cache.insert(name.clone(), sell_min);
-                        },
-                        Err(_) => {
-                            item_sell_price_cache.insert(name.clone(), 0.0);
-                        }
-                    }
-                } else {
-                    item_sell_price_cache.insert(name.clone(), 0.0);
-                }
-            } else {
-                item_buy_price_cache.insert(name.clone(), 0.0);
-                item_sell_price_cache.insert(name.clone(), 0.0);
-            }
+        if report_progress {
+            self.emit_progress("price_fetch", &format!("시세 조회 완료 ({:.2}초)", end_price_fetch.as_secs_f64()), Some(100.0), true);
         }
+        debug!("시세 조회 완료. 소요 시간: {:.2}초", end_price_fetch.as_secs_f64());
 
-        self.emit_progress("analysis", "런 지표 계산 및 통계 생성 중...", Some(0.0), false);
-        println!("  ▶️ 런 지표 계산 및 통계 생성 중... 📊");
-        let start_analysis = std::time::Instant::now();
-        
-        // 데이터를 먼저 벡터로 변환하여 계산을 단순화
-        let mut runs_data = Vec::new();
-        
-        // DataFrame을 row별로 처리
-        let start_time_col = df.column("시작시각(KST)").map_err(|e| anyhow::anyhow!("시작시각(KST) 컬럼 없음: {}", e))?.str().unwrap();
-        let end_time_col = df.column("종료시각(KST)").map_err(|e| anyhow::anyhow!("종료시각(KST) 컬럼 없음: {}", e))?.str().unwrap();
-        let run_time_col = df.column("런 소요(분)").map_err(|e| anyhow::anyhow!("런 소요(분) 컬럼 없음: {}", e))?.f64().unwrap();
-        let abyssal_type_col = df.column("어비셜 종류").map_err(|e| anyhow::anyhow!("어비셜 종류 컬럼 없음: {}", e))?.str().unwrap();
-        let acquired_items_col = df.column("획득 아이템").map_err(|e| anyhow::anyhow!("획득 아이템 컬럼 없음: {}", e))?.str().unwrap();
-        
-        // 함급 컬럼은 선택적 (기존 CSV와의 호환성)
-        let ship_class_col_i32 = df.column("함급").ok().and_then(|col| col.i32().ok());
-        let ship_class_col_i64 = df.column("함급").ok().and_then(|col| col.i64().ok());
-        
-        let data_manager = self.data_manager.lock().await;
-        
-        for i in 0..df.height() {
-            let start_time = start_time_col.get(i).unwrap_or("").to_string();
-            let end_time = end_time_col.get(i).unwrap_or("").to_string();
-            let run_time_minutes = run_time_col.get(i).unwrap_or(0.0);
-            let abyssal_type = abyssal_type_col.get(i).unwrap_or("").to_string();
-            let acquired_items = acquired_items_col.get(i).unwrap_or("").to_string();
-            
-            // 함급 읽기 (기본값: 1 - Cruiser)
-            let ship_class = if let Some(ship_class_col) = &ship_class_col_i64 {
-                ship_class_col.get(i).unwrap_or(1).try_into().unwrap()
-            } else {
-                1 // 기본값: Cruiser
-            };
-            
-            // 드롭 가격 계산
-            let drop_value: f64 = {
-                let parsed_items = data_manager.parse_items(&acquired_items);
-                let total_drop_value: f64 = parsed_items.into_iter().map(|(name, qty)| {
-                    let price = item_buy_price_cache.get(&name).unwrap_or(&0.0);
-                    let item_value = price * (qty as f64);
-                    item_value
-                }).sum();
-                total_drop_value
-            };
-            
-            // 입장료 계산 - ship_class에 따라 필라멘트 개수 결정
-            let entry_cost: f64 = {
-                if let Some(filament) = data_manager.abyssal_type_to_filament_name(&abyssal_type) {
-                    let price = item_sell_price_cache.get(&filament).unwrap_or(&0.0);
-                    let cost = price * (ship_class as f64); // 함급에 따른 배수
-                    cost
-                } else {
-                    0.0
-                }
-            };
-            
-            // 실수익 및 ISK/h 계산
-            let net_profit = drop_value - entry_cost;
-            
-            // 실수익 및 ISK/h 계산
-            let isk_per_hour = if run_time_minutes > 0.0 {
-                net_profit / (run_time_minutes / 60.0)
-            } else {
-                0.0
-            };
-            
-            // 날짜 추출
-            let date = if start_time.len() >= 10 {
-                start_time[0..10].to_string()
-            } else {
-                "".to_string()
-            };
-            
-            runs_data.push(RunData {
-                start_time,
-                end_time,
-                run_time_minutes,
-                abyssal_type,
-                ship_class,
-                net_profit,
-                isk_per_hour,
-                acquired_items,
-                date,
-                drop_value,
-                entry_cost,
-            });
-        }
-        
-        drop(data_manager); // Mutex 락 해제
+        // 런 날짜별 평가에 사용할 일별 시세 (방금 조회한 오늘 시세 포함)
+        let (mut history, fetched_at, english_names) = {
+            let eve_api = self.eve_api.lock().await;
+            (eve_api.price_history(&ids).await, eve_api.price_fetched_at(&ids).await, eve_api.english_names(&names).await)
+        };
+        self.item_english_names.extend(english_names);
 
-        // 일별 통계 생성
-        let mut daily_stats = HashMap::new();
-        let mut grouped_by_date: HashMap<String, Vec<&RunData>> = HashMap::new();
-        
-        for run in &runs_data {
-            grouped_by_date.entry(run.date.clone()).or_insert_with(Vec::new).push(run);
+        for name in names {
+            let type_id = name_to_id.get(&name);
+            match type_id.and_then(|type_id| fetched_at.get(type_id)) {
+                Some(&time) => { self.item_fetched_at.insert(name.clone(), time); }
+                None => { self.item_fetched_at.remove(&name); }
+            }
+            let quote = type_id.and_then(|type_id| prices.get(type_id)).cloned();
+            match type_id.and_then(|type_id| history.remove(type_id)) {
+                Some(snapshots) => { self.item_history.insert(name.clone(), snapshots); }
+                None => { self.item_history.remove(&name); }
+            }
+            self.item_quotes.insert(name, quote);
         }
+        self.apply_prices();
+        Ok(())
+    }
 
-        for (date, runs) in grouped_by_date {
-            let avg_isk = if runs.is_empty() { 0.0 } else { 
-                runs.iter().map(|r| r.net_profit).sum::<f64>() / runs.len() as f64 
-            };
-            let avg_time = if runs.is_empty() { 0.0 } else { 
-                runs.iter().map(|r| r.run_time_minutes).sum::<f64>() / runs.len() as f64 
-            };
-            let avg_iskph = if runs.is_empty() { 0.0 } else { 
-                runs.iter().map(|r| r.isk_per_hour).sum::<f64>() / runs.len() as f64 
-            };
-            
-            daily_stats.insert(date, DailyStats {
-                runs: runs.into_iter().cloned().collect(),
-                avg_isk,
-                avg_time,
-                avg_iskph,
-            });
-        }
-
-        // 전체 통계 생성
-        let overall_avg_isk = if runs_data.is_empty() { 0.0 } else {
-            runs_data.iter().map(|r| r.net_profit).sum::<f64>() / runs_data.len() as f64
-        };
-        let overall_avg_time = if runs_data.is_empty() { 0.0 } else {
-            runs_data.iter().map(|r| r.run_time_minutes).sum::<f64>() / runs_data.len() as f64
+    // 런 날짜에 가장 가까운 일별 시세의 아이템 가격 (기록이 없으면 현재가, 수동 가격 우선)
+    fn historical_price(&self, name: &str, date: &str, basis: PriceBasis, current: &HashMap<String, f64>) -> f64 {
+        if let Some(&price) = self.price_overrides.get(name) {
+            return price;
+        }
+        let current_price = || current.get(name).copied().unwrap_or(0.0);
+        let (Some(snapshots), Ok(run_date)) = (self.item_history.get(name), NaiveDate::parse_from_str(date, "%Y-%m-%d")) else {
+            return current_price();
         };
-        let overall_avg_iskph = if runs_data.is_empty() { 0.0 } else {
-            runs_data.iter().map(|r| r.isk_per_hour).sum::<f64>() / runs_data.len() as f64
+
+        let days_from_run = |snapshot_date: &String| NaiveDate::parse_from_str(snapshot_date, "%Y-%m-%d")
+            .map(|d| (d - run_date).num_days().abs())
+            .unwrap_or(i64::MAX);
+        let before = snapshots.range(..=date.to_string()).next_back();
+        let after = snapshots.range(date.to_string()..).next();
+        let nearest = match (before, after) {
+            (Some(b), Some(a)) => if days_from_run(a.0) < days_from_run(b.0) { Some(a) } else { Some(b) },
+            (b, a) => b.or(a),
         };
+        nearest.map_or_else(current_price, |(_, quote)| basis.price(quote))
+    }
 
-        // 티어/웨더별 통계
-        let mut tier_weather_groups: HashMap<(String, String), Vec<&RunData>> = HashMap::new();
-        for run in &runs_data {
-            let parts: Vec<&str> = run.abyssal_type.split_whitespace().collect();
-            if parts.len() >= 2 {
-                let tier = parts[0].to_string();
-                let weather = parts[1].to_string();
-                tier_weather_groups.entry((tier, weather)).or_insert_with(Vec::new).push(run);
-            }
-        }
+    // 캐시된 시세로 런 하나의 드롭/입장료/실수익 계산 (현재가와 당시가 모두)
+    fn value_run(&self, data_manager: &AbyssalDataManager, row: &AbyssalResult) -> RunData {
+        let items = data_manager.parse_items(&row.acquired_items);
+        let filament = data_manager.abyssal_type_to_filament_name(&row.abyssal_type);
 
-        let mut tier_weather_stats = Vec::new();
-        for ((tier, weather), runs) in tier_weather_groups {
-            if !runs.is_empty() {
-                let avg_isk = runs.iter().map(|r| r.net_profit).sum::<f64>() / runs.len() as f64;
-                let avg_time = runs.iter().map(|r| r.run_time_minutes).sum::<f64>() / runs.len() as f64;
-                let avg_iskph = runs.iter().map(|r| r.isk_per_hour).sum::<f64>() / runs.len() as f64;
-                let total_entry_cost = runs.iter().map(|r| r.entry_cost).sum::<f64>();
-                
-                tier_weather_stats.push(TierWeatherStats {
-                    tier,
-                    weather,
-                    runs_count: runs.len(),
-                    avg_isk,
-                    avg_time,
-                    avg_iskph,
-                    total_entry_cost,
-                });
-            }
-        }
+        // 드롭 가격 계산
+        let drop_value_current: f64 = items.iter()
+            .map(|(name, qty)| self.item_buy_price_cache.get(name).unwrap_or(&0.0) * (*qty as f64))
+            .sum();
+        let drop_value_historical: f64 = items.iter()
+            .map(|(name, qty)| self.historical_price(name, &row.date, self.loot_basis, &self.item_buy_price_cache) * (*qty as f64))
+            .sum();
 
-        let overall_stats = OverallStats {
-            avg_isk: overall_avg_isk,
-            avg_time: overall_avg_time,
-            avg_iskph: overall_avg_iskph,
-            tier_weather_stats,
+        // 입장료 계산 - ship_class에 따라 필라멘트 개수 결정 (함급에 따른 배수)
+        let entry_cost_current: f64 = filament.as_ref()
+            .map_or(0.0, |filament| self.item_sell_price_cache.get(filament).unwrap_or(&0.0) * (row.ship_class as f64));
+        let entry_cost_historical: f64 = filament.as_ref()
+            .map_or(0.0, |filament| self.historical_price(filament, &row.date, self.filament_basis, &self.item_sell_price_cache) * (row.ship_class as f64));
+
+        // 실수익 및 ISK/h 계산
+        let isk_per_hour = |net_profit: f64| if row.run_time_minutes > 0.0 {
+            net_profit / (row.run_time_minutes / 60.0)
+        } else {
+            0.0
+        };
+        let net_profit_current = drop_value_current - entry_cost_current;
+        let net_profit_historical = drop_value_historical - entry_cost_historical;
+        let (drop_value, entry_cost, net_profit) = match self.valuation_mode {
+            ValuationMode::Current => (drop_value_current, entry_cost_current, net_profit_current),
+            ValuationMode::Historical => (drop_value_historical, entry_cost_historical, net_profit_historical),
         };
 
-        let end_analysis = start_analysis.elapsed();
-        self.emit_progress("analysis", &format!("데이터 분석 및 통계 생성 완료 ({:.2}초)", end_analysis.as_secs_f64()), Some(100.0), true);
-        println!("  ▶️ 데이터 분석 및 통계 생성 완료. 소요 시간: {:.2}초 ✅", end_analysis.as_secs_f64());
+        RunData {
+            run_id: row.run_id.clone(),
+            start_time: row.start_time.clone(),
+            end_time: row.end_time.clone(),
+            start_time_utc: row.start_time_utc.clone(),
+            end_time_utc: row.end_time_utc.clone(),
+            run_time_minutes: row.run_time_minutes,
+            abyssal_type: row.abyssal_type.clone(),
+            ship_class: row.ship_class,
+            net_profit,
+            isk_per_hour: isk_per_hour(net_profit),
+            acquired_items: row.acquired_items.clone(),
+            date: row.date.clone(), // 하루 기준 설정이 적용된 통계 날짜
+            drop_value,
+            entry_cost,
+            valuation: self.valuation_mode.name().to_string(),
+            net_profit_current,
+            isk_per_hour_current: isk_per_hour(net_profit_current),
+            net_profit_historical,
+            isk_per_hour_historical: isk_per_hour(net_profit_historical),
+        }
+    }
 
-        let end_total = start_total.elapsed();
-        self.emit_progress("complete", &format!("전체 데이터 로딩 및 분석 완료! (총 {:.2}초)", end_total.as_secs_f64()), Some(100.0), true);
-        println!("✨ [AbyssalDataAnalyzer] 전체 데이터 로딩 및 분석 완료. 총 소요 시간: {:.2}초 ✨", end_total.as_secs_f64());
-        
-        Ok(AnalysisResult {
-            df: runs_data,
-            daily_stats,
-            overall_stats,
-            item_buy_price_cache,
-        })
-    }
-
-    // 가벼운 데이터 분석 - 기존 캐시된 가격 정보 활용
-    pub async fn light_analyze_data(&mut self, df: DataFrame) -> Result<AnalysisResult, anyhow::Error> {
-        println!("🚀 [AbyssalDataAnalyzer] 가벼운 데이터 분석 시작 (캐시된 가격 정보 활용)");
-        let start_total = std::time::Instant::now();
+    fn rebuild_stats(&mut self) {
+        let mut grouped_by_date: HashMap<String, Vec<&Arc<RunData>>> = HashMap::new();
+        for run in &self.runs {
+            grouped_by_date.entry(run.date.clone()).or_insert_with(Vec::new).push(run);
+        }
+        self.daily_stats = grouped_by_date.into_iter()
+            .map(|(date, runs)| (date, Self::daily_stats(&runs)))
+            .collect();
+        self.weekly_stats = Self::grouped_period_stats(self.runs.iter().map(|run| &**run), Self::week_of);
+        self.monthly_stats = Self::grouped_period_stats(self.runs.iter().map(|run| &**run), Self::month_of);
+        self.overall = OverallAggregate::from_runs(self.runs.iter().map(|run| &**run));
+        self.overall_stats = self.overall.stats();
+    }
 
-        if df.height() == 0 {
-            println!("⚠️ 분석할 데이터가 없습니다.");
-            return Ok(AnalysisResult {
-                df: Vec::new(),
-                daily_stats: HashMap::new(),
-                overall_stats: OverallStats {
-                    avg_isk: 0.0,
-                    avg_time: 0.0,
-                    avg_iskph: 0.0,
-                    tier_weather_stats: Vec::new(),
-                },
-                item_buy_price_cache: HashMap::new(),
-            });
-        }
-
-        // 새로운 아이템들 수집
-        let mut all_item_names = std::collections::HashSet::new();
-        let acquired_items_col = df.column("획득 아이템")
-            .map_err(|e| anyhow::anyhow!("획득 아이템 컬럼 없음: {}", e))?
-            .str().unwrap();
-        
-        let data_manager = self.data_manager.lock().await;
-        for i in 0..df.height() {
-            if let Some(items_str) = acquired_items_col.get(i) {
-                let parsed_items = data_manager.parse_items(items_str);
-                for (name, _) in parsed_items {
-                    all_item_names.insert(name);
-                }
+    // 바뀐 날짜가 속한 일/주/월 통계만 다시 계산 (전체 통계는 누적값에서 갱신)
+    fn update_stats(&mut self, affected_dates: &HashSet<String>) {
+        for date in affected_dates {
+            let runs: Vec<&Arc<RunData>> = self.runs_between(date, date).iter().collect();
+            if runs.is_empty() {
+                self.daily_stats.remove(date);
+            } else {
+                self.daily_stats.insert(date.clone(), Self::daily_stats(&runs));
             }
         }
 
-        // 어비셜 타입에서 필라멘트 이름 추출
-        let abyssal_type_col = df.column("어비셜 종류")
-            .map_err(|e| anyhow::anyhow!("어비셜 종류 컬럼 없음: {}", e))?
-            .str().unwrap();
-        
-        for i in 0..df.height() {
-            if let Some(abyssal_type) = abyssal_type_col.get(i) {
-                if let Some(filament_name) = data_manager.abyssal_type_to_filament_name(abyssal_type) {
-                    all_item_names.insert(filament_name);
+        for (stats, period_of) in [
+            (&mut self.weekly_stats, Self::week_of as fn(NaiveDate) -> (String, NaiveDate, NaiveDate)),
+            (&mut self.monthly_stats, Self::month_of),
+        ] {
+            let affected_periods: HashMap<String, (NaiveDate, NaiveDate)> = affected_dates.iter()
+                .filter_map(|date| Self::parse_date(date))
+                .map(|date| {
+                    let (key, start_date, end_date) = period_of(date);
+                    (key, (start_date, end_date))
+                })
+                .collect();
+            for (key, (start_date, end_date)) in affected_periods {
+                let runs: Vec<&RunData> = Self::slice_between(&self.runs, &Self::format_date(start_date), &Self::format_date(end_date))
+                    .iter().map(|run| &**run).collect();
+                if runs.is_empty() {
+                    stats.remove(&key);
+                } else {
+                    stats.insert(key, Self::period_stats(start_date, end_date, &runs));
                 }
             }
         }
-        drop(data_manager);
+        self.overall_stats = self.overall.stats();
+    }
 
-        println!("  ▶️ 총 {}개의 고유 아이템 발견", all_item_names.len());
-
-        // 기존 캐시에서 가격 정보 로드 시도
-        let mut item_buy_price_cache = HashMap::new();
-        let mut item_sell_price_cache = HashMap::new();
-        let mut missing_items = Vec::new();
-
-        // 모든 아이템을 missing으로 처리 (실제 캐시 로직은 EVEApi에서 처리됨)
-        for item_name in &all_item_names {
-            missing_items.push(item_name.clone());
-        }
-
-        // 새로운 아이템이 있으면 API 호출
-        if !missing_items.is_empty() {
-            println!("  ▶️ {}개의 새로운 아이템에 대해 API 조회 중...", missing_items.len());
-            
-            // TypeID 조회
-            let name_to_id = self.eve_api.lock().await.fetch_type_ids(missing_items).await?;
-            
-            // 가격 조회
-            let type_ids: Vec<u32> = name_to_id.values().cloned().collect();
-            let prices = self.eve_api.lock().await.fetch_fuzzwork_prices(type_ids).await?;
-            
-            // 가격 캐시 업데이트
-            for (name, type_id) in &name_to_id {
-                if let Some(price_data) = prices.get(&type_id.to_string()) {
-                    if let Some(buy_max_str) = price_data.get("buy")
-                        .and_then(|buy| buy.get("max"))
-                        .and_then(|max| max.as_str()) {
-                        if let Ok(buy_max) = buy_max_str.parse::<f64>() {
-                            item_buy_price_cache.insert(name.clone(), buy_max);
-                        }
-                    }
-                    
-                    if let Some(sell_min_str) = price_data.get("sell")
-                        .and_then(|sell| sell.get("min"))
-                        .and_then(|min| min.as_str()) {
-                        if let Ok(sell_min) = sell_min_str.parse::<f64>() {
-                            item_sell_price_cache.insert(name.clone(), sell_min);
-                        }
-                    }
-                }
-            }
-            println!("  ▶️ 새로운 아이템 가격 조회 완료");
-        } else {
-            println!("  ▶️ 모든 아이템이 캐시에 있음, API 호출 생략");
-        }
-
-        // 런 데이터 계산 (기존과 동일한 로직)
-        let mut runs_data = Vec::new();
-        
-        let start_time_col = df.column("시작시각(KST)").unwrap().str().unwrap();
-        let end_time_col = df.column("종료시각(KST)").unwrap().str().unwrap();
-        let run_time_col = df.column("런 소요(분)").unwrap().f64().unwrap();
-        let abyssal_type_col = df.column("어비셜 종류").unwrap().str().unwrap();
-        let acquired_items_col = df.column("획득 아이템").unwrap().str().unwrap();
-        
-        // 함급 컬럼은 선택적 (기존 CSV와의 호환성)
-        let ship_class_col_i32 = df.column("함급").ok().and_then(|col| col.i32().ok());
-        let ship_class_col_i64 = df.column("함급").ok().and_then(|col| col.i64().ok());
-        println!("DEBUG analyze_data: ship_class_col_i32 exists: {:?}, ship_class_col_i64 exists: {:?}", 
-                ship_class_col_i32.is_some(), ship_class_col_i64.is_some());
-        if let Some(col) = &ship_class_col_i32 {
-            println!("DEBUG analyze_data: first 5 ship class values (i32): {:?}", col.slice(0, 5));
-        }
-        if let Some(col) = &ship_class_col_i64 {
-            println!("DEBUG analyze_data: first 5 ship class values (i64): {:?}", col.slice(0, 5));
-        }
-        
-        let data_manager = self.data_manager.lock().await;
-        
-        for i in 0..df.height() {
-            let start_time = start_time_col.get(i).unwrap_or("").to_string();
-            let end_time = end_time_col.get(i).unwrap_or("").to_string();
-            let run_time_minutes = run_time_col.get(i).unwrap_or(0.0);
-            let abyssal_type = abyssal_type_col.get(i).unwrap_or("").to_string();
-            let acquired_items = acquired_items_col.get(i).unwrap_or("").to_string();
-            
-            // 함급 읽기 (기본값: 1 - Cruiser)
-            let ship_class = if let Some(ship_class_col) = &ship_class_col_i64 {
-                ship_class_col.get(i).unwrap_or(1).try_into().unwrap()
-            } else {
-                1 // 기본값: Cruiser
-            };
-            
-            // 드롭 가격 계산
-            let drop_value: f64 = {
-                let parsed_items = data_manager.parse_items(&acquired_items);
-                parsed_items.into_iter().map(|(name, qty)| {
-                    let price = item_buy_price_cache.get(&name).unwrap_or(&0.0);
-                    price * (qty as f64)
-                }).sum()
-            };
-            
-            // 입장료 계산 - ship_class에 따라 필라멘트 개수 결정
-            let entry_cost: f64 = {
-                if let Some(filament) = data_manager.abyssal_type_to_filament_name(&abyssal_type) {
-                    let price = item_sell_price_cache.get(&filament).unwrap_or(&0.0);
-                    price * (ship_class as f64) // 함급에 따른 배수
-                } else {
-                    0.0
-                }
-            };
-            
-            let net_profit = drop_value - entry_cost;
-            let isk_per_hour = if run_time_minutes > 0.0 {
-                net_profit / (run_time_minutes / 60.0)
-            } else {
-                0.0
-            };
-            
-            let date = if start_time.len() >= 10 {
-                start_time[0..10].to_string()
-            } else {
-                "".to_string()
-            };
-            
-            runs_data.push(RunData {
-                start_time,
-                end_time,
-                run_time_minutes,
-                abyssal_type,
-                ship_class,
-                net_profit,
-                isk_per_hour,
-                acquired_items,
-                date,
-                drop_value,
-                entry_cost,
-            });
-        }
-        
-        drop(data_manager);
+    // 통계 날짜가 start_date ~ end_date(포함)인 런 - 런이 시작시각 순이라 날짜도 정렬되어 있음
+    fn runs_between(&self, start_date: &str, end_date: &str) -> &[Arc<RunData>] {
+        Self::slice_between(&self.runs, start_date, end_date)
+    }
 
-        // 통계 계산 (기존과 동일)
-        let mut daily_stats = HashMap::new();
-        let mut grouped_by_date: HashMap<String, Vec<&RunData>> = HashMap::new();
-        
-        for run in &runs_data {
-            grouped_by_date.entry(run.date.clone()).or_insert_with(Vec::new).push(run);
+    fn slice_between<'a>(runs: &'a [Arc<RunData>], start_date: &str, end_date: &str) -> &'a [Arc<RunData>] {
+        let start = runs.partition_point(|run| run.date.as_str() < start_date);
+        let end = runs.partition_point(|run| run.date.as_str() <= end_date);
+        &runs[start..end.max(start)]
+    }
+
+    fn format_date(date: NaiveDate) -> String {
+        date.format("%Y-%m-%d").to_string()
+    }
+
+    fn daily_stats(runs: &[&Arc<RunData>]) -> DailyStats {
+        let aggregate = OverallAggregate::from_runs(runs.iter().map(|run| &***run)).all;
+        DailyStats {
+            runs: runs.iter().map(|run| Arc::clone(run)).collect(),
+            avg_isk: aggregate.average(aggregate.total_profit),
+            avg_time: aggregate.average(aggregate.total_time),
+            avg_iskph: aggregate.average(aggregate.total_iskph),
+            weighted_iskph: aggregate.weighted_iskph(),
+            distributions: aggregate.distributions(),
         }
+    }
 
-        for (date, runs) in grouped_by_date {
-            let avg_isk = if runs.is_empty() { 0.0 } else { 
-                runs.iter().map(|r| r.net_profit).sum::<f64>() / runs.len() as f64 
-            };
-            let avg_time = if runs.is_empty() { 0.0 } else { 
-                runs.iter().map(|r| r.run_time_minutes).sum::<f64>() / runs.len() as f64 
-            };
-            let avg_iskph = if runs.is_empty() { 0.0 } else { 
-                runs.iter().map(|r| r.isk_per_hour).sum::<f64>() / runs.len() as f64 
-            };
-            
-            daily_stats.insert(date, DailyStats {
-                runs: runs.into_iter().cloned().collect(),
-                avg_isk,
-                avg_time,
-                avg_iskph,
-            });
-        }
-
-        // 전체 통계 생성
-        let overall_avg_isk = if runs_data.is_empty() { 0.0 } else {
-            runs_data.iter().map(|r| r.net_profit).sum::<f64>() / runs_data.len() as f64
-        };
-        let overall_avg_time = if runs_data.is_empty() { 0.0 } else {
-            runs_data.iter().map(|r| r.run_time_minutes).sum::<f64>() / runs_data.len() as f64
+    // 정렬된 유한값의 분포
+    fn sorted_distribution(values: &[f64]) -> Distribution {
+        if values.is_empty() {
+            return Distribution::default();
+        }
+
+        let percentile = |p: f64| {
+            let rank = p * (values.len() - 1) as f64;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            values[lower] + (values[upper] - values[lower]) * (rank - lower as f64)
         };
-        let overall_avg_iskph = if runs_data.is_empty() { 0.0 } else {
-            runs_data.iter().map(|r| r.isk_per_hour).sum::<f64>() / runs_data.len() as f64
+
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let std_dev = if values.len() > 1 {
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64).sqrt()
+        } else {
+            0.0
         };
 
-        // 티어/웨더별 통계
-        let mut tier_weather_groups: HashMap<(String, String), Vec<&RunData>> = HashMap::new();
-        for run in &runs_data {
-            let parts: Vec<&str> = run.abyssal_type.split_whitespace().collect();
-            if parts.len() >= 2 {
-                let tier = parts[0].to_string();
-                let weather = parts[1].to_string();
-                tier_weather_groups.entry((tier, weather)).or_insert_with(Vec::new).push(run);
-            }
+        Distribution {
+            median: percentile(0.5),
+            std_dev,
+            p10: percentile(0.1),
+            p25: percentile(0.25),
+            p75: percentile(0.75),
+            p90: percentile(0.9),
         }
+    }
+
+    fn period_stats(start_date: NaiveDate, end_date: NaiveDate, runs: &[&RunData]) -> PeriodStats {
+        let aggregate = OverallAggregate::from_runs(runs.iter().copied());
+        PeriodStats {
+            start_date: start_date.format("%Y-%m-%d").to_string(),
+            end_date: end_date.format("%Y-%m-%d").to_string(),
+            runs_count: aggregate.all.count,
+            total_profit: aggregate.all.total_profit,
+            total_time: aggregate.all.total_time,
+            weighted_iskph: aggregate.all.weighted_iskph(),
+            distributions: aggregate.all.distributions(),
+            tier_weather_stats: aggregate.tier_weather_stats(),
+        }
+    }
 
-        let mut tier_weather_stats = Vec::new();
-        for ((tier, weather), runs) in tier_weather_groups {
-            if !runs.is_empty() {
-                let avg_isk = runs.iter().map(|r| r.net_profit).sum::<f64>() / runs.len() as f64;
-                let avg_time = runs.iter().map(|r| r.run_time_minutes).sum::<f64>() / runs.len() as f64;
-                let avg_iskph = runs.iter().map(|r| r.isk_per_hour).sum::<f64>() / runs.len() as f64;
-                let total_entry_cost = runs.iter().map(|r| r.entry_cost).sum::<f64>();
-                
-                tier_weather_stats.push(TierWeatherStats {
-                    tier,
-                    weather,
-                    runs_count: runs.len(),
-                    avg_isk,
-                    avg_time,
-                    avg_iskph,
-                    total_entry_cost,
-                });
+    // 날짜 → (키, 기간 시작일, 기간 마지막 날) 함수로 런을 묶어서 기간별 통계 생성
+    fn grouped_period_stats<'a>(runs_data: impl Iterator<Item = &'a RunData>, period_of: fn(NaiveDate) -> (String, NaiveDate, NaiveDate)) -> HashMap<String, PeriodStats> {
+        let mut groups: HashMap<String, (NaiveDate, NaiveDate, Vec<&RunData>)> = HashMap::new();
+        for run in runs_data {
+            if let Some(date) = Self::parse_date(&run.date) {
+                let (key, start_date, end_date) = period_of(date);
+                groups.entry(key).or_insert_with(|| (start_date, end_date, Vec::new())).2.push(run);
             }
         }
 
-        let overall_stats = OverallStats {
-            avg_isk: overall_avg_isk,
-            avg_time: overall_avg_time,
-            avg_iskph: overall_avg_iskph,
-            tier_weather_stats,
+        groups.into_iter()
+            .map(|(key, (start_date, end_date, runs))| (key, Self::period_stats(start_date, end_date, &runs)))
+            .collect()
+    }
+
+    fn parse_date(date: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }
+
+    // ISO 주 (월요일 시작)
+    fn week_of(date: NaiveDate) -> (String, NaiveDate, NaiveDate) {
+        let week = date.iso_week();
+        let monday = NaiveDate::from_isoywd_opt(week.year(), week.week(), chrono::Weekday::Mon).unwrap_or(date);
+        (format!("{}-W{:02}", week.year(), week.week()), monday, monday + chrono::Duration::days(6))
+    }
+
+    fn month_of(date: NaiveDate) -> (String, NaiveDate, NaiveDate) {
+        let first_day = date.with_day(1).unwrap_or(date);
+        let next_month = if date.month() == 12 {
+            NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
         };
+        let last_day = next_month.map(|d| d - chrono::Duration::days(1)).unwrap_or(date);
+        (date.format("%Y-%m").to_string(), first_day, last_day)
+    }
 
-        let end_total = start_total.elapsed();
-        println!("✨ [AbyssalDataAnalyzer] 가벼운 데이터 분석 완료. 소요 시간: {:.2}초 ✨", end_total.as_secs_f64());
-        
-        Ok(AnalysisResult {
-            df: runs_data,
-            daily_stats,
-            overall_stats,
-            item_buy_price_cache,
-        })
+    // 지정한 기간(시작일과 종료일 포함)의 통계
+    pub fn range_stats(&self, start_date: NaiveDate, end_date: NaiveDate) -> PeriodStats {
+        let runs: Vec<&RunData> = self.runs_between(&Self::format_date(start_date), &Self::format_date(end_date))
+            .iter().map(|run| &**run).collect();
+        Self::period_stats(start_date, end_date, &runs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(abyssal_type: &str, net_profit: f64, run_time_minutes: f64) -> RunData {
+        RunData {
+            run_id: String::new(),
+            start_time: String::new(),
+            end_time: String::new(),
+            start_time_utc: String::new(),
+            end_time_utc: String::new(),
+            run_time_minutes,
+            abyssal_type: abyssal_type.to_string(),
+            ship_class: 1,
+            net_profit,
+            isk_per_hour: if run_time_minutes > 0.0 { net_profit / (run_time_minutes / 60.0) } else { 0.0 },
+            acquired_items: String::new(),
+            date: String::from("2024-05-01"),
+            drop_value: net_profit,
+            entry_cost: 0.0,
+            valuation: String::from("historical"),
+            net_profit_current: net_profit,
+            isk_per_hour_current: 0.0,
+            net_profit_historical: net_profit,
+            isk_per_hour_historical: 0.0,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    // 일별/주별/월별/전체 통계와 같은 누적값으로 계산한 수익 분포
+    fn profit_distribution(profits: &[f64]) -> Distribution {
+        let mut aggregate = RunAggregate::default();
+        for profit in profits {
+            aggregate.add(&run("T5 Dark", *profit, 20.0));
+        }
+        aggregate.distributions().profit
+    }
+
+    #[test]
+    fn distribution_interpolates_percentiles() {
+        let distribution = profit_distribution(&[40.0, 10.0, 30.0, 20.0, 50.0]);
+        assert_close(distribution.median, 30.0);
+        assert_close(distribution.p10, 14.0);
+        assert_close(distribution.p25, 20.0);
+        assert_close(distribution.p75, 40.0);
+        assert_close(distribution.p90, 46.0);
+        // 표본 표준편차: sqrt(1000 / 4)
+        assert_close(distribution.std_dev, 250f64.sqrt());
+
+        let even = profit_distribution(&[1.0, 2.0, 3.0, 4.0]);
+        assert_close(even.median, 2.5);
+        assert_close(even.p10, 1.3);
+    }
+
+    #[test]
+    fn distribution_handles_small_and_invalid_inputs() {
+        let empty = profit_distribution(&[]);
+        assert_eq!((empty.median, empty.std_dev, empty.p90), (0.0, 0.0, 0.0));
+
+        let single = profit_distribution(&[7.0]);
+        assert_eq!((single.median, single.p10, single.p90, single.std_dev), (7.0, 7.0, 7.0, 0.0));
+
+        let with_nan = profit_distribution(&[f64::NAN, 1.0, f64::INFINITY, 3.0]);
+        assert_close(with_nan.median, 2.0);
+    }
+
+    #[test]
+    fn weighted_iskph_divides_total_profit_by_total_time() {
+        let runs = [run("T5 Dark", 10_000_000.0, 20.0), run("T5 Dark", 50_000_000.0, 40.0)];
+        let refs: Vec<&RunData> = runs.iter().collect();
+        let period = AbyssalDataAnalyzer::period_stats(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), &refs);
+        // 6천만 ISK / 1시간 (런별 ISK/h 평균인 5,250만과 다름)
+        assert_close(period.weighted_iskph, 60_000_000.0);
+        assert_eq!(period.runs_count, 2);
+        assert_close(period.total_time, 60.0);
+        let arcs: Vec<Arc<RunData>> = runs.iter().cloned().map(Arc::new).collect();
+        let daily = AbyssalDataAnalyzer::daily_stats(&arcs.iter().collect::<Vec<_>>());
+        assert_close(daily.avg_iskph, (30_000_000.0 + 75_000_000.0) / 2.0);
+        assert_close(daily.weighted_iskph, 60_000_000.0);
+
+        assert_eq!(RunAggregate::default().weighted_iskph(), 0.0);
+        let zero_time = run("T5 Dark", 1_000.0, 0.0);
+        assert_eq!(OverallAggregate::from_runs([&zero_time]).all.weighted_iskph(), 0.0);
+    }
+
+    #[test]
+    fn aggregate_add_and_remove_match_full_rebuild() {
+        let runs = [run("T5 Dark", 10.0, 20.0), run("T5 Dark", 30.0, 20.0), run("T6 Firestorm", 50.0, 10.0), run("T5 Dark", 30.0, 15.0)];
+        let mut incremental = OverallAggregate::default();
+        runs.iter().for_each(|run| incremental.add(run));
+        incremental.remove(&runs[1]);
+        incremental.remove(&runs[2]);
+
+        let rebuilt = OverallAggregate::from_runs([&runs[0], &runs[3]]);
+        let (incremental, rebuilt) = (incremental.stats(), rebuilt.stats());
+        assert_close(incremental.avg_isk, rebuilt.avg_isk);
+        assert_close(incremental.weighted_iskph, rebuilt.weighted_iskph);
+        assert_close(incremental.distributions.profit.median, rebuilt.distributions.profit.median);
+        assert_close(incremental.distributions.iskph.std_dev, rebuilt.distributions.iskph.std_dev);
+        // 런이 모두 빠진 티어/웨더는 사라짐
+        assert_eq!(incremental.tier_weather_stats.len(), 1);
+        assert_eq!(incremental.tier_weather_stats[0].runs_count, 2);
+    }
+
+    #[test]
+    fn slices_runs_by_statistics_date() {
+        let runs: Vec<Arc<RunData>> = ["2024-04-30", "2024-05-01", "2024-05-01", "2024-05-03"].iter()
+            .map(|date| Arc::new(RunData { date: date.to_string(), ..run("T5 Dark", 1.0, 1.0) }))
+            .collect();
+        assert_eq!(AbyssalDataAnalyzer::slice_between(&runs, "2024-05-01", "2024-05-01").len(), 2);
+        assert_eq!(AbyssalDataAnalyzer::slice_between(&runs, "2024-05-01", "2024-05-31").len(), 3);
+        assert!(AbyssalDataAnalyzer::slice_between(&runs, "2024-05-02", "2024-05-02").is_empty());
+    }
+
+    #[test]
+    fn tier_weather_stats_group_by_type() {
+        let runs = [run("T5 Dark", 10.0, 20.0), run("T5 Dark", 30.0, 20.0), run("T6 Firestorm", 50.0, 10.0), run("Unknown", 1.0, 1.0)];
+        let mut stats = OverallAggregate::from_runs(&runs).tier_weather_stats();
+        stats.sort_by(|a, b| a.tier.cmp(&b.tier));
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].tier.as_str(), stats[0].weather.as_str(), stats[0].runs_count), ("T5", "Dark", 2));
+        assert_close(stats[0].avg_isk, 20.0);
+        assert_close(stats[0].distributions.profit.median, 20.0);
+    }
+
+    #[test]
+    fn periods_use_iso_weeks_and_calendar_months() {
+        let (key, start, end) = AbyssalDataAnalyzer::week_of(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
+        assert_eq!(key, "2020-W53");
+        assert_eq!((start, end), (NaiveDate::from_ymd_opt(2020, 12, 28).unwrap(), NaiveDate::from_ymd_opt(2021, 1, 3).unwrap()));
+
+        let (key, start, end) = AbyssalDataAnalyzer::month_of(NaiveDate::from_ymd_opt(2024, 2, 15).unwrap());
+        assert_eq!(key, "2024-02");
+        assert_eq!((start, end), (NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()));
+        assert_eq!(AbyssalDataAnalyzer::month_of(NaiveDate::from_ymd_opt(2024, 12, 3).unwrap()).2, NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
     }
 }
@@ -1,20 +1,56 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager};
 use std::fs;
+use std::io::Write;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use polars::prelude::*;
 use regex::Regex;
-use chrono::{DateTime, Local};
-use std::ops::{BitAnd, Not};
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
 use csv;
 use log::*;
 
+use crate::time_display::{self, TimeDisplay};
+
+const DATABASE_FILE_NAME: &str = "abyssal_results.db";
+const ABYSSAL_WEATHERS: [&str; 5] = ["Exotic", "Firestorm", "Gamma", "Dark", "Electrical"];
+const CSV_IMPORTED_META_KEY: &str = "csv_imported";
+const RUN_IDS_ASSIGNED_META_KEY: &str = "run_ids_assigned";
+const UTC_TIMES_META_KEY: &str = "utc_times_converted";
+// 런 ID로 한 번에 조회하는 개수 (SQLite 바인딩 변수 개수 제한)
+const RUN_ID_QUERY_CHUNK: usize = 500;
+
+// 일별 CSV 파일 컬럼 순서 (시각은 UTC, 파일은 하루 기준 설정에 따른 날짜로 구분)
+const CSV_COLUMNS: [&str; 8] = [
+    "시작시각(UTC)",
+    "종료시각(UTC)",
+    "런 소요(초)",
+    "런 소요(분)",
+    "어비셜 종류",
+    "함급",
+    "획득 아이템",
+    "런 ID",
+];
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AbyssalResult {
-    #[serde(rename = "시작시각(KST)")]
-    pub start_time_kst: String,
-    #[serde(rename = "종료시각(KST)")]
-    pub end_time_kst: String,
+    #[serde(rename = "런 ID")]
+    pub run_id: String,
+    #[serde(rename = "시작시각(UTC)")]
+    pub start_time_utc: String,
+    #[serde(rename = "종료시각(UTC)")]
+    pub end_time_utc: String,
+    // 표시 시간대로 변환한 시각 (저장하지 않음)
+    #[serde(rename = "시작시각")]
+    pub start_time: String,
+    #[serde(rename = "종료시각")]
+    pub end_time: String,
+    // 하루 기준을 적용한 통계 날짜 (저장하지 않음)
+    #[serde(rename = "날짜")]
+    pub date: String,
     #[serde(rename = "런 소요(초)")]
     pub run_time_seconds: f64,
     #[serde(rename = "런 소요(분)")]
@@ -27,14 +63,25 @@ pub struct AbyssalResult {
     pub acquired_items: String,
 }
 
+// 분석기가 마지막으로 읽은 이후 저장/수정/삭제된 런
+#[derive(Debug, Default)]
+pub struct RunChanges {
+    pub run_ids: HashSet<String>,
+    pub all: bool, // 표시 시간대나 하루 기준이 바뀌어 모든 런을 다시 읽어야 함
+}
+
 #[derive(Clone)]
 pub struct AbyssalDataManager {
     app_handle: AppHandle,
     data_dir_path: PathBuf,
+    db_path: PathBuf,
+    csv_recovery_done: Arc<AtomicBool>,
+    time_display: TimeDisplay,
+    run_changes: Arc<Mutex<RunChanges>>,
 }
 
 impl AbyssalDataManager {
-    pub fn new(app_handle: AppHandle) -> Self {
+    pub fn new(app_handle: AppHandle, time_display: TimeDisplay) -> Self {
         // 앱 데이터 디렉토리 사용 (설치된 앱에서 안전한 위치)
         let data_dir_path = match app_handle.path().app_data_dir() {
             Ok(app_data_dir) => {
@@ -52,254 +99,732 @@ impl AbyssalDataManager {
                 PathBuf::from("data")
             }
         };
+        let db_path = data_dir_path.join(DATABASE_FILE_NAME);
 
-        AbyssalDataManager {
+        let manager = AbyssalDataManager {
             app_handle,
             data_dir_path,
+            db_path,
+            csv_recovery_done: Arc::new(AtomicBool::new(false)),
+            time_display,
+            run_changes: Arc::new(Mutex::new(RunChanges::default())),
+        };
+
+        // SQLite 스키마 생성 및 기존 CSV 1회 가져오기
+        if let Err(e) = manager.initialize_database() {
+            error!("Failed to initialize abyssal results database: {}", e);
         }
+
+        manager
     }
 
-    pub fn load_abyssal_results(&self) -> Result<DataFrame, String> {
-        if !self.data_dir_path.exists() {
-            return Err("Data directory does not exist".to_string());
+    fn open_database(&self) -> Result<Connection, String> {
+        fs::create_dir_all(&self.data_dir_path)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| format!("Failed to open database {}: {}", self.db_path.display(), e))?;
+
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = FULL;
+             CREATE TABLE IF NOT EXISTS abyssal_runs (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 run_id TEXT,
+                 start_time TEXT NOT NULL,
+                 end_time TEXT NOT NULL,
+                 run_time_seconds REAL NOT NULL,
+                 run_time_minutes REAL NOT NULL,
+                 abyssal_type TEXT NOT NULL,
+                 ship_class INTEGER NOT NULL DEFAULT 1,
+                 acquired_items TEXT NOT NULL DEFAULT ''
+             );
+             CREATE INDEX IF NOT EXISTS idx_abyssal_runs_start_time ON abyssal_runs(start_time);
+             CREATE TABLE IF NOT EXISTS meta (
+                 key TEXT PRIMARY KEY,
+                 value TEXT NOT NULL
+             );",
+        )
+        .map_err(|e| format!("Failed to create database schema: {}", e))?;
+
+        Ok(conn)
+    }
+
+    fn has_meta(conn: &Connection, key: &str) -> Result<bool, String> {
+        conn.query_row("SELECT value FROM meta WHERE key = ?1", params![key], |row| row.get::<_, String>(0))
+            .optional()
+            .map(|value| value.is_some())
+            .map_err(|e| format!("Failed to read database metadata: {}", e))
+    }
+
+    // 한 번만 실행하는 작업(CSV 가져오기, 마이그레이션)의 완료 시각 기록
+    fn set_meta(conn: &Connection, key: &str) -> Result<(), String> {
+        conn.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+            params![key, time_display::format_utc(Utc::now())],
+        )
+        .map_err(|e| format!("Failed to record {}: {}", key, e))?;
+        Ok(())
+    }
+
+    pub fn time_display(&self) -> TimeDisplay {
+        self.time_display
+    }
+
+    // 표시 시간대나 하루 기준 변경 시 날짜 구분이 바뀌므로 일별 CSV 전체를 다시 작성
+    pub fn set_time_display(&mut self, time_display: TimeDisplay) -> Result<(), String> {
+        self.time_display = time_display;
+        if let Ok(mut changes) = self.run_changes.lock() {
+            changes.all = true;
+        }
+        self.rebuild_daily_csvs()
+    }
+
+    fn mark_run_changed(&self, run_id: &str) {
+        if let Ok(mut changes) = self.run_changes.lock() {
+            changes.run_ids.insert(run_id.to_string());
+        }
+    }
+
+    // 마지막 호출 이후 바뀐 런 목록을 가져오고 비움
+    pub fn take_run_changes(&self) -> RunChanges {
+        self.run_changes.lock()
+            .map(|mut changes| std::mem::take(&mut *changes))
+            .unwrap_or_default()
+    }
+
+    // 이전 버전이 KST로 저장한 시각을 UTC로 변환 (DB당 한 번, 변환할 수 없는 행은 그대로 둠)
+    fn ensure_utc_times(conn: &mut Connection) -> Result<(), String> {
+        if Self::has_meta(conn, UTC_TIMES_META_KEY)? {
+            return Ok(());
         }
 
-        let mut all_dataframes = Vec::new();
+        let tx = conn.transaction()
+            .map_err(|e| format!("Failed to start time migration: {}", e))?;
+        let legacy_rows: Vec<(i64, String, String)> = tx
+            .prepare("SELECT id, start_time, end_time FROM abyssal_runs WHERE start_time NOT LIKE '%Z' OR end_time NOT LIKE '%Z'")
+            .and_then(|mut stmt| stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?.collect())
+            .map_err(|e| format!("Failed to find runs with legacy times: {}", e))?;
+
+        let mut converted = 0usize;
+        for (id, start_time, end_time) in &legacy_rows {
+            let to_utc = |value: &str| time_display::parse_utc(value).or_else(|| time_display::parse_legacy_kst(value));
+            match (to_utc(start_time), to_utc(end_time)) {
+                (Some(start_utc), Some(end_utc)) => {
+                    tx.execute(
+                        "UPDATE abyssal_runs SET start_time = ?1, end_time = ?2 WHERE id = ?3",
+                        params![time_display::format_utc(start_utc), time_display::format_utc(end_utc), id],
+                    )
+                    .map_err(|e| format!("Failed to migrate run times: {}", e))?;
+                    converted += 1;
+                }
+                _ => warn!("Warning: Could not convert times of run {} ('{}' ~ '{}') to UTC", id, start_time, end_time),
+            }
+        }
+        Self::set_meta(&tx, UTC_TIMES_META_KEY)?;
+        tx.commit().map_err(|e| format!("Failed to commit time migration: {}", e))?;
+
+        if !legacy_rows.is_empty() {
+            info!("Converted {} of {} runs from KST to UTC", converted, legacy_rows.len());
+        }
+        Ok(())
+    }
+
+    // run_id 컬럼이 없는 DB에 컬럼과 인덱스 추가 (DB당 한 번)
+    // ID가 없는 행은 시작할 때마다 ID 부여 - 수정/삭제할 수 없는 행이 남지 않도록 (없으면 인덱스 조회 한 번)
+    fn ensure_run_ids(conn: &mut Connection) -> Result<(), String> {
+        let tx = conn.transaction()
+            .map_err(|e| format!("Failed to start run_id migration: {}", e))?;
+        if !Self::has_meta(&tx, RUN_IDS_ASSIGNED_META_KEY)? {
+            let has_run_id = tx
+                .prepare("SELECT 1 FROM pragma_table_info('abyssal_runs') WHERE name = 'run_id'")
+                .and_then(|mut stmt| stmt.exists([]))
+                .map_err(|e| format!("Failed to inspect database schema: {}", e))?;
+            if !has_run_id {
+                tx.execute("ALTER TABLE abyssal_runs ADD COLUMN run_id TEXT", [])
+                    .map_err(|e| format!("Failed to add run_id column: {}", e))?;
+            }
+            tx.execute("CREATE UNIQUE INDEX IF NOT EXISTS idx_abyssal_runs_run_id ON abyssal_runs(run_id)", [])
+                .map_err(|e| format!("Failed to create run_id index: {}", e))?;
+            Self::set_meta(&tx, RUN_IDS_ASSIGNED_META_KEY)?;
+        }
+
+        let missing: Vec<i64> = tx
+            .prepare("SELECT id FROM abyssal_runs WHERE run_id IS NULL OR run_id = ''")
+            .and_then(|mut stmt| stmt.query_map([], |row| row.get(0))?.collect())
+            .map_err(|e| format!("Failed to find runs without run_id: {}", e))?;
+        for id in &missing {
+            tx.execute("UPDATE abyssal_runs SET run_id = ?1 WHERE id = ?2", params![Self::new_run_id(), id])
+                .map_err(|e| format!("Failed to backfill run_id: {}", e))?;
+        }
+        tx.commit().map_err(|e| format!("Failed to commit run_id migration: {}", e))?;
+
+        if !missing.is_empty() {
+            info!("Assigned run IDs to {} existing runs", missing.len());
+        }
+        Ok(())
+    }
+
+    pub fn new_run_id() -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+
+    fn initialize_database(&self) -> Result<(), String> {
+        let mut conn = self.open_database()?;
+
+        // 이전 버전 DB 마이그레이션 (스키마 변경과 시각 변환은 각각 한 번만)
+        Self::ensure_run_ids(&mut conn)?;
+        Self::ensure_utc_times(&mut conn)?;
+
+        if Self::has_meta(&conn, CSV_IMPORTED_META_KEY)? {
+            return Ok(());
+        }
+
+        // 기존 일별 CSV 파일을 한 트랜잭션으로 가져오기
+        let csv_files = self.list_csv_files()?;
+        let tx = conn.transaction()
+            .map_err(|e| format!("Failed to start import transaction: {}", e))?;
+        let mut imported_rows = 0usize;
+        let mut seen_run_ids = std::collections::HashSet::new();
+        for path in &csv_files {
+            let rows = match Self::read_csv_rows(path) {
+                Ok(rows) => rows,
+                Err(e) => {
+                    warn!("Warning: Skipping CSV {} during import: {}", path.display(), e);
+                    continue;
+                }
+            };
+            for mut row in rows.iter().cloned() {
+                // 복사된 파일 등으로 중복된 런 ID는 새로 부여
+                if !seen_run_ids.insert(row.run_id.clone()) {
+                    row.run_id = Self::new_run_id();
+                    seen_run_ids.insert(row.run_id.clone());
+                }
+                Self::insert_row(&tx, &row)?;
+            }
+            imported_rows += rows.len();
+        }
+        Self::set_meta(&tx, CSV_IMPORTED_META_KEY)?;
+        tx.commit().map_err(|e| format!("Failed to commit CSV import: {}", e))?;
+
+        info!("Imported {} runs from {} CSV files into {}", imported_rows, csv_files.len(), self.db_path.display());
+        Ok(())
+    }
+
+    fn list_csv_files(&self) -> Result<Vec<PathBuf>, String> {
+        if !self.data_dir_path.exists() {
+            return Ok(Vec::new());
+        }
 
-        // Read all CSV files that match the pattern abyssal_results_*.csv
         let entries = fs::read_dir(&self.data_dir_path)
             .map_err(|e| format!("Failed to read data directory: {}", e))?;
 
+        let mut files = Vec::new();
         for entry in entries {
             let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
             let path = entry.path();
-            
             if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
                 if file_name.starts_with("abyssal_results_") && file_name.ends_with(".csv") {
-                    // 기존 CSV 파일의 헤더를 먼저 확인
-                    let mut csv_reader = csv::Reader::from_path(&path)
-                        .map_err(|e| format!("Failed to open CSV file {}: {}", file_name, e))?;
-                    
-                    let headers = csv_reader.headers()
-                        .map_err(|e| format!("Failed to read CSV headers from {}: {}", file_name, e))?
-                        .clone();
-                    
-                    let has_ship_class = headers.iter().any(|h| h == "함급");
-                    
-                    // Polars로 CSV 읽기 (더 관대한 설정 사용)
-                    match CsvReader::from_path(&path)
-                        .map_err(|e| format!("Failed to open data file {}: {}", file_name, e))?
-                        .has_header(true)
-                        .with_ignore_errors(true)  // 에러 무시하고 계속 읽기
-                        .finish()
-                    {
-                        Ok(mut df) => {
-                            df = df.select([
-                                "시작시각(KST)",
-                                "종료시각(KST)",
-                                "런 소요(초)",
-                                "런 소요(분)",
-                                "어비셜 종류",
-                                "함급",
-                                "획득 아이템",
-                            ])
-                            .map_err(|e| format!("Failed to reorder columns: {}", e))?;
-                            
-                            all_dataframes.push(df);
-                        },
-                        Err(e) => warn!("Warning: Failed to read CSV {}: {}", file_name, e),
-                    }
+                    files.push(path);
                 }
             }
         }
+        files.sort();
+        Ok(files)
+    }
+
+    // 일별 CSV 파일 읽기 (함급 컬럼이 없는 구버전 파일은 함급 1로, KST 시각은 UTC로 변환)
+    fn read_csv_rows(path: &Path) -> Result<Vec<AbyssalResult>, String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_path(path)
+            .map_err(|e| format!("Failed to open CSV file: {}", e))?;
+
+        let headers = reader.headers()
+            .map_err(|e| format!("Failed to read CSV headers: {}", e))?
+            .clone();
+        let column_index = |name: &str| headers.iter().position(|h| h.trim_start_matches('\u{feff}') == name);
+
+        let is_legacy_kst = column_index("시작시각(UTC)").is_none();
+        let (start_column, end_column) = if is_legacy_kst {
+            ("시작시각(KST)", "종료시각(KST)")
+        } else {
+            ("시작시각(UTC)", "종료시각(UTC)")
+        };
+        let start_idx = column_index(start_column).ok_or_else(|| format!("Missing {} column", start_column))?;
+        let end_idx = column_index(end_column).ok_or_else(|| format!("Missing {} column", end_column))?;
+        let seconds_idx = column_index("런 소요(초)");
+        let minutes_idx = column_index("런 소요(분)");
+        let type_idx = column_index("어비셜 종류").ok_or("Missing 어비셜 종류 column")?;
+        let ship_class_idx = column_index("함급");
+        let items_idx = column_index("획득 아이템");
+        let run_id_idx = column_index("런 ID");
+
+        let mut rows = Vec::new();
+        for (line, record) in reader.records().enumerate() {
+            let record = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    warn!("Warning: Skipping malformed row {} in {}: {}", line + 2, path.display(), e);
+                    continue;
+                }
+            };
+            let field = |idx: Option<usize>| idx.and_then(|i| record.get(i)).unwrap_or("").trim().to_string();
+
+            let parse_time = |value: &str| if is_legacy_kst {
+                time_display::parse_legacy_kst(value)
+            } else {
+                time_display::parse_utc(value)
+            };
+            let (start_utc, end_utc) = match (parse_time(&field(Some(start_idx))), parse_time(&field(Some(end_idx)))) {
+                (Some(start_utc), Some(end_utc)) => (start_utc, end_utc),
+                _ => {
+                    warn!("Warning: Skipping row {} in {} with invalid times", line + 2, path.display());
+                    continue;
+                }
+            };
+
+            let run_time_seconds = field(seconds_idx).parse::<f64>().unwrap_or(0.0);
+            let run_time_minutes = field(minutes_idx).parse::<f64>()
+                .unwrap_or((run_time_seconds / 60.0 * 100.0).round() / 100.0);
+
+            // 런 ID가 없는 구버전 행은 새 ID 부여
+            let run_id = Some(field(run_id_idx))
+                .filter(|id| !id.is_empty())
+                .unwrap_or_else(Self::new_run_id);
+
+            rows.push(AbyssalResult {
+                run_id,
+                start_time_utc: time_display::format_utc(start_utc),
+                end_time_utc: time_display::format_utc(end_utc),
+                start_time: String::new(),
+                end_time: String::new(),
+                date: String::new(),
+                run_time_seconds,
+                run_time_minutes,
+                abyssal_type: field(Some(type_idx)),
+                ship_class: field(ship_class_idx).parse::<f64>().map(|v| v as i32).unwrap_or(1),
+                acquired_items: field(items_idx),
+            });
+        }
+
+        Ok(rows)
+    }
+
+    fn insert_row(conn: &Connection, row: &AbyssalResult) -> Result<(), String> {
+        conn.execute(
+            "INSERT INTO abyssal_runs (run_id, start_time, end_time, run_time_seconds, run_time_minutes, abyssal_type, ship_class, acquired_items)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                row.run_id,
+                row.start_time_utc,
+                row.end_time_utc,
+                row.run_time_seconds,
+                row.run_time_minutes,
+                row.abyssal_type,
+                row.ship_class,
+                row.acquired_items,
+            ],
+        )
+        .map_err(|e| format!("Failed to insert run: {}", e))?;
+        Ok(())
+    }
+
+    fn query_rows(&self, conn: &Connection, where_clause: &str, args: &[&dyn rusqlite::ToSql]) -> Result<Vec<AbyssalResult>, String> {
+        let sql = format!(
+            "SELECT run_id, start_time, end_time, run_time_seconds, run_time_minutes, abyssal_type, ship_class, acquired_items
+             FROM abyssal_runs {} ORDER BY start_time, id",
+            where_clause
+        );
+        let mut stmt = conn.prepare(&sql)
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+        let rows = stmt.query_map(args, |row| {
+            let start_time_utc: String = row.get(1)?;
+            let end_time_utc: String = row.get(2)?;
+            Ok(AbyssalResult {
+                run_id: row.get(0)?,
+                start_time: self.display_time(&start_time_utc),
+                end_time: self.display_time(&end_time_utc),
+                date: time_display::parse_utc(&start_time_utc)
+                    .map(|utc| self.time_display.day_of(utc).format("%Y-%m-%d").to_string())
+                    .unwrap_or_default(),
+                start_time_utc,
+                end_time_utc,
+                run_time_seconds: row.get(3)?,
+                run_time_minutes: row.get(4)?,
+                abyssal_type: row.get(5)?,
+                ship_class: row.get(6)?,
+                acquired_items: row.get(7)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query runs: {}", e))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read run row: {}", e))
+    }
+
+    fn display_time(&self, utc_str: &str) -> String {
+        time_display::parse_utc(utc_str)
+            .map(|utc| self.time_display.format(utc))
+            .unwrap_or_else(|| utc_str.to_string())
+    }
+
+    // 하루 기준 설정에 따라 해당 날짜에 시작한 런
+    fn query_rows_for_date(&self, conn: &Connection, date: NaiveDate) -> Result<Vec<AbyssalResult>, String> {
+        let (day_start, day_end) = self.time_display.day_range_utc(date);
+        let day_start = time_display::format_utc(day_start);
+        let day_end = time_display::format_utc(day_end);
+        self.query_rows(conn, "WHERE start_time >= ?1 AND start_time < ?2", &[&day_start, &day_end])
+    }
+
+    // 모든 런 (시작시각 순)
+    pub fn load_abyssal_runs(&self) -> Result<Vec<AbyssalResult>, String> {
+        let conn = self.open_database()?;
+
+        // 프로세스당 한 번, 이전 실행에서 중단된 CSV 쓰기 복구
+        if !self.csv_recovery_done.swap(true, Ordering::SeqCst) {
+            if let Err(e) = self.recover_daily_csvs(&conn) {
+                warn!("Failed to recover daily CSV files: {}", e);
+            }
+        }
+        self.query_rows(&conn, "", &[])
+    }
+
+    // 지정한 런 ID의 런 (삭제된 런은 결과에 없음)
+    pub fn load_abyssal_runs_by_id(&self, run_ids: &HashSet<String>) -> Result<Vec<AbyssalResult>, String> {
+        let conn = self.open_database()?;
+        let run_ids: Vec<&String> = run_ids.iter().collect();
+        let mut rows = Vec::with_capacity(run_ids.len());
+        for chunk in run_ids.chunks(RUN_ID_QUERY_CHUNK) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let args: Vec<&dyn rusqlite::ToSql> = chunk.iter().map(|id| *id as &dyn rusqlite::ToSql).collect();
+            rows.extend(self.query_rows(&conn, &format!("WHERE run_id IN ({})", placeholders), &args)?);
+        }
+        Ok(rows)
+    }
+
+    pub fn load_abyssal_results(&self) -> Result<DataFrame, String> {
+        let rows = self.load_abyssal_runs()?;
+
+        DataFrame::new(vec![
+            Series::new("런 ID", rows.iter().map(|r| r.run_id.clone()).collect::<Vec<_>>()),
+            Series::new("시작시각(UTC)", rows.iter().map(|r| r.start_time_utc.clone()).collect::<Vec<_>>()),
+            Series::new("종료시각(UTC)", rows.iter().map(|r| r.end_time_utc.clone()).collect::<Vec<_>>()),
+            Series::new("시작시각", rows.iter().map(|r| r.start_time.clone()).collect::<Vec<_>>()),
+            Series::new("날짜", rows.iter().map(|r| r.date.clone()).collect::<Vec<_>>()),
+            Series::new("종료시각", rows.iter().map(|r| r.end_time.clone()).collect::<Vec<_>>()),
+            Series::new("런 소요(초)", rows.iter().map(|r| r.run_time_seconds).collect::<Vec<_>>()),
+            Series::new("런 소요(분)", rows.iter().map(|r| r.run_time_minutes).collect::<Vec<_>>()),
+            Series::new("어비셜 종류", rows.iter().map(|r| r.abyssal_type.clone()).collect::<Vec<_>>()),
+            Series::new("함급", rows.iter().map(|r| r.ship_class as i64).collect::<Vec<_>>()),
+            Series::new("획득 아이템", rows.iter().map(|r| r.acquired_items.clone()).collect::<Vec<_>>()),
+        ]).map_err(|e| format!("Failed to create DataFrame: {}", e))
+    }
+
+    // 저장된 런의 ID를 반환
+    pub fn save_abyssal_result(&self, start_time: DateTime<Utc>, end_time: DateTime<Utc>, acquired_items: String, abyssal_type: String, ship_class: i32) -> Result<String, String> {
+        self.validate_run(start_time, end_time, &abyssal_type, ship_class)?;
+
+        let row = self.build_row(Self::new_run_id(), start_time, end_time, &acquired_items, abyssal_type, ship_class);
+
+        let conn = self.open_database()?;
+        Self::insert_row(&conn, &row)?;
+        self.mark_run_changed(&row.run_id);
+
+        // 날짜별 CSV 파일에 행 추가 (Python과 일치)
+        // CSV는 DB의 사본이므로 실패해도 저장은 성공 - 다음 로드 시 recover_daily_csvs가 DB 기준으로 다시 작성
+        if let Err(e) = self.append_daily_csv(&conn, &row) {
+            warn!("Failed to mirror run {} to daily CSV: {}", row.run_id, e);
+            self.csv_recovery_done.store(false, Ordering::SeqCst);
+        }
+
+        Ok(row.run_id)
+    }
+
+    // 저장된 런의 아이템, 어비셜 종류, 함급, 시작/종료 시각 수정
+    pub fn update_abyssal_run(&self, run_id: &str, start_time: DateTime<Utc>, end_time: DateTime<Utc>, acquired_items: String, abyssal_type: String, ship_class: i32) -> Result<(), String> {
+        self.validate_run(start_time, end_time, &abyssal_type, ship_class)?;
+
+        let conn = self.open_database()?;
+
+        let old_start_utc: String = conn
+            .query_row("SELECT start_time FROM abyssal_runs WHERE run_id = ?1", params![run_id], |row| row.get(0))
+            .optional()
+            .map_err(|e| format!("Failed to look up run: {}", e))?
+            .ok_or_else(|| format!("Run not found: {}", run_id))?;
+
+        let row = self.build_row(run_id.to_string(), start_time, end_time, &acquired_items, abyssal_type, ship_class);
+        conn.execute(
+            "UPDATE abyssal_runs
+             SET start_time = ?1, end_time = ?2, run_time_seconds = ?3, run_time_minutes = ?4,
+                 abyssal_type = ?5, ship_class = ?6, acquired_items = ?7
+             WHERE run_id = ?8",
+            params![
+                row.start_time_utc,
+                row.end_time_utc,
+                row.run_time_seconds,
+                row.run_time_minutes,
+                row.abyssal_type,
+                row.ship_class,
+                row.acquired_items,
+                row.run_id,
+            ],
+        )
+        .map_err(|e| format!("Failed to update run: {}", e))?;
+        self.mark_run_changed(run_id);
+
+        // 날짜가 바뀐 경우 이전 날짜 파일도 갱신
+        let new_date = self.time_display.day_of(start_time);
+        if let Some(old_date) = time_display::parse_utc(&old_start_utc).map(|utc| self.time_display.day_of(utc)) {
+            if old_date != new_date {
+                self.write_daily_csv(&conn, old_date)?;
+            }
+        }
+        self.write_daily_csv(&conn, new_date)
+    }
+
+    fn validate_run(&self, start_time: DateTime<Utc>, end_time: DateTime<Utc>, abyssal_type: &str, ship_class: i32) -> Result<(), String> {
+        if end_time <= start_time {
+            return Err(format!("End time {} must be after start time {}", self.time_display.format(end_time), self.time_display.format(start_time)));
+        }
 
-        if all_dataframes.is_empty() {
-            // Create an empty DataFrame with the expected schema if no files exist
-            return Ok(DataFrame::new(vec![
-                Series::new("시작시각(KST)", Vec::<String>::new()),
-                Series::new("종료시각(KST)", Vec::<String>::new()),
-                Series::new("런 소요(초)", Vec::<f64>::new()),
-                Series::new("런 소요(분)", Vec::<f64>::new()),
-                Series::new("어비셜 종류", Vec::<String>::new()),
-                Series::new("함급", Vec::<i32>::new()),
-                Series::new("획득 아이템", Vec::<String>::new()),
-            ]).map_err(|e| format!("Failed to create empty DataFrame: {}", e))?);
+        let weather = abyssal_type.split_whitespace().nth(1).unwrap_or("");
+        if self.abyssal_type_to_filament_name(abyssal_type).is_none() || !ABYSSAL_WEATHERS.contains(&weather) {
+            return Err(format!("Invalid abyssal type: '{}'", abyssal_type));
         }
 
-        // Concatenate all dataframes
-        let combined_df = all_dataframes.into_iter().reduce(|acc, df| {
-            acc.vstack(&df).unwrap_or(acc)
-        }).unwrap();
+        // 1: 크루저, 2: 디스트로이어, 3: 프리깃
+        if !(1..=3).contains(&ship_class) {
+            return Err(format!("Invalid ship class: {}", ship_class));
+        }
 
-        Ok(combined_df)
+        Ok(())
     }
 
-    pub fn save_abyssal_result(&self, start_time: DateTime<Local>, end_time: DateTime<Local>, acquired_items: String, abyssal_type: String, ship_class: i32) -> Result<(), String> {
+    fn build_row(&self, run_id: String, start_time: DateTime<Utc>, end_time: DateTime<Utc>, acquired_items: &str, abyssal_type: String, ship_class: i32) -> AbyssalResult {
         let items = acquired_items.trim();
-        
-        // 빈 아이템이어도 저장 - 아무것도 얻지 못한 런도 기록
-        let items = if items.is_empty() {
-            ""  // 빈 문자열로 저장
-        } else {
-            items
-        };
 
+        // 빈 아이템이어도 저장 - 아무것도 얻지 못한 런도 기록
         // 아이템 파싱 테스트 (빈 아이템도 허용)
         let _parsed_items = self.parse_items(items);
 
-        // data 디렉토리 생성
-        fs::create_dir_all(&self.data_dir_path)
-            .map_err(|e| format!("Failed to create data directory: {}", e))?;
-
-        // 날짜별 파일명 생성 (Python과 일치)
-        let date_str = start_time.format("%Y-%m-%d").to_string();
-        let filename = format!("abyssal_results_{}.csv", date_str);
-        let data_file_path = self.data_dir_path.join(&filename);
-        
-        let file_exists = data_file_path.exists();
-        
         // 지속시간 계산 (Python과 일치)
         let duration = end_time - start_time;
         let duration_sec = duration.num_seconds() as f64;
         let duration_min = (duration_sec / 60.0 * 100.0).round() / 100.0; // 소수점 2자리 반올림
-        
+
         // 아이템 문자열 정규화 (Python과 일치)
         let items = items.replace('\n', "; ").replace('\r', "");
-        
-        // 새 행 데이터 생성 (구조체 필드 순서와 일치)
-        let new_row_df = DataFrame::new(vec![
-            Series::new("시작시각(KST)", &[start_time.format("%Y-%m-%d %H:%M:%S").to_string()]),
-            Series::new("종료시각(KST)", &[end_time.format("%Y-%m-%d %H:%M:%S").to_string()]),
-            Series::new("런 소요(초)", &[duration_sec as i64]),
-            Series::new("런 소요(분)", &[duration_min]),
-            Series::new("어비셜 종류", &[abyssal_type.clone()]),
-            Series::new("함급", &[ship_class]),
-            Series::new("획득 아이템", &[items.clone()]),
-        ]).map_err(|e| format!("Failed to create new row DataFrame: {}", e))?;
-
-        if file_exists {
-            // 기존 파일의 헤더를 먼저 확인
-            let mut csv_reader = csv::Reader::from_path(&data_file_path)
-                .map_err(|e| format!("Failed to open existing CSV file: {}", e))?;
-            
-            let headers = csv_reader.headers()
-                .map_err(|e| format!("Failed to read CSV headers: {}", e))?
-                .clone();
-            
-            let has_ship_class = headers.iter().any(|h| h == "함급");
-            
-            // 기존 파일에 추가
-            let mut existing_df = CsvReader::from_path(&data_file_path)
-                .map_err(|e| format!("Failed to open existing data file: {}", e))?
-                .has_header(true)
-                .with_ignore_errors(true)  // 에러 무시하고 계속 읽기
-                .finish()
-                .map_err(|e| format!("Failed to read existing CSV: {}", e))?;
-
-            // 기존 파일에 함급 컬럼이 없으면 기본값 1로 추가
-            if !has_ship_class || existing_df.column("함급").is_err() {
-                existing_df = existing_df.lazy().with_column(lit(1i32).alias("함급")).collect()
-                    .map_err(|e| format!("Failed to add ship_class column to existing data: {}", e))?;
+
+        AbyssalResult {
+            run_id,
+            start_time_utc: time_display::format_utc(start_time),
+            end_time_utc: time_display::format_utc(end_time),
+            start_time: self.time_display.format(start_time),
+            end_time: self.time_display.format(end_time),
+            date: self.time_display.day_of(start_time).format("%Y-%m-%d").to_string(),
+            run_time_seconds: duration_sec,
+            run_time_minutes: duration_min,
+            abyssal_type,
+            ship_class,
+            acquired_items: items,
+        }
+    }
+
+    fn daily_csv_path(&self, date: NaiveDate) -> PathBuf {
+        self.data_dir_path.join(format!("abyssal_results_{}.csv", date.format("%Y-%m-%d")))
+    }
+
+    fn daily_csv_date(path: &Path) -> Option<NaiveDate> {
+        path.file_stem()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix("abyssal_results_"))
+            .and_then(|n| NaiveDate::parse_from_str(n, "%Y-%m-%d").ok())
+    }
+
+    fn csv_record(row: &AbyssalResult) -> [String; 8] {
+        [
+            row.start_time_utc.clone(),
+            row.end_time_utc.clone(),
+            row.run_time_seconds.to_string(),
+            row.run_time_minutes.to_string(),
+            row.abyssal_type.clone(),
+            row.ship_class.to_string(),
+            row.acquired_items.clone(),
+            row.run_id.clone(),
+        ]
+    }
+
+    fn is_daily_csv_intact(path: &Path) -> bool {
+        Self::daily_csv_row_count(path).is_some()
+    }
+
+    // 헤더가 현재 형식과 같고, 모든 행이 완전하며, 마지막 줄이 개행으로 끝나면 행 수 반환
+    fn daily_csv_row_count(path: &Path) -> Option<usize> {
+        let content = fs::read(path).ok()?;
+        if !content.ends_with(b"\n") {
+            return None;
+        }
+
+        let mut reader = csv::ReaderBuilder::new().from_reader(content.as_slice());
+        let header_ok = match reader.headers() {
+            Ok(headers) => headers.iter()
+                .map(|h| h.trim_start_matches('\u{feff}'))
+                .eq(CSV_COLUMNS.iter().copied()),
+            Err(_) => false,
+        };
+        if !header_ok {
+            return None;
+        }
+        let mut count = 0;
+        for record in reader.records() {
+            record.ok()?;
+            count += 1;
+        }
+        Some(count)
+    }
+
+    // 기존 행은 건드리지 않고 해당 날짜의 CSV 파일 끝에 한 행만 추가
+    fn append_daily_csv(&self, conn: &Connection, row: &AbyssalResult) -> Result<(), String> {
+        let date = match time_display::parse_utc(&row.start_time_utc) {
+            Some(start_utc) => self.time_display.day_of(start_utc),
+            None => return Err(format!("Invalid start time: {}", row.start_time_utc)),
+        };
+        let data_file_path = self.daily_csv_path(date);
+
+        // 파일이 없거나, 구버전 형식이거나, 손상된 경우 DB 기준으로 다시 작성
+        if !Self::is_daily_csv_intact(&data_file_path) {
+            return self.write_daily_csv(conn, date);
+        }
+
+        let file = fs::OpenOptions::new()
+            .append(true)
+            .open(&data_file_path)
+            .map_err(|e| format!("Failed to open data file for append: {}", e))?;
+
+        let mut writer = csv::Writer::from_writer(file);
+        writer.write_record(Self::csv_record(row))
+            .map_err(|e| format!("Failed to append CSV row: {}", e))?;
+        let file = writer.into_inner()
+            .map_err(|e| format!("Failed to append CSV row: {}", e))?;
+        file.sync_all()
+            .map_err(|e| format!("Failed to sync data file: {}", e))?;
+
+        Ok(())
+    }
+
+    // DB 내용으로 해당 날짜의 CSV 파일을 다시 작성 (임시 파일에 쓴 후 원자적으로 교체)
+    fn write_daily_csv(&self, conn: &Connection, date: NaiveDate) -> Result<(), String> {
+        let rows = self.query_rows_for_date(conn, date)?;
+        let data_file_path = self.daily_csv_path(date);
+
+        if rows.is_empty() {
+            // 모든 행이 삭제되었으면 파일 삭제
+            if data_file_path.exists() {
+                fs::remove_file(&data_file_path)
+                    .map_err(|e| format!("Failed to delete empty data file: {}", e))?;
             }
-            
-            // 컬럼 순서를 일관되게 맞추기
-            existing_df = existing_df.select([
-                "시작시각(KST)",
-                "종료시각(KST)",
-                "런 소요(초)",
-                "런 소요(분)",
-                "어비셜 종류",
-                "함급",
-                "획득 아이템",
-            ])
-            .map_err(|e| format!("Failed to reorder existing columns: {}", e))?;
-
-            // 기존 파일의 스키마와 맞추기 위해 타입 변환
-            let new_row_df = {
-                let mut needs_float64_seconds = false;
-                let mut needs_int64_ship_class = false;
-                
-                // "런 소요(초)" 컬럼 타입 체크
-                if let Ok(existing_seconds_col) = existing_df.column("런 소요(초)") {
-                    if matches!(existing_seconds_col.dtype(), polars::datatypes::DataType::Float64) {
-                        needs_float64_seconds = true;
-                    }
-                }
-                
-                // "함급" 컬럼 타입 체크
-                if let Ok(existing_ship_class_col) = existing_df.column("함급") {
-                    if matches!(existing_ship_class_col.dtype(), polars::datatypes::DataType::Int64) {
-                        needs_int64_ship_class = true;
-                    }
-                }
-                
-                // 타입 변환이 필요한 경우 새로운 DataFrame 생성
-                if needs_float64_seconds || needs_int64_ship_class {
-                    let seconds_value: Box<dyn std::any::Any> = if needs_float64_seconds {
-                        Box::new(duration_sec) // Float64
-                    } else {
-                        Box::new(duration_sec as i64) // Int64
-                    };
-                    
-                    let ship_class_value: Box<dyn std::any::Any> = if needs_int64_ship_class {
-                        Box::new(ship_class as i64) // Int64
-                    } else {
-                        Box::new(ship_class) // Int32
-                    };
-                    
-                    DataFrame::new(vec![
-                        Series::new("시작시각(KST)", &[start_time.format("%Y-%m-%d %H:%M:%S").to_string()]),
-                        Series::new("종료시각(KST)", &[end_time.format("%Y-%m-%d %H:%M:%S").to_string()]),
-                        if needs_float64_seconds {
-                            Series::new("런 소요(초)", &[duration_sec])  // Float64
-                        } else {
-                            Series::new("런 소요(초)", &[duration_sec as i64])  // Int64
-                        },
-                        Series::new("런 소요(분)", &[duration_min]),
-                        Series::new("어비셜 종류", &[abyssal_type.clone()]),
-                        if needs_int64_ship_class {
-                            Series::new("함급", &[ship_class as i64])  // Int64
-                        } else {
-                            Series::new("함급", &[ship_class])  // Int32
-                        },
-                        Series::new("획득 아이템", &[items.clone()]),
-                    ]).map_err(|e| format!("Failed to create type-matched DataFrame: {}", e))?
-                } else {
-                    new_row_df
-                }
-            };
+            return Ok(());
+        }
 
-            existing_df = existing_df.vstack(&new_row_df)
-                .map_err(|e| format!("Failed to append new row: {}", e))?;
+        let temp_file_path = data_file_path.with_extension("csv.tmp");
 
-            // UTF-8-BOM으로 저장 (Python과 일치)
-            let mut file = fs::File::create(&data_file_path)
-                .map_err(|e| format!("Failed to create data file: {}", e))?;
-            
-            // UTF-8 BOM 작성
-            use std::io::Write;
-            file.write_all(&[0xEF, 0xBB, 0xBF])
-                .map_err(|e| format!("Failed to write BOM: {}", e))?;
+        // UTF-8-BOM으로 저장 (Python과 일치)
+        let mut file = fs::File::create(&temp_file_path)
+            .map_err(|e| format!("Failed to create temporary data file: {}", e))?;
+        file.write_all(&[0xEF, 0xBB, 0xBF])
+            .map_err(|e| format!("Failed to write BOM: {}", e))?;
 
-            CsvWriter::new(file)
-                .finish(&mut existing_df)
-                .map_err(|e| format!("Failed to write CSV: {}", e))?;
-        } else {
-            // 새 파일 생성
-            let mut df = new_row_df;
-            let mut file = fs::File::create(&data_file_path)
-                .map_err(|e| format!("Failed to create data file: {}", e))?;
-
-            // UTF-8 BOM 작성
-            use std::io::Write;
-            file.write_all(&[0xEF, 0xBB, 0xBF])
-                .map_err(|e| format!("Failed to write BOM: {}", e))?;
-
-            CsvWriter::new(file)
-                .finish(&mut df)
+        let mut writer = csv::Writer::from_writer(file);
+        writer.write_record(CSV_COLUMNS)
+            .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+        for row in &rows {
+            writer.write_record(Self::csv_record(row))
                 .map_err(|e| format!("Failed to write CSV: {}", e))?;
         }
-        
+        let file = writer.into_inner()
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+        file.sync_all()
+            .map_err(|e| format!("Failed to sync temporary data file: {}", e))?;
+        drop(file);
+
+        fs::rename(&temp_file_path, &data_file_path)
+            .map_err(|e| format!("Failed to replace data file: {}", e))?;
+
+        Ok(())
+    }
+
+    // 비정상 종료로 남은 임시 파일 정리 및 손상된 일별 CSV를 DB 기준으로 복구
+    fn recover_daily_csvs(&self, conn: &Connection) -> Result<(), String> {
+        let entries = fs::read_dir(&self.data_dir_path)
+            .map_err(|e| format!("Failed to read data directory: {}", e))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_temp_file = path.file_name()
+                .and_then(|n| n.to_str())
+                .map_or(false, |n| n.starts_with("abyssal_results_") && n.ends_with(".csv.tmp"));
+            if is_temp_file {
+                warn!("Removing leftover temporary data file: {}", path.display());
+                let _ = fs::remove_file(&path);
+            }
+        }
+
+        for path in self.list_csv_files()? {
+            if Self::is_daily_csv_intact(&path) {
+                continue;
+            }
+
+            let date = match Self::daily_csv_date(&path) {
+                Some(date) => date,
+                None => continue,
+            };
+            let has_rows = !self.query_rows_for_date(conn, date)?.is_empty();
+            if has_rows {
+                warn!("Data file {} is corrupted or outdated, rewriting from database", path.display());
+                self.write_daily_csv(conn, date)?;
+            } else {
+                warn!("Data file {} is corrupted and has no matching runs in the database, leaving it untouched", path.display());
+            }
+        }
+
+        // CSV 쓰기가 실패해 DB에만 있는 런이 있으면 해당 날짜 파일을 다시 작성
+        let mut run_counts: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        for row in self.query_rows(conn, "", &[])? {
+            if let Some(start_utc) = time_display::parse_utc(&row.start_time_utc) {
+                *run_counts.entry(self.time_display.day_of(start_utc)).or_default() += 1;
+            }
+        }
+        for (date, count) in run_counts {
+            let path = self.daily_csv_path(date);
+            if Self::daily_csv_row_count(&path) != Some(count) {
+                warn!("Data file {} does not match the database, rewriting", path.display());
+                self.write_daily_csv(conn, date)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // 현재 시간대와 하루 기준으로 모든 일별 CSV를 다시 작성하고 런이 없는 날짜 파일은 삭제
+    pub fn rebuild_daily_csvs(&self) -> Result<(), String> {
+        let conn = self.open_database()?;
+        let dates: std::collections::BTreeSet<NaiveDate> = self.query_rows(&conn, "", &[])?
+            .iter()
+            .filter_map(|row| time_display::parse_utc(&row.start_time_utc))
+            .map(|utc| self.time_display.day_of(utc))
+            .collect();
+
+        for date in &dates {
+            self.write_daily_csv(&conn, *date)?;
+        }
+        for path in self.list_csv_files()? {
+            if Self::daily_csv_date(&path).map_or(false, |date| !dates.contains(&date)) {
+                fs::remove_file(&path)
+                    .map_err(|e| format!("Failed to delete outdated data file: {}", e))?;
+            }
+        }
+
+        info!("Rebuilt {} daily CSV files for timezone {}", dates.len(), self.time_display.timezone_name());
         Ok(())
     }
 
@@ -370,72 +895,111 @@ impl AbyssalDataManager {
         None
     }
 
-    pub fn delete_abyssal_run(&self, start_time_kst: &str, end_time_kst: &str) -> Result<(), String> {
-        // 시작 시간에서 날짜 추출
-        let date_str = if let Some(date_part) = start_time_kst.split(' ').next() {
-            date_part.to_string()
-        } else {
-            return Err("Invalid start time format".to_string());
-        };
-        
-        let filename = format!("abyssal_results_{}.csv", date_str);
-        let data_file_path = self.data_dir_path.join(&filename);
-        
-        if !data_file_path.exists() {
-            return Err(format!("Data file not found: {}", filename));
-        }
-        
-        // CSV 파일 읽기
-        let mut df = CsvReader::from_path(&data_file_path)
-            .map_err(|e| format!("Failed to open data file: {}", e))?
-            .has_header(true)
-            .finish()
-            .map_err(|e| format!("Failed to read CSV: {}", e))?;
-        
-        // 삭제할 행 찾기 (시작시간과 종료시간이 모두 일치하는 행)
-        let start_time_col = df.column("시작시각(KST)")
-            .map_err(|e| format!("Failed to get start time column: {}", e))?;
-        let end_time_col = df.column("종료시각(KST)")
-            .map_err(|e| format!("Failed to get end time column: {}", e))?;
-        
-        // 조건에 맞지 않는 행들만 유지 (즉, 삭제할 행 제외)
-        let mask = start_time_col.equal(start_time_kst)
-            .map_err(|e| format!("Failed to create start time mask: {}", e))?
-            .bitand(
-                end_time_col.equal(end_time_kst)
-                    .map_err(|e| format!("Failed to create end time mask: {}", e))?
-            )
-            .not(); // 조건에 맞는 행을 제외하기 위해 not() 적용
-        
-        let filtered_df = df.filter(&mask)
-            .map_err(|e| format!("Failed to filter DataFrame: {}", e))?;
-        
-        if filtered_df.height() == df.height() {
-            return Err("Run not found in the data file".to_string());
-        }
-        
-        // 필터링된 데이터를 다시 저장
-        if filtered_df.height() == 0 {
-            // 모든 행이 삭제되었으면 파일 삭제
-            fs::remove_file(&data_file_path)
-                .map_err(|e| format!("Failed to delete empty data file: {}", e))?;
-        } else {
-            // 남은 데이터를 파일에 저장
-            let mut df_to_write = filtered_df;
-            
-            // UTF-8-BOM으로 저장
-            let mut file = fs::File::create(&data_file_path)
-                .map_err(|e| format!("Failed to create data file: {}", e))?;
-            
-            use std::io::Write;
-            file.write_all(&[0xEF, 0xBB, 0xBF])
-                .map_err(|e| format!("Failed to write BOM: {}", e))?;
+    pub fn delete_abyssal_run(&self, run_id: &str) -> Result<(), String> {
+        let conn = self.open_database()?;
 
-            CsvWriter::new(file)
-                .finish(&mut df_to_write)
-                .map_err(|e| format!("Failed to write CSV: {}", e))?;
+        // 삭제 후 CSV를 갱신할 날짜 확인
+        let start_utc: String = conn
+            .query_row("SELECT start_time FROM abyssal_runs WHERE run_id = ?1", params![run_id], |row| row.get(0))
+            .optional()
+            .map_err(|e| format!("Failed to look up run: {}", e))?
+            .ok_or_else(|| format!("Run not found: {}", run_id))?;
+
+        conn.execute("DELETE FROM abyssal_runs WHERE run_id = ?1", params![run_id])
+            .map_err(|e| format!("Failed to delete run: {}", e))?;
+        self.mark_run_changed(run_id);
+
+        match time_display::parse_utc(&start_utc) {
+            Some(utc) => self.write_daily_csv(&conn, self.time_display.day_of(utc)),
+            None => Ok(()),
         }
-        
-        Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // run_id 컬럼이 없던 이전 버전 스키마
+    fn legacy_database() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE abyssal_runs (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 start_time TEXT NOT NULL,
+                 end_time TEXT NOT NULL,
+                 run_time_seconds REAL NOT NULL,
+                 run_time_minutes REAL NOT NULL,
+                 abyssal_type TEXT NOT NULL,
+                 ship_class INTEGER NOT NULL DEFAULT 1,
+                 acquired_items TEXT NOT NULL DEFAULT ''
+             );
+             CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+             INSERT INTO abyssal_runs (start_time, end_time, run_time_seconds, run_time_minutes, abyssal_type)
+             VALUES ('2024-05-01 21:00:00', '2024-05-01 21:20:00', 1200, 20, 'T5 Dark'),
+                    ('2024-05-01T12:30:00Z', '2024-05-01T12:50:00Z', 1200, 20, 'T5 Dark'),
+                    ('invalid', '2024-05-01 21:20:00', 1200, 20, 'T5 Dark');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn backfills_missing_run_ids() {
+        let mut conn = legacy_database();
+        AbyssalDataManager::ensure_run_ids(&mut conn).unwrap();
+        let ids: Vec<String> = conn.prepare("SELECT run_id FROM abyssal_runs ORDER BY id").unwrap()
+            .query_map([], |row| row.get(0)).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 3);
+        assert!(AbyssalDataManager::has_meta(&conn, RUN_IDS_ASSIGNED_META_KEY).unwrap());
+
+        // 마이그레이션 이후에도 ID가 없는 행은 다시 채우고, 기존 ID는 그대로 둠
+        conn.execute("UPDATE abyssal_runs SET run_id = NULL WHERE id = 1", []).unwrap();
+        AbyssalDataManager::ensure_run_ids(&mut conn).unwrap();
+        let second: Vec<String> = conn.prepare("SELECT run_id FROM abyssal_runs ORDER BY id").unwrap()
+            .query_map([], |row| row.get(0)).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_ne!(second[0], ids[0]);
+        assert_eq!(second[1..], ids[1..]);
+    }
+
+    #[test]
+    fn counts_rows_of_intact_daily_csv() {
+        let path = std::env::temp_dir().join(format!("abyssal_results_row_count_{}.csv", std::process::id()));
+        let header = CSV_COLUMNS.join(",");
+        let row = "2024-05-01T12:00:00Z,2024-05-01T12:20:00Z,1200,20,T5 Dark,1,,id-1";
+
+        fs::write(&path, format!("\u{feff}{}\n{}\n{}\n", header, row, row)).unwrap();
+        assert_eq!(AbyssalDataManager::daily_csv_row_count(&path), Some(2));
+        // 마지막 행이 잘린 파일과 구버전 헤더는 손상으로 판정
+        fs::write(&path, format!("{}\n{}", header, row)).unwrap();
+        assert_eq!(AbyssalDataManager::daily_csv_row_count(&path), None);
+        fs::write(&path, format!("시작시각,종료시각\n{}\n", row)).unwrap();
+        assert_eq!(AbyssalDataManager::daily_csv_row_count(&path), None);
+
+        fs::remove_file(&path).ok();
+        assert_eq!(AbyssalDataManager::daily_csv_row_count(&path), None);
+    }
+
+    #[test]
+    fn converts_legacy_kst_times_once() {
+        let mut conn = legacy_database();
+        AbyssalDataManager::ensure_utc_times(&mut conn).unwrap();
+        let times: Vec<(String, String)> = conn.prepare("SELECT start_time, end_time FROM abyssal_runs ORDER BY id").unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        // KST 21:00 = UTC 12:00, 이미 UTC인 행은 그대로
+        assert_eq!(times[0], (String::from("2024-05-01T12:00:00Z"), String::from("2024-05-01T12:20:00Z")));
+        assert_eq!(times[1], (String::from("2024-05-01T12:30:00Z"), String::from("2024-05-01T12:50:00Z")));
+        // 변환할 수 없는 행은 남겨 두고, 다음 실행에서 다시 시도하지 않음
+        assert_eq!(times[2].0, "invalid");
+        assert!(AbyssalDataManager::has_meta(&conn, UTC_TIMES_META_KEY).unwrap());
+
+        conn.execute("UPDATE abyssal_runs SET start_time = '2024-05-02 09:00:00' WHERE id = 2", []).unwrap();
+        AbyssalDataManager::ensure_utc_times(&mut conn).unwrap();
+        let second: String = conn.query_row("SELECT start_time FROM abyssal_runs WHERE id = 2", [], |row| row.get(0)).unwrap();
+        assert_eq!(second, "2024-05-02 09:00:00");
+    }
+}
@@ -0,0 +1,326 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use encoding_rs::{Decoder, Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use serde::Serialize;
+use log::*;
+
+use crate::time_display;
+
+// 한 번에 읽는 바이트 수
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+// BOM이 없을 때 인코딩을 추측하려고 보는 앞부분 바이트 수
+const ENCODING_SNIFF_SIZE: usize = 64;
+// 헤더 구분선을 못 찾아도 이 줄 수까지만 헤더로 봄
+const HEADER_MAX_LINES: usize = 20;
+
+// BOM으로 인코딩 판단 (UTF-16LE/BE, UTF-8) - 반환값은 (인코딩, BOM 길이)
+// BOM이 없으면 앞부분의 NUL 바이트 위치로 UTF-16을 추측하고, 그것도 아니면 UTF-8
+pub fn detect_encoding(prefix: &[u8]) -> (&'static Encoding, usize) {
+    if let Some((encoding, bom_length)) = Encoding::for_bom(prefix) {
+        return (encoding, bom_length);
+    }
+
+    let even_nuls = prefix.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd_nuls = prefix.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+    if odd_nuls > even_nuls && odd_nuls * 4 >= prefix.len() / 2 {
+        (UTF_16LE, 0)
+    } else if even_nuls > odd_nuls && even_nuls * 4 >= prefix.len() / 2 {
+        (UTF_16BE, 0)
+    } else {
+        (UTF_8, 0)
+    }
+}
+
+// 채팅 로그 맨 앞의 헤더 블록 (클라이언트 언어와 관계없이 영어 라벨)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ChatLogHeader {
+    pub channel_id: Option<String>,
+    pub channel_name: Option<String>,
+    pub listener: Option<String>,
+    pub session_started: Option<DateTime<Utc>>, // 로그 시각과 같은 UTC
+}
+
+impl ChatLogHeader {
+    pub fn parse(lines: &[String]) -> Self {
+        let mut header = Self::default();
+        for line in lines {
+            let Some((label, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            match label.trim() {
+                "Channel ID" => header.channel_id = Some(value.to_string()),
+                "Channel Name" => header.channel_name = Some(value.to_string()),
+                "Listener" => header.listener = Some(value.to_string()),
+                "Session started" => header.session_started = time_display::parse_eve_log_timestamp(value),
+                _ => {}
+            }
+        }
+        header
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        Ok(Self::parse(&ChatLogReader::open(path)?.read_header()))
+    }
+}
+
+// 파일 이름의 세션 시작 시각 (예: Local_20240101_123456_90000001.txt) - 헤더를 읽을 수 없을 때 사용
+pub fn session_started_from_filename(path: &Path) -> Option<DateTime<Utc>> {
+    let stem = path.file_stem()?.to_str()?;
+    let parts: Vec<&str> = stem.split('_').collect();
+    parts.windows(2).find_map(|pair| {
+        let (date, time) = (pair[0], pair[1]);
+        if date.len() != 8 || time.len() != 6 {
+            return None;
+        }
+        NaiveDateTime::parse_from_str(&format!("{}{}", date, time), "%Y%m%d%H%M%S").ok()
+            .map(|naive| Utc.from_utc_datetime(&naive))
+    })
+}
+
+// 헤더 앞뒤의 "-----" 구분선
+fn is_header_separator(line: &str) -> bool {
+    line.len() >= 10 && line.chars().all(|c| c == '-')
+}
+
+// EVE 채팅 로그를 조금씩 읽어 디코딩하면서 한 줄씩 돌려주는 리더 (앞뒤 공백 제거)
+// 파일 전체를 메모리에 올리지 않으므로 필요한 만큼만 읽고 멈출 수 있음
+pub struct ChatLogReader<R: Read> {
+    reader: R,
+    decoder: Decoder,
+    raw: Vec<u8>,
+    pending: String, // 아직 줄바꿈을 만나지 못한 디코딩 결과
+    finished: bool,
+}
+
+impl ChatLogReader<BufReader<File>> {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> ChatLogReader<R> {
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut raw = vec![0; ENCODING_SNIFF_SIZE];
+        let mut filled = 0;
+        while filled < raw.len() {
+            match reader.read(&mut raw[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        raw.truncate(filled);
+
+        let (encoding, bom_length) = detect_encoding(&raw);
+        raw.drain(..bom_length);
+        Ok(Self {
+            reader,
+            decoder: encoding.new_decoder_without_bom_handling(),
+            raw,
+            pending: String::new(),
+            finished: false,
+        })
+    }
+
+    // 다음 청크를 읽어 pending에 디코딩 - 파일 끝이면 false
+    fn fill(&mut self) -> io::Result<bool> {
+        if self.finished {
+            return Ok(false);
+        }
+
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        let read = if self.raw.is_empty() {
+            loop {
+                match self.reader.read(&mut chunk) {
+                    Ok(n) => break n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+        } else {
+            // 인코딩 판단에 쓴 앞부분 먼저 디코딩
+            let sniffed = std::mem::take(&mut self.raw);
+            chunk[..sniffed.len()].copy_from_slice(&sniffed);
+            sniffed.len()
+        };
+
+        let last = read == 0;
+        let input = &chunk[..read];
+        if let Some(capacity) = self.decoder.max_utf8_buffer_length(input.len()) {
+            self.pending.reserve(capacity);
+        }
+        // 청크 경계에서 잘린 UTF-16 코드 유닛이나 UTF-8 바이트는 디코더가 다음 호출까지 보관
+        let _ = self.decoder.decode_to_string(input, &mut self.pending, last);
+        if last {
+            self.finished = true;
+        }
+        Ok(!last)
+    }
+
+    // 헤더(Channel ID, Channel Name, Listener, Session started)만 읽고 멈춤
+    // 두 번째 구분선, 첫 메시지 줄, 또는 HEADER_MAX_LINES 중 먼저 오는 곳까지
+    pub fn read_header(&mut self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut separators = 0;
+        for line in self.by_ref().take(HEADER_MAX_LINES) {
+            if line.starts_with('[') {
+                break;
+            }
+            if is_header_separator(&line) {
+                separators += 1;
+                if separators == 2 {
+                    break;
+                }
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(line);
+            }
+        }
+        lines
+    }
+}
+
+impl<R: Read> Iterator for ChatLogReader<R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            if let Some(newline) = self.pending.find('\n') {
+                let line: String = self.pending.drain(..=newline).collect();
+                return Some(line.trim().trim_start_matches('\u{feff}').to_string());
+            }
+
+            match self.fill() {
+                Ok(true) => continue,
+                Ok(false) => {
+                    // 줄바꿈 없이 끝난 마지막 줄
+                    if self.pending.is_empty() {
+                        return None;
+                    }
+                    let line = std::mem::take(&mut self.pending);
+                    return Some(line.trim().trim_start_matches('\u{feff}').to_string());
+                }
+                Err(e) => {
+                    warn!("채팅 로그 읽기 실패: {}", e);
+                    self.finished = true;
+                    self.pending.clear();
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\n\n        ---------------------------------------------------------------\n\n          Channel ID:      local\n          Channel Name:    Local\n          Listener:        Some Pilot\n          Session started: 2024.05.01 12:30:00\n        ---------------------------------------------------------------\n[ 2024.05.01 12:34:56 ] EVE System > Channel changed to Local : Jita\r\n[ 2024.05.01 12:35:00 ] Some Pilot > 주이타 o7";
+
+    // 한 번에 몇 바이트씩만 돌려줘서 청크 경계에서 코드 유닛이 잘리게 함
+    struct SmallReads<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for SmallReads<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    fn encode(text: &str, encoding: &'static Encoding, bom: bool) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        if encoding == UTF_16LE {
+            if bom {
+                bytes.extend_from_slice(&[0xFF, 0xFE]);
+            }
+            text.encode_utf16().for_each(|unit| bytes.extend_from_slice(&unit.to_le_bytes()));
+        } else if encoding == UTF_16BE {
+            if bom {
+                bytes.extend_from_slice(&[0xFE, 0xFF]);
+            }
+            text.encode_utf16().for_each(|unit| bytes.extend_from_slice(&unit.to_be_bytes()));
+        } else {
+            if bom {
+                bytes.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+            }
+            bytes.extend_from_slice(text.as_bytes());
+        }
+        bytes
+    }
+
+    fn read_lines(bytes: &[u8], step: usize) -> Vec<String> {
+        ChatLogReader::new(SmallReads { data: bytes, step }).unwrap().filter(|line| !line.is_empty()).collect()
+    }
+
+    #[test]
+    fn detects_encoding_from_bom() {
+        assert_eq!(detect_encoding(&[0xFF, 0xFE, b'a', 0]), (UTF_16LE, 2));
+        assert_eq!(detect_encoding(&[0xFE, 0xFF, 0, b'a']), (UTF_16BE, 2));
+        assert_eq!(detect_encoding(&[0xEF, 0xBB, 0xBF, b'a']), (UTF_8, 3));
+        assert_eq!(detect_encoding(&encode("Channel ID", UTF_16LE, false)), (UTF_16LE, 0));
+        assert_eq!(detect_encoding(&encode("Channel ID", UTF_16BE, false)), (UTF_16BE, 0));
+        assert_eq!(detect_encoding(b"Channel ID"), (UTF_8, 0));
+    }
+
+    #[test]
+    fn decodes_every_encoding_across_chunk_boundaries() {
+        let expected: Vec<String> = SAMPLE.lines().map(|line| line.trim().to_string()).filter(|line| !line.is_empty()).collect();
+        for encoding in [UTF_16LE, UTF_16BE, UTF_8] {
+            for bom in [true, false] {
+                let bytes = encode(SAMPLE, encoding, bom);
+                // 홀수 크기로 읽어 UTF-16 코드 유닛과 한글 UTF-8 바이트가 청크 사이에서 잘리게 함
+                for step in [1, 3, 7, READ_CHUNK_SIZE] {
+                    assert_eq!(read_lines(&bytes, step), expected, "{} bom={} step={}", encoding.name(), bom, step);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn returns_trailing_line_without_newline() {
+        let lines = read_lines(&encode("first\nlast line", UTF_16LE, true), 5);
+        assert_eq!(lines, vec!["first", "last line"]);
+        assert!(read_lines(&[], 1).is_empty());
+    }
+
+    #[test]
+    fn reads_header_and_stops_before_messages() {
+        let bytes = encode(SAMPLE, UTF_16LE, true);
+        let mut reader = ChatLogReader::new(SmallReads { data: &bytes, step: 7 }).unwrap();
+        let header = ChatLogHeader::parse(&reader.read_header());
+        assert_eq!(header.channel_id.as_deref(), Some("local"));
+        assert_eq!(header.channel_name.as_deref(), Some("Local"));
+        assert_eq!(header.listener.as_deref(), Some("Some Pilot"));
+        assert_eq!(header.session_started, Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap()));
+        assert_eq!(reader.next().as_deref(), Some("[ 2024.05.01 12:34:56 ] EVE System > Channel changed to Local : Jita"));
+    }
+
+    #[test]
+    fn parses_session_start_from_filename() {
+        assert_eq!(
+            session_started_from_filename(Path::new("logs/Chatlogs/Local_20240501_123000_90000001.txt")),
+            Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap())
+        );
+        assert_eq!(
+            session_started_from_filename(Path::new("지역_20240501_123000.txt")),
+            Some(Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap())
+        );
+        assert_eq!(session_started_from_filename(Path::new("Local_notadate.txt")), None);
+    }
+}
@@ -7,17 +7,25 @@ use configparser::ini::Ini;
 use std::sync::Arc; // Arc 추가
 use log::*;
 
+use crate::time_display::{DayBoundary, TimeDisplay, DEFAULT_DAY_BOUNDARY, DEFAULT_TIMEZONE};
+use crate::http_client::{HttpClient, HttpSettings, SharedRequestScheduler};
+use crate::price_provider::{Market, PriceBasis, PriceSource, ValuationMode, DEFAULT_FILAMENT_BASIS, DEFAULT_LOOT_BASIS, DEFAULT_MARKET_SCOPE, DEFAULT_PRICE_PROVIDERS, DEFAULT_TRADE_HUB, DEFAULT_VALUATION_MODE};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     pub general: GeneralConfig,
     pub tracker: TrackerConfig,
     pub ui: UiConfig,
+    pub prices: PriceConfig,
+    pub http: HttpSettings,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GeneralConfig {
     pub log_path: String,
     pub character_name: String,
+    pub timezone: String, // IANA 시간대 이름 (예: Asia/Seoul, Europe/Berlin)
+    pub day_boundary: String, // midnight, downtime 또는 UTC 기준 HH:MM
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -27,6 +35,17 @@ pub struct TrackerConfig {
     pub overall_stats_path: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceConfig {
+    pub providers: String, // 시세 제공자 우선순위 (예: fuzzwork,esi_orders,esi_average,static)
+    pub trade_hub: String, // jita, amarr, dodixie, rens, hek
+    pub market_scope: String, // region: 허브 지역 전체, station: 허브 정거장만
+    pub loot_basis: String, // 드롭 가치 기준 (예: buy_max, sell_median, split)
+    pub filament_basis: String, // 입장료 기준 (예: sell_min)
+    pub valuation: String, // historical: 런 날짜의 시세, current: 현재 시세
+    pub offline: bool, // 네트워크 없이 캐시된 type_id와 시세만 사용
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct UiConfig {
     pub last_abyssal_type: String,
@@ -47,6 +66,8 @@ impl Default for AppConfig {
             general: GeneralConfig {
                 log_path: logs_path,
                 character_name: String::new(),
+                timezone: String::from(DEFAULT_TIMEZONE),
+                day_boundary: String::from(DEFAULT_DAY_BOUNDARY),
             },
             tracker: TrackerConfig {
                 abyssal_data_path: String::from("data"),
@@ -57,6 +78,16 @@ impl Default for AppConfig {
                 last_abyssal_type: String::from("T5 Exotic"), // 기본값
                 last_ship_class: 1, // 기본값: Cruiser
             },
+            prices: PriceConfig {
+                providers: String::from(DEFAULT_PRICE_PROVIDERS),
+                trade_hub: String::from(DEFAULT_TRADE_HUB),
+                market_scope: String::from(DEFAULT_MARKET_SCOPE),
+                loot_basis: String::from(DEFAULT_LOOT_BASIS),
+                filament_basis: String::from(DEFAULT_FILAMENT_BASIS),
+                valuation: String::from(DEFAULT_VALUATION_MODE),
+                offline: false,
+            },
+            http: HttpSettings::default(),
         }
     }
 }
@@ -107,6 +138,12 @@ impl ConfigManager {
         if let Some(character_name) = config_ini.get("default", "character_name") {
             app_config.general.character_name = character_name;
         }
+        if let Some(timezone) = config_ini.get("default", "timezone") {
+            app_config.general.timezone = timezone;
+        }
+        if let Some(day_boundary) = config_ini.get("default", "day_boundary") {
+            app_config.general.day_boundary = day_boundary;
+        }
 
 
         if let Some(abyssal_data_path) = config_ini.get("tracker", "abyssal_data_path") {
@@ -129,6 +166,64 @@ impl ConfigManager {
             }
         }
 
+        // 시세 설정 로드
+        if let Some(providers) = config_ini.get("prices", "providers") {
+            app_config.prices.providers = providers;
+        }
+        if let Some(trade_hub) = config_ini.get("prices", "trade_hub") {
+            app_config.prices.trade_hub = trade_hub;
+        }
+        if let Some(market_scope) = config_ini.get("prices", "market_scope") {
+            app_config.prices.market_scope = market_scope;
+        }
+        if let Some(loot_basis) = config_ini.get("prices", "loot_basis") {
+            app_config.prices.loot_basis = loot_basis;
+        }
+        if let Some(filament_basis) = config_ini.get("prices", "filament_basis") {
+            app_config.prices.filament_basis = filament_basis;
+        }
+        if let Some(valuation) = config_ini.get("prices", "valuation") {
+            app_config.prices.valuation = valuation;
+        }
+        if let Some(offline) = config_ini.get("prices", "offline") {
+            app_config.prices.offline = offline.trim().eq_ignore_ascii_case("true");
+        }
+
+        // HTTP 설정 (기본 URL, 타임아웃, 프록시, User-Agent 연락처, 재시도)
+        if let Some(esi_base_url) = config_ini.get("http", "esi_base_url") {
+            app_config.http.esi_base_url = esi_base_url;
+        }
+        if let Some(fuzzwork_base_url) = config_ini.get("http", "fuzzwork_base_url") {
+            app_config.http.fuzzwork_base_url = fuzzwork_base_url;
+        }
+        if let Some(image_base_url) = config_ini.get("http", "image_base_url") {
+            app_config.http.image_base_url = image_base_url;
+        }
+        if let Some(github_base_url) = config_ini.get("http", "github_base_url") {
+            app_config.http.github_base_url = github_base_url;
+        }
+        if let Some(timeout_seconds_str) = config_ini.get("http", "timeout_seconds") {
+            if let Ok(timeout_seconds) = timeout_seconds_str.parse::<u64>() {
+                app_config.http.timeout_seconds = timeout_seconds;
+            }
+        }
+        if let Some(proxy) = config_ini.get("http", "proxy") {
+            app_config.http.proxy = proxy;
+        }
+        if let Some(contact) = config_ini.get("http", "contact") {
+            app_config.http.contact = contact;
+        }
+        if let Some(retries_str) = config_ini.get("http", "retries") {
+            if let Ok(retries) = retries_str.parse::<u32>() {
+                app_config.http.retries = retries;
+            }
+        }
+        if let Some(max_parallel_requests_str) = config_ini.get("http", "max_parallel_requests") {
+            if let Ok(max_parallel_requests) = max_parallel_requests_str.parse::<usize>() {
+                app_config.http.max_parallel_requests = max_parallel_requests;
+            }
+        }
+
         self.config = app_config;
         self.validate()?;
         Ok(())
@@ -139,6 +234,8 @@ impl ConfigManager {
 
         config_ini.set("default", "logs_path", Some(self.config.general.log_path.clone()));
         config_ini.set("default", "character_name", Some(self.config.general.character_name.clone()));
+        config_ini.set("default", "timezone", Some(self.config.general.timezone.clone()));
+        config_ini.set("default", "day_boundary", Some(self.config.general.day_boundary.clone()));
 
         config_ini.set("tracker", "abyssal_data_path", Some(self.config.tracker.abyssal_data_path.clone()));
         config_ini.set("tracker", "daily_stats_path", Some(self.config.tracker.daily_stats_path.clone()));
@@ -148,6 +245,26 @@ impl ConfigManager {
         config_ini.set("ui", "last_abyssal_type", Some(self.config.ui.last_abyssal_type.clone()));
         config_ini.set("ui", "last_ship_class", Some(self.config.ui.last_ship_class.to_string()));
 
+        // 시세 설정 저장
+        config_ini.set("prices", "providers", Some(self.config.prices.providers.clone()));
+        config_ini.set("prices", "trade_hub", Some(self.config.prices.trade_hub.clone()));
+        config_ini.set("prices", "market_scope", Some(self.config.prices.market_scope.clone()));
+        config_ini.set("prices", "loot_basis", Some(self.config.prices.loot_basis.clone()));
+        config_ini.set("prices", "filament_basis", Some(self.config.prices.filament_basis.clone()));
+        config_ini.set("prices", "valuation", Some(self.config.prices.valuation.clone()));
+        config_ini.set("prices", "offline", Some(self.config.prices.offline.to_string()));
+
+        // HTTP 설정 저장
+        config_ini.set("http", "esi_base_url", Some(self.config.http.esi_base_url.clone()));
+        config_ini.set("http", "fuzzwork_base_url", Some(self.config.http.fuzzwork_base_url.clone()));
+        config_ini.set("http", "image_base_url", Some(self.config.http.image_base_url.clone()));
+        config_ini.set("http", "github_base_url", Some(self.config.http.github_base_url.clone()));
+        config_ini.set("http", "timeout_seconds", Some(self.config.http.timeout_seconds.to_string()));
+        config_ini.set("http", "proxy", Some(self.config.http.proxy.clone()));
+        config_ini.set("http", "contact", Some(self.config.http.contact.clone()));
+        config_ini.set("http", "retries", Some(self.config.http.retries.to_string()));
+        config_ini.set("http", "max_parallel_requests", Some(self.config.http.max_parallel_requests.to_string()));
+
         config_ini.write(&self.config_path).map_err(|e| anyhow!("Failed to write config to file: {}", e))?;
         Ok(())
     }
@@ -167,6 +284,30 @@ impl ConfigManager {
         if logs_path.is_empty() || character_name.is_empty() {
             warn!("{}에 logs_path와 character_name 값을 입력하면 더 나은 기능을 사용할 수 있습니다.", self.config_path.display());
         }
+        if let Err(e) = TimeDisplay::new(&self.config.general.timezone) {
+            warn!("{} ({} 시간대를 사용합니다)", e, DEFAULT_TIMEZONE);
+        }
+        if let Err(e) = DayBoundary::parse(&self.config.general.day_boundary) {
+            warn!("{} (자정 기준을 사용합니다)", e);
+        }
+        if let Err(e) = PriceSource::parse_list(&self.config.prices.providers) {
+            warn!("{} ({} 순서를 사용합니다)", e, DEFAULT_PRICE_PROVIDERS);
+        }
+        if let Err(e) = Market::parse(&self.config.prices.trade_hub, &self.config.prices.market_scope) {
+            warn!("{} ({} 지역 시세를 사용합니다)", e, DEFAULT_TRADE_HUB);
+        }
+        if let Err(e) = PriceBasis::parse(&self.config.prices.loot_basis) {
+            warn!("{} (드롭은 {} 기준을 사용합니다)", e, DEFAULT_LOOT_BASIS);
+        }
+        if let Err(e) = PriceBasis::parse(&self.config.prices.filament_basis) {
+            warn!("{} (필라멘트는 {} 기준을 사용합니다)", e, DEFAULT_FILAMENT_BASIS);
+        }
+        if let Err(e) = ValuationMode::parse(&self.config.prices.valuation) {
+            warn!("{} ({} 평가를 사용합니다)", e, DEFAULT_VALUATION_MODE);
+        }
+        if let Err(e) = self.config.http.validate() {
+            warn!("{} (기본 HTTP 설정을 사용합니다)", e);
+        }
         Ok(())
     }
 
@@ -177,6 +318,41 @@ impl ConfigManager {
     pub fn get_character_name(&self) -> String {
         self.config.general.character_name.trim().to_string()
     }
+
+    // 설정된 시간대나 하루 기준이 잘못된 경우 기본값 사용
+    pub fn time_display(&self) -> TimeDisplay {
+        let day_boundary = DayBoundary::parse(&self.config.general.day_boundary).unwrap_or(DayBoundary::Midnight);
+        TimeDisplay::new(&self.config.general.timezone)
+            .unwrap_or_default()
+            .with_day_boundary(day_boundary)
+    }
+
+    pub fn price_sources(&self) -> Vec<PriceSource> {
+        PriceSource::parse_list(&self.config.prices.providers)
+            .or_else(|_| PriceSource::parse_list(DEFAULT_PRICE_PROVIDERS))
+            .unwrap_or_default()
+    }
+
+    pub fn market(&self) -> Market {
+        Market::parse(&self.config.prices.trade_hub, &self.config.prices.market_scope).unwrap_or_default()
+    }
+
+    // (드롭 기준, 필라멘트 기준)
+    pub fn price_basis(&self) -> (PriceBasis, PriceBasis) {
+        (
+            PriceBasis::parse(&self.config.prices.loot_basis).unwrap_or(PriceBasis::BuyMax),
+            PriceBasis::parse(&self.config.prices.filament_basis).unwrap_or(PriceBasis::SellMin),
+        )
+    }
+
+    // 설정이 잘못된 경우 기본 HTTP 설정 사용
+    pub fn http_client(&self, scheduler: &SharedRequestScheduler) -> HttpClient {
+        HttpClient::new_or_default(self.config.http.clone(), scheduler.clone())
+    }
+
+    pub fn valuation_mode(&self) -> ValuationMode {
+        ValuationMode::parse(&self.config.prices.valuation).unwrap_or(ValuationMode::Historical)
+    }
 }
 
 #[tauri::command]
@@ -216,6 +392,192 @@ pub async fn set_character_name(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn set_timezone(
+    app_handle: AppHandle,
+    state: State<'_, Arc<tokio::sync::Mutex<ConfigManager>>>,
+    timezone: String
+) -> Result<(), String> {
+    let timezone = TimeDisplay::new(&timezone)?.timezone_name().to_string();
+
+    let mut config_manager = state.inner().lock().await;
+    config_manager.config.general.timezone = timezone;
+    config_manager.save().map_err(|e| e.to_string())?;
+    let time_display = config_manager.time_display();
+    drop(config_manager);
+
+    apply_time_display(&app_handle, time_display).await?;
+    info!("Display timezone set to {}", time_display.timezone_name());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_day_boundary(
+    app_handle: AppHandle,
+    state: State<'_, Arc<tokio::sync::Mutex<ConfigManager>>>,
+    day_boundary: String
+) -> Result<(), String> {
+    let day_boundary = DayBoundary::parse(&day_boundary)?.to_config_string();
+
+    let mut config_manager = state.inner().lock().await;
+    config_manager.config.general.day_boundary = day_boundary.clone();
+    config_manager.save().map_err(|e| e.to_string())?;
+    let time_display = config_manager.time_display();
+    drop(config_manager);
+
+    apply_time_display(&app_handle, time_display).await?;
+    info!("Day boundary set to {}", day_boundary);
+    Ok(())
+}
+
+// 시간대나 하루 기준이 바뀌면 일별 CSV 날짜 구분도 다시 작성
+async fn apply_time_display(app_handle: &AppHandle, time_display: TimeDisplay) -> Result<(), String> {
+    let eve_api = app_handle.state::<Arc<tokio::sync::Mutex<crate::eve_api::EVEApi>>>();
+    eve_api.lock().await.set_time_display(time_display);
+
+    let abyssal_data_manager = app_handle.state::<Arc<tokio::sync::Mutex<crate::abyssal_data_manager::AbyssalDataManager>>>();
+    let result = abyssal_data_manager.lock().await.set_time_display(time_display);
+    result
+}
+
+#[tauri::command]
+pub async fn set_price_providers(
+    app_handle: AppHandle,
+    state: State<'_, Arc<tokio::sync::Mutex<ConfigManager>>>,
+    providers: String
+) -> Result<(), String> {
+    let price_sources = PriceSource::parse_list(&providers)?;
+
+    let mut config_manager = state.inner().lock().await;
+    config_manager.config.prices.providers = PriceSource::list_to_string(&price_sources);
+    config_manager.save().map_err(|e| e.to_string())?;
+    drop(config_manager);
+
+    let eve_api = app_handle.state::<Arc<tokio::sync::Mutex<crate::eve_api::EVEApi>>>();
+    eve_api.lock().await.set_price_sources(&price_sources).await;
+    invalidate_prices(&app_handle).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_trade_hub(
+    app_handle: AppHandle,
+    state: State<'_, Arc<tokio::sync::Mutex<ConfigManager>>>,
+    trade_hub: String,
+    market_scope: String
+) -> Result<(), String> {
+    let market = Market::parse(&trade_hub, &market_scope)?;
+
+    let mut config_manager = state.inner().lock().await;
+    if config_manager.market() == market {
+        return Ok(());
+    }
+    config_manager.config.prices.trade_hub = market.hub.name().to_string();
+    config_manager.config.prices.market_scope = market.scope_name().to_string();
+    config_manager.save().map_err(|e| e.to_string())?;
+    drop(config_manager);
+
+    let eve_api = app_handle.state::<Arc<tokio::sync::Mutex<crate::eve_api::EVEApi>>>();
+    eve_api.lock().await.set_market(market).await;
+    invalidate_prices(&app_handle).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_price_basis(
+    app_handle: AppHandle,
+    state: State<'_, Arc<tokio::sync::Mutex<ConfigManager>>>,
+    loot_basis: String,
+    filament_basis: String
+) -> Result<(), String> {
+    let loot_basis = PriceBasis::parse(&loot_basis)?;
+    let filament_basis = PriceBasis::parse(&filament_basis)?;
+
+    let mut config_manager = state.inner().lock().await;
+    config_manager.config.prices.loot_basis = loot_basis.name().to_string();
+    config_manager.config.prices.filament_basis = filament_basis.name().to_string();
+    config_manager.save().map_err(|e| e.to_string())?;
+    drop(config_manager);
+
+    let abyssal_data_analyzer = app_handle.state::<Arc<tokio::sync::Mutex<crate::abyssal_data_analyzer::AbyssalDataAnalyzer>>>();
+    abyssal_data_analyzer.lock().await.set_price_basis(loot_basis, filament_basis);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_valuation_mode(
+    app_handle: AppHandle,
+    state: State<'_, Arc<tokio::sync::Mutex<ConfigManager>>>,
+    valuation: String
+) -> Result<(), String> {
+    let valuation = ValuationMode::parse(&valuation)?;
+
+    let mut config_manager = state.inner().lock().await;
+    config_manager.config.prices.valuation = valuation.name().to_string();
+    config_manager.save().map_err(|e| e.to_string())?;
+    drop(config_manager);
+
+    let abyssal_data_analyzer = app_handle.state::<Arc<tokio::sync::Mutex<crate::abyssal_data_analyzer::AbyssalDataAnalyzer>>>();
+    abyssal_data_analyzer.lock().await.set_valuation_mode(valuation);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_http_settings(
+    app_handle: AppHandle,
+    state: State<'_, Arc<tokio::sync::Mutex<ConfigManager>>>,
+    scheduler: State<'_, SharedRequestScheduler>,
+    settings: HttpSettings
+) -> Result<(), String> {
+    let http = HttpClient::new(settings.clone(), scheduler.inner().clone()).map_err(|e| e.to_string())?;
+
+    let mut config_manager = state.inner().lock().await;
+    if config_manager.config.http == settings {
+        return Ok(());
+    }
+    config_manager.config.http = settings;
+    config_manager.save().map_err(|e| e.to_string())?;
+    drop(config_manager);
+
+    scheduler.set_max_parallel_requests(http.max_parallel_requests()).await;
+
+    let eve_api = app_handle.state::<Arc<tokio::sync::Mutex<crate::eve_api::EVEApi>>>();
+    eve_api.lock().await.set_http_client(http.clone());
+    let icon_cache = app_handle.state::<Arc<tokio::sync::Mutex<crate::icon_cache::IconCache>>>();
+    info!("HTTP 설정 변경: User-Agent '{}'", http.settings().user_agent());
+    icon_cache.lock().await.set_http_client(http);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_offline_mode(
+    app_handle: AppHandle,
+    state: State<'_, Arc<tokio::sync::Mutex<ConfigManager>>>,
+    offline: bool
+) -> Result<(), String> {
+    let mut config_manager = state.inner().lock().await;
+    if config_manager.config.prices.offline == offline {
+        return Ok(());
+    }
+    config_manager.config.prices.offline = offline;
+    config_manager.save().map_err(|e| e.to_string())?;
+    drop(config_manager);
+
+    let eve_api = app_handle.state::<Arc<tokio::sync::Mutex<crate::eve_api::EVEApi>>>();
+    eve_api.lock().await.set_offline(offline);
+    let icon_cache = app_handle.state::<Arc<tokio::sync::Mutex<crate::icon_cache::IconCache>>>();
+    icon_cache.lock().await.set_offline(offline);
+    // 온라인으로 돌아오면 만료된 시세를 다시 조회하도록 전체 재분석
+    invalidate_prices(&app_handle).await;
+    Ok(())
+}
+
+// 시세 출처가 바뀌면 다음 분석에서 모든 런을 새 시세로 다시 계산
+async fn invalidate_prices(app_handle: &AppHandle) {
+    let abyssal_data_analyzer = app_handle.state::<Arc<tokio::sync::Mutex<crate::abyssal_data_analyzer::AbyssalDataAnalyzer>>>();
+    abyssal_data_analyzer.lock().await.invalidate_prices();
+}
+
 #[tauri::command]
 pub async fn get_ui_config(state: State<'_, Arc<tokio::sync::Mutex<ConfigManager>>>) -> Result<UiConfig, String> {
     Ok(state.inner().lock().await.config.ui.clone())
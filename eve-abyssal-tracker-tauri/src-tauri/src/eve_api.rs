@@ -1,18 +1,53 @@
-use std::{collections::HashMap, path::PathBuf, sync::Arc, time::{SystemTime, UNIX_EPOCH}};
+use std::{collections::{BTreeMap, HashMap}, path::{Path, PathBuf}, sync::Arc, time::{SystemTime, UNIX_EPOCH}};
 use tokio::sync::Mutex;
+use futures_util::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
-use reqwest::Client;
 use anyhow::{Result, anyhow};
 use tokio::fs;
+use tokio::io::AsyncWriteExt;
 use tauri::{AppHandle, Manager};
 use log::*;
 
+use crate::http_client::HttpClient;
+use crate::static_types::SharedStaticTypes;
+use crate::price_provider::{self, Market, PriceProvider, PriceQuote, PriceSource};
+use crate::time_display::TimeDisplay;
+
 // Python과 동일한 상수값들
-const REGION_ID: u32 = 10000002; // The Forge
-const STATION_ID: u32 = 60003760; // Jita 4-4
 const CACHE_FILE_NAME: &str = "typeid_cache.json"; // 파일명만 정의
-const PRICE_CACHE_FILE: &str = "data/price_cache.json"; // 가격 캐시 파일
+const PRICE_CACHE_FILE_NAME: &str = "price_cache.json"; // 아이템별 가격 캐시 파일
+// 이전 버전이 작업 디렉토리 기준으로 저장하던 가격 캐시
+const LEGACY_PRICE_CACHE_FILE: &str = "data/price_cache.json";
 const PRICE_CACHE_TTL_SECONDS: u64 = 30 * 60; // 30분 TTL
+const PRICE_HISTORY_FILE_NAME: &str = "price_history.json"; // 일별 시세 기록
+// 일별 시세 보관 기간 - 그보다 오래된 런은 남겨 둔 가장 최근의 이전 시세로 계산
+const PRICE_HISTORY_RETENTION_DAYS: i64 = 365;
+const TYPE_NAME_CACHE_FILE_NAME: &str = "typename_cache.json"; // type_id별 영어 이름
+// 영어로 찾지 못한 이름을 다시 조회할 클라이언트 언어 (ESI language 값)
+const LOCALIZED_LANGUAGES: [&str; 6] = ["ko", "ja", "zh", "ru", "de", "fr"];
+
+// 이름에 쓰인 문자로 붙여넣은 전리품의 클라이언트 언어 후보 추정
+fn candidate_languages(name: &str) -> &'static [&'static str] {
+    let contains = |is_script: fn(&char) -> bool| name.chars().any(|c| is_script(&c));
+    if contains(|c| ('\u{AC00}'..='\u{D7A3}').contains(c) || ('\u{3130}'..='\u{318F}').contains(c)) {
+        &["ko"]
+    } else if contains(|c| ('\u{3040}'..='\u{30FF}').contains(c)) {
+        &["ja"]
+    } else if contains(|c| ('\u{4E00}'..='\u{9FFF}').contains(c)) {
+        &["zh", "ja"] // 가나 없이 한자만 있으면 중국어부터
+    } else if contains(|c| ('\u{0400}'..='\u{04FF}').contains(c)) {
+        &["ru"]
+    } else {
+        &["de", "fr"]
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EsiName {
+    id: u32,
+    name: String,
+    category: String,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeIdResponse {
@@ -20,64 +55,91 @@ pub struct TypeIdResponse {
     pub name: String,
 }
 
+// 아이템 하나의 시세와 조회 시각
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FuzzworkPrice {
-    pub buy: FuzzworkPriceDetail,
-    pub sell: FuzzworkPriceDetail,
+pub struct CachedQuote {
+    pub quote: PriceQuote,
+    pub fetched_at: u64, // Unix timestamp
+    pub ttl_seconds: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FuzzworkPriceDetail {
-    pub volume: u64,
-    pub weighted_average: f64,
-    pub max: f64,
-    pub min: f64,
-    pub stddev: f64,
-    pub median: f64,
-    pub order_count: u64,
-    pub percentiles: HashMap<String, f64>,
+impl CachedQuote {
+    fn is_expired(&self, now: u64) -> bool {
+        now.saturating_sub(self.fetched_at) > self.ttl_seconds
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CachedPriceData {
-    pub prices: HashMap<String, serde_json::Value>,
-    pub cached_at: u64, // Unix timestamp
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PriceCache {
+    pub market: String, // 시세를 조회한 시장 (Market::key)
+    pub quotes: HashMap<u32, CachedQuote>,
 }
 
-impl CachedPriceData {
-    fn new(prices: HashMap<String, serde_json::Value>) -> Self {
-        let cached_at = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        Self {
-            prices,
-            cached_at,
+// 시장별, type_id별 일별 시세 (날짜는 런과 같은 하루 기준의 통계 날짜 "YYYY-MM-DD", 하루의 마지막 조회 시세)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PriceHistory {
+    pub markets: HashMap<String, HashMap<u32, BTreeMap<String, PriceQuote>>>,
+}
+
+impl PriceHistory {
+    // 해당 날짜의 시세를 갱신하고, 하나라도 바뀌었으면 true
+    fn record(&mut self, market: String, date: &str, quotes: &HashMap<u32, PriceQuote>) -> bool {
+        let market_history = self.markets.entry(market).or_default();
+        let mut changed = false;
+        for (id, quote) in quotes {
+            let snapshots = market_history.entry(*id).or_default();
+            if snapshots.get(date) != Some(quote) {
+                snapshots.insert(date.to_string(), quote.clone());
+                changed = true;
+            }
         }
+        changed
     }
-    
-    fn is_expired(&self) -> bool {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        now - self.cached_at > PRICE_CACHE_TTL_SECONDS
+
+    // cutoff("YYYY-MM-DD")보다 오래된 시세 삭제 - 아이템마다 cutoff 이전의 마지막 시세 하나는 남겨
+    // 오래된 런도 가장 가까운 시세로 계산되도록 함, 하나라도 삭제했으면 true
+    fn prune(&mut self, cutoff: &str) -> bool {
+        let mut changed = false;
+        for market_history in self.markets.values_mut() {
+            for snapshots in market_history.values_mut() {
+                let recent = snapshots.split_off(cutoff);
+                let mut old = std::mem::replace(snapshots, recent);
+                if let Some((date, quote)) = old.pop_last() {
+                    changed |= !old.is_empty();
+                    snapshots.insert(date, quote);
+                }
+            }
+        }
+        changed
     }
 }
 
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
 pub struct EVEApi {
-    client: Client,
-    name_to_id_cache: Arc<Mutex<HashMap<String, u32>>>, // Python과 동일한 구조
-    price_cache: Arc<Mutex<Option<CachedPriceData>>>, // 가격 캐시
+    http: HttpClient,
+    name_to_id_cache: Arc<Mutex<HashMap<String, u32>>>, // Python과 동일한 구조 (현지화된 이름 포함)
+    english_names: Arc<Mutex<HashMap<u32, String>>>, // 현지화된 이름으로 찾은 type_id의 영어 이름
+    static_types: SharedStaticTypes, // SDE 기반 정적 타입 DB (ESI보다 먼저 조회)
+    price_cache: Arc<Mutex<PriceCache>>, // type_id별 가격 캐시 (만료된 시세도 보관)
+    price_history: Arc<Mutex<PriceHistory>>,
     data_dir: PathBuf, // 데이터 디렉토리 경로
+    price_sources: Vec<PriceSource>,
+    price_providers: Vec<Box<dyn PriceProvider>>, // 우선순위 순서
+    market: Market, // 시세를 조회할 거래 허브
+    offline: bool, // 오프라인 모드 - 네트워크 없이 캐시된 type_id와 시세만 사용
+    time_display: TimeDisplay, // 일별 시세의 날짜 구분 (런의 통계 날짜와 같은 기준)
 }
 
 impl EVEApi {
-    pub async fn new(app_handle: &AppHandle) -> Result<Self> {
+    pub async fn new(app_handle: &AppHandle, http: HttpClient, static_types: SharedStaticTypes, price_sources: &[PriceSource], market: Market) -> Result<Self> {
         let name_to_id_cache = Arc::new(Mutex::new(HashMap::new()));
-        let price_cache = Arc::new(Mutex::new(None));
+        let price_cache = Arc::new(Mutex::new(PriceCache { market: market.key(), quotes: HashMap::new() }));
 
         let data_dir = match app_handle.path().app_data_dir() {
             Ok(app_data_dir) => {
@@ -95,15 +157,26 @@ impl EVEApi {
             }
         };
 
+        let price_providers = price_provider::build_providers(price_sources, market, &data_dir);
         let api = Self {
-            client: Client::new(),
+            http,
             name_to_id_cache,
+            english_names: Arc::new(Mutex::new(HashMap::new())),
+            static_types,
             price_cache,
+            price_history: Arc::new(Mutex::new(PriceHistory::default())),
             data_dir,
+            price_sources: price_sources.to_vec(),
+            price_providers,
+            market,
+            offline: false,
+            time_display: TimeDisplay::default(),
         };
 
         api.load_cache().await?;
+        api.load_english_names().await;
         api.load_price_cache().await?;
+        api.load_price_history().await;
         Ok(api)
     }
 
@@ -131,49 +204,193 @@ impl EVEApi {
         Ok(())
     }
 
+    async fn load_english_names(&self) {
+        let cache_path = self.data_dir.join(TYPE_NAME_CACHE_FILE_NAME);
+        if !cache_path.exists() {
+            return;
+        }
+        let loaded = fs::read_to_string(&cache_path).await.map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<HashMap<u32, String>>(&content).map_err(|e| e.to_string()));
+        match loaded {
+            Ok(names) => *self.english_names.lock().await = names,
+            Err(e) => warn!("영어 이름 캐시 읽기 실패: {}", e),
+        }
+    }
+
+    async fn save_english_names(&self) -> Result<()> {
+        fs::create_dir_all(&self.data_dir).await?;
+        let content = serde_json::to_string_pretty(&*self.english_names.lock().await)?;
+        fs::write(self.data_dir.join(TYPE_NAME_CACHE_FILE_NAME), content).await?;
+        Ok(())
+    }
+
+    // 현지화된 이름 → 영어 이름 (영어 이름과 같거나 모르는 이름은 제외)
+    pub async fn english_names(&self, names: &[String]) -> HashMap<String, String> {
+        let name_to_id = self.name_to_id_cache.lock().await;
+        let english_names = self.english_names.lock().await;
+        let static_types = self.static_types.read().unwrap_or_else(|e| e.into_inner());
+        names.iter()
+            .filter_map(|name| {
+                let type_id = name_to_id.get(name).copied().or_else(|| static_types.get_type_id(name))?;
+                let english_name = english_names.get(&type_id).map(|name| name.as_str())
+                    .or_else(|| static_types.english_name(type_id))?;
+                (english_name != name).then(|| (name.clone(), english_name.to_string()))
+            })
+            .collect()
+    }
+
     async fn load_price_cache(&self) -> Result<()> {
-        let cache_path = PathBuf::from(PRICE_CACHE_FILE);
+        if PathBuf::from(LEGACY_PRICE_CACHE_FILE).exists() {
+            info!("이전 위치의 가격 캐시를 삭제합니다: {}", LEGACY_PRICE_CACHE_FILE);
+            let _ = fs::remove_file(LEGACY_PRICE_CACHE_FILE).await;
+        }
+
+        let cache_path = self.data_dir.join(PRICE_CACHE_FILE_NAME);
         if cache_path.exists() {
             let content = fs::read_to_string(&cache_path).await?;
-            let loaded_cache: CachedPriceData = serde_json::from_str(&content)?;
-            
-            if !loaded_cache.is_expired() {
-                let cached_at = loaded_cache.cached_at;
-                let prices_count = loaded_cache.prices.len();
-                *self.price_cache.lock().await = Some(loaded_cache);
-                
-                let remaining_minutes = (PRICE_CACHE_TTL_SECONDS - (SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs() - cached_at)) / 60;
-                
-                info!("가격 캐시 로드 성공 ({}개 아이템, 남은 시간: {}분)", 
-                    prices_count, remaining_minutes);
-            } else {
-                info!("가격 캐시가 만료되어 삭제합니다.");
+            let loaded_cache: PriceCache = match serde_json::from_str(&content) {
+                Ok(cache) => cache,
+                Err(e) => {
+                    info!("가격 캐시 형식이 맞지 않아 삭제합니다: {}", e);
+                    let _ = fs::remove_file(&cache_path).await;
+                    return Ok(());
+                }
+            };
+
+            if loaded_cache.market != self.market.key() {
+                info!("다른 거래 허브({})의 가격 캐시를 삭제합니다.", loaded_cache.market);
                 let _ = fs::remove_file(&cache_path).await;
+            } else {
+                let now = unix_now();
+                let fresh_count = loaded_cache.quotes.values().filter(|cached| !cached.is_expired(now)).count();
+                info!("가격 캐시 로드 성공 ({}개 아이템, 유효: {}개)", loaded_cache.quotes.len(), fresh_count);
+                *self.price_cache.lock().await = loaded_cache;
             }
         }
         Ok(())
     }
 
-    async fn save_price_cache(&self, prices: &HashMap<String, serde_json::Value>) -> Result<()> {
-        let cache_path = PathBuf::from(PRICE_CACHE_FILE);
+    async fn save_price_cache(&self) -> Result<()> {
+        let cache_path = self.data_dir.join(PRICE_CACHE_FILE_NAME);
         
         // data 디렉토리 생성
-        if let Some(parent) = cache_path.parent() {
-            fs::create_dir_all(parent).await?;
-        }
+        fs::create_dir_all(&self.data_dir).await?;
         
-        let cached_data = CachedPriceData::new(prices.clone());
-        let content = serde_json::to_string_pretty(&cached_data)?;
+        let content = serde_json::to_string(&*self.price_cache.lock().await)?;
         fs::write(&cache_path, content).await?;
-        
-        *self.price_cache.lock().await = Some(cached_data);
-        info!("가격 캐시 저장 완료 ({}개 아이템, 30분 TTL)", prices.len());
         Ok(())
     }
 
+    async fn load_price_history(&self) {
+        let history_path = self.data_dir.join(PRICE_HISTORY_FILE_NAME);
+        if !history_path.exists() {
+            return;
+        }
+        let loaded = fs::read_to_string(&history_path).await.map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<PriceHistory>(&content).map_err(|e| e.to_string()));
+        match loaded {
+            Ok(history) => {
+                let snapshot_count: usize = history.markets.values()
+                    .flat_map(|items| items.values())
+                    .map(|snapshots| snapshots.len())
+                    .sum();
+                info!("시세 기록 로드 성공 ({}개 일별 시세)", snapshot_count);
+                *self.price_history.lock().await = history;
+            }
+            // 기록은 다시 만들 수 없으므로 삭제하지 않음
+            Err(e) => warn!("시세 기록 읽기 실패: {}", e),
+        }
+    }
+
+    // 오늘(통계 날짜 기준) 시세로 일별 기록 갱신하고 보관 기간이 지난 시세 정리 - 바뀐 것이 없으면 파일을 쓰지 않음
+    async fn record_price_snapshots(&self, quotes: &HashMap<u32, PriceQuote>) -> Result<()> {
+        let today = self.time_display.day_of(chrono::Utc::now());
+        let cutoff = (today - chrono::Duration::days(PRICE_HISTORY_RETENTION_DAYS)).format("%Y-%m-%d").to_string();
+        let content = {
+            let mut history = self.price_history.lock().await;
+            let recorded = history.record(self.market.key(), &today.format("%Y-%m-%d").to_string(), quotes);
+            let pruned = history.prune(&cutoff);
+            if !recorded && !pruned {
+                return Ok(());
+            }
+            serde_json::to_string(&*history)?
+        };
+
+        // 기록은 다시 만들 수 없으므로 임시 파일에 쓴 뒤 교체 (쓰는 도중 종료되어도 기존 파일 유지)
+        fs::create_dir_all(&self.data_dir).await?;
+        let history_path = self.data_dir.join(PRICE_HISTORY_FILE_NAME);
+        let temp_path = history_path.with_extension("json.tmp");
+        // 쓰기 핸들로 동기화 (Windows는 읽기 전용 핸들의 sync_all이 거부됨)
+        let mut file = fs::File::create(&temp_path).await?;
+        file.write_all(content.as_bytes()).await?;
+        file.sync_all().await?;
+        drop(file);
+        fs::rename(&temp_path, &history_path).await?;
+        Ok(())
+    }
+
+    // 현재 시장의 type_id별 일별 시세
+    pub async fn price_history(&self, ids: &[u32]) -> HashMap<u32, BTreeMap<String, PriceQuote>> {
+        let history = self.price_history.lock().await;
+        let Some(market_history) = history.markets.get(&self.market.key()) else {
+            return HashMap::new();
+        };
+        ids.iter()
+            .filter_map(|id| market_history.get(id).map(|snapshots| (*id, snapshots.clone())))
+            .collect()
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    // 시세 제공자 우선순위 변경 - 이전 제공자의 캐시된 시세는 버림
+    pub async fn set_price_sources(&mut self, price_sources: &[PriceSource]) {
+        self.price_sources = price_sources.to_vec();
+        self.rebuild_price_providers().await;
+        info!("시세 제공자 우선순위: {}", PriceSource::list_to_string(price_sources));
+    }
+
+    // 거래 허브 변경 - 다른 시장의 캐시된 시세는 버림
+    pub async fn set_market(&mut self, market: Market) {
+        self.market = market;
+        self.rebuild_price_providers().await;
+        info!("시세 조회 시장: {}", market.key());
+    }
+
+    // 프록시/타임아웃/기본 URL 설정 변경
+    pub fn set_http_client(&mut self, http: HttpClient) {
+        self.http = http;
+    }
+
+    // 표시 시간대나 하루 기준 변경
+    pub fn set_time_display(&mut self, time_display: TimeDisplay) {
+        self.time_display = time_display;
+    }
+
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+        info!("오프라인 모드: {}", if offline { "켜짐" } else { "꺼짐" });
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    // type_id별 시세 조회 시각 (unix 초, 가격 캐시에 있는 시세만)
+    pub async fn price_fetched_at(&self, ids: &[u32]) -> HashMap<u32, u64> {
+        let cache = self.price_cache.lock().await;
+        ids.iter()
+            .filter_map(|id| cache.quotes.get(id).map(|cached| (*id, cached.fetched_at)))
+            .collect()
+    }
+
+    async fn rebuild_price_providers(&mut self) {
+        self.price_providers = price_provider::build_providers(&self.price_sources, self.market, &self.data_dir);
+        *self.price_cache.lock().await = PriceCache { market: self.market.key(), quotes: HashMap::new() };
+        let _ = fs::remove_file(self.data_dir.join(PRICE_CACHE_FILE_NAME)).await;
+    }
+
     pub async fn fetch_type_ids(&self, names: Vec<String>) -> Result<HashMap<String, u32>> {
         let mut name_to_id = HashMap::new();
         let mut names_to_query = Vec::new();
@@ -195,34 +412,60 @@ impl EVEApi {
             info!("type_id 캐시에서 {}개 아이템 조회", cached_count);
         }
 
+        // 정적 타입 DB (모든 언어 이름 포함)에서 조회 - 네트워크 불필요
+        {
+            let static_types = self.static_types.read().unwrap_or_else(|e| e.into_inner());
+            names_to_query.retain(|name| match static_types.get_type_id(name) {
+                Some(id) => {
+                    name_to_id.insert(name.clone(), id);
+                    false
+                }
+                None => true,
+            });
+        }
+        if name_to_id.len() > cached_count {
+            info!("정적 타입 DB에서 {}개 아이템 조회", name_to_id.len() - cached_count);
+        }
+
         if names_to_query.is_empty() {
             info!("모든 아이템 type_id가 캐시에 존재합니다. API 호출 건너뜀.");
             return Ok(name_to_id);
         }
 
+        if self.offline {
+            warn!("오프라인 모드: 캐시에 없는 {}개 아이템은 type_id 조회 건너뜀: {:?}", names_to_query.len(), names_to_query);
+            return Ok(name_to_id);
+        }
+
         info!("ESI API로 {}개의 아이템 type_id 조회 시작...", names_to_query.len());
-        
-        // Python과 동일하게 20개씩 청크로 나누어 처리
-        for (i, chunk) in names_to_query.chunks(20).enumerate() {
-            info!("ESI API 호출 중... ({}/{})", i * 20 + 1, names_to_query.len());
-            
-            match self.fetch_chunk_type_ids(chunk.to_vec()).await {
-                Ok(chunk_results) => {
-                    for (name, id) in chunk_results {
-                        name_to_id.insert(name.clone(), id);
-                        // 캐시에 추가
-                        self.name_to_id_cache.lock().await.insert(name, id);
-                    }
-                }
-                Err(e) => {
-                    error!("ESI API 호출 중 오류 발생: {}, 청크: {:?}", e, chunk);
-                    // 오류 발생 시 해당 청크는 건너뛰고 계속 진행
-                    continue;
-                }
+        let mut resolved = self.lookup_type_ids(&names_to_query, None).await;
+
+        // 영어로 찾지 못한 이름은 문자로 추정한 클라이언트 언어로 다시 조회
+        let mut localized: HashMap<String, u32> = HashMap::new();
+        for language in LOCALIZED_LANGUAGES {
+            let pending: Vec<String> = names_to_query.iter()
+                .filter(|name| !resolved.contains_key(*name) && !localized.contains_key(*name))
+                .filter(|name| candidate_languages(name).contains(&language))
+                .cloned()
+                .collect();
+            if pending.is_empty() {
+                continue;
+            }
+            info!("ESI API로 {}개 아이템 type_id 조회 (language={})", pending.len(), language);
+            localized.extend(self.lookup_type_ids(&pending, Some(language)).await);
+        }
+        if !localized.is_empty() {
+            self.fetch_english_names(localized.values().copied().collect()).await;
+        }
+        resolved.extend(localized);
+
+        {
+            let mut cache = self.name_to_id_cache.lock().await;
+            for (name, id) in resolved {
+                // 캐시에 추가
+                cache.insert(name.clone(), id);
+                name_to_id.insert(name, id);
             }
-            
-            // Python과 동일한 지연
-            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
         }
 
         self.save_cache().await?;
@@ -230,18 +473,37 @@ impl EVEApi {
         Ok(name_to_id)
     }
 
-    async fn fetch_chunk_type_ids(&self, chunk: Vec<String>) -> Result<HashMap<String, u32>> {
+    // 20개씩 청크로 나누어 병렬 처리 (동시 요청 수와 에러 한도 대기는 HTTP 스케줄러가 관리)
+    async fn lookup_type_ids(&self, names: &[String], language: Option<&str>) -> HashMap<String, u32> {
+        let chunk_results: Vec<_> = stream::iter(names.chunks(20).map(|chunk| chunk.to_vec()))
+            .map(|chunk| async move {
+                let result = self.fetch_chunk_type_ids(chunk.clone(), language).await;
+                (chunk, result)
+            })
+            .buffer_unordered(self.http.max_parallel_requests())
+            .collect()
+            .await;
+
+        let mut resolved = HashMap::new();
+        for (chunk, result) in chunk_results {
+            match result {
+                Ok(chunk_results) => resolved.extend(chunk_results),
+                // 오류 발생 시 해당 청크는 건너뛰고 계속 진행
+                Err(e) => error!("ESI API 호출 중 오류 발생: {}, 청크: {:?}", e, chunk),
+            }
+        }
+        resolved
+    }
+
+    // language가 없으면 영어 이름으로 조회, 결과는 요청한 이름 그대로를 키로 사용
+    async fn fetch_chunk_type_ids(&self, chunk: Vec<String>, language: Option<&str>) -> Result<HashMap<String, u32>> {
         let mut result = HashMap::new();
         
-        let url = "https://esi.evetech.net/latest/universe/ids/";
-        let response = self.client
-            .post(url)
-            .header("User-Agent", "bulk-lookup 1.0")
-            .header("Content-Type", "application/json")
-            .json(&chunk)
-            .timeout(tokio::time::Duration::from_secs(10))
-            .send()
-            .await?;
+        let url = match language {
+            Some(language) => self.http.esi_url(&format!("universe/ids/?language={}", language)),
+            None => self.http.esi_url("universe/ids/"),
+        };
+        let response = self.http.send(self.http.post(&url).json(&chunk)).await?;
 
         if !response.status().is_success() {
             return Err(anyhow!("ESI API HTTP 오류: {}", response.status()));
@@ -254,13 +516,15 @@ impl EVEApi {
             return Ok(result);
         }
 
+        let requested: HashMap<String, &String> = chunk.iter().map(|name| (name.to_lowercase(), name)).collect();
         if let Some(inventory_types) = response_data.get("inventory_types").and_then(|v| v.as_array()) {
             for item in inventory_types {
                 if let (Some(name), Some(id)) = (
                     item.get("name").and_then(|v| v.as_str()),
                     item.get("id").and_then(|v| v.as_u64())
                 ) {
-                    result.insert(name.to_string(), id as u32);
+                    let name = requested.get(&name.to_lowercase()).map_or_else(|| name.to_string(), |requested| (*requested).clone());
+                    result.insert(name, id as u32);
                 }
             }
         }
@@ -268,95 +532,133 @@ impl EVEApi {
         Ok(result)
     }
 
-    pub async fn fetch_fuzzwork_prices(&self, ids: Vec<u32>) -> Result<HashMap<String, serde_json::Value>> {
+    // 현지화된 이름으로 찾은 type_id의 영어 이름 조회 (/universe/names/는 항상 영어 이름 반환)
+    async fn fetch_english_names(&self, mut ids: Vec<u32>) {
+        {
+            let english_names = self.english_names.lock().await;
+            ids.retain(|id| !english_names.contains_key(id));
+        }
+        ids.sort_unstable();
+        ids.dedup();
         if ids.is_empty() {
-            info!("Fuzzwork API에 조회할 type_id가 없습니다.");
+            return;
+        }
+
+        let url = self.http.esi_url("universe/names/");
+        for chunk in ids.chunks(1000) {
+            let names = match self.http.send(self.http.post(&url).json(chunk)).await {
+                Ok(response) if response.status().is_success() => response.json::<Vec<EsiName>>().await.map_err(|e| e.to_string()),
+                Ok(response) => Err(format!("HTTP {}", response.status())),
+                Err(e) => Err(e.to_string()),
+            };
+            match names {
+                Ok(names) => {
+                    let mut english_names = self.english_names.lock().await;
+                    for name in names.into_iter().filter(|name| name.category == "inventory_type") {
+                        english_names.insert(name.id, name.name);
+                    }
+                }
+                Err(e) => warn!("ESI 영어 이름 조회 실패: {}", e),
+            }
+        }
+        if let Err(e) = self.save_english_names().await {
+            warn!("영어 이름 캐시 저장 실패: {}", e);
+        }
+    }
+
+    pub async fn fetch_prices(&self, ids: Vec<u32>) -> Result<HashMap<u32, PriceQuote>> {
+        if ids.is_empty() {
+            info!("시세를 조회할 type_id가 없습니다.");
             return Ok(HashMap::new());
         }
 
-        // 캐시 확인
-        let cache_guard = self.price_cache.lock().await;
-        if let Some(cached_data) = cache_guard.as_ref() {
-            if !cached_data.is_expired() {
-                // 요청된 모든 ID가 캐시에 있는지 확인
-                let ids_str_set: std::collections::HashSet<String> = ids.iter().map(|id| id.to_string()).collect();
-                let cached_ids_set: std::collections::HashSet<String> = cached_data.prices.keys().cloned().collect();
-                
-                if ids_str_set.is_subset(&cached_ids_set) {
-                    // 캐시에서 요청된 항목들만 추출
-                    let mut result = HashMap::new();
-                    for id in &ids {
-                        let id_str = id.to_string();
-                        if let Some(price_data) = cached_data.prices.get(&id_str) {
-                            result.insert(id_str, price_data.clone());
-                        }
+        if self.offline {
+            return self.fetch_offline_prices(&ids).await;
+        }
+
+        // 캐시 확인 - 만료되지 않은 시세는 그대로 사용하고 나머지만 조회
+        let now = unix_now();
+        let mut result = HashMap::new();
+        let mut missing_ids = Vec::new();
+        {
+            let cache = self.price_cache.lock().await;
+            for id in &ids {
+                match cache.quotes.get(id) {
+                    Some(cached) if !cached.is_expired(now) => {
+                        result.insert(*id, cached.quote.clone());
                     }
-                    let remaining_minutes = (PRICE_CACHE_TTL_SECONDS - (SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs() - cached_data.cached_at)) / 60;
-                    
-                    info!("가격 캐시에서 {}개 아이템 조회 (남은 시간: {}분)", result.len(), remaining_minutes);
-                    drop(cache_guard);
-                    return Ok(result);
+                    _ => missing_ids.push(*id),
                 }
             }
         }
-        drop(cache_guard);
 
-        // 캐시 미스 또는 만료된 경우 API 호출
-        info!("가격 캐시 미스, Fuzzwork API 호출 중...");
-        
-        let ids_str: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
-        let url = format!(
-            "https://market.fuzzwork.co.uk/aggregates/?region={}&types={}",
-            REGION_ID,
-            ids_str.join(",")
-        );
-
-        match self.client
-            .get(&url)
-            .timeout(tokio::time::Duration::from_secs(15))
-            .send()
-            .await
+        if missing_ids.is_empty() {
+            info!("가격 캐시에서 {}개 아이템 조회", result.len());
+            return Ok(result);
+        }
+
+        // 캐시 미스 또는 만료된 아이템만 제공자 우선순위대로 조회
+        info!("가격 캐시 {}개 적중, {}개 아이템 시세 조회 중...", result.len(), missing_ids.len());
+        let quotes = price_provider::fetch_with_fallback(&self.price_providers, &self.http, &missing_ids).await?;
+
+        if quotes.is_empty() {
+            warn!("시세 제공자 응답에 {}개 아이템의 시세가 없습니다.", missing_ids.len());
+        } else {
+            info!("시세 조회 성공. {}개 아이템.", quotes.len());
+            
+            // 새로운 시세를 기존 캐시에 병합
+            {
+                let mut cache = self.price_cache.lock().await;
+                for (id, quote) in &quotes {
+                    cache.quotes.insert(*id, CachedQuote {
+                        quote: quote.clone(),
+                        fetched_at: now,
+                        ttl_seconds: PRICE_CACHE_TTL_SECONDS,
+                    });
+                }
+            }
+            if let Err(e) = self.save_price_cache().await {
+                warn!("가격 캐시 저장 실패: {}", e);
+            }
+            if let Err(e) = self.record_price_snapshots(&quotes).await {
+                warn!("시세 기록 저장 실패: {}", e);
+            }
+        }
+        result.extend(quotes);
+        Ok(result)
+    }
+
+    // 오프라인 모드 - 만료 여부와 관계없이 캐시된 최근 시세 사용, 나머지는 로컬 제공자(정적 파일)에서만 조회
+    async fn fetch_offline_prices(&self, ids: &[u32]) -> Result<HashMap<u32, PriceQuote>> {
+        let mut result = HashMap::new();
+        let mut missing_ids = Vec::new();
         {
-            Ok(response) => {
-                if !response.status().is_success() {
-                    error!("Fuzzwork API HTTP 오류: {} - URL: {}", response.status(), url);
-                    return Ok(HashMap::new());
+            let cache = self.price_cache.lock().await;
+            for id in ids {
+                match cache.quotes.get(id) {
+                    Some(cached) => { result.insert(*id, cached.quote.clone()); }
+                    None => missing_ids.push(*id),
                 }
+            }
+        }
 
-                match response.json::<HashMap<String, serde_json::Value>>().await {
-                    Ok(data) => {
-                        if data.is_empty() {
-                            warn!("Fuzzwork API 응답이 비어있습니다. URL: {}", url);
-                        } else {
-                            info!("Fuzzwork API 시세 조회 성공. {}개 아이템.", data.len());
-                            
-                            // 새로운 데이터를 캐시에 저장
-                            if let Err(e) = self.save_price_cache(&data).await {
-                                warn!("가격 캐시 저장 실패: {}", e);
-                            }
-                        }
-                        Ok(data)
-                    }
-                    Err(e) => {
-                        error!("Fuzzwork API 응답 JSON 디코딩 오류: {}", e);
-                        Ok(HashMap::new())
+        if !missing_ids.is_empty() {
+            for provider in self.price_providers.iter().filter(|provider| provider.is_local()) {
+                match provider.fetch_quotes(&self.http, &missing_ids).await {
+                    Ok(quotes) => {
+                        missing_ids.retain(|id| !quotes.contains_key(id));
+                        result.extend(quotes);
                     }
+                    Err(e) => warn!("{} 시세 제공자 조회 실패: {}", provider.name(), e),
                 }
-            }
-            Err(e) => {
-                if e.is_timeout() {
-                    error!("Fuzzwork API 호출 타임아웃 발생: {}", url);
-                } else if e.is_connect() {
-                    error!("Fuzzwork API 연결 오류: {}, URL: {}", e, url);
-                } else {
-                    error!("Fuzzwork API 호출 중 오류 발생: {}", e);
+                if missing_ids.is_empty() {
+                    break;
                 }
-                Ok(HashMap::new())
             }
         }
+
+        info!("오프라인 모드: 캐시된 시세 {}개 사용, 시세 없음 {}개", result.len(), missing_ids.len());
+        Ok(result)
     }
 }
 
@@ -368,8 +670,88 @@ pub async fn get_type_ids(app_handle: AppHandle, item_names: Vec<String>) -> Res
 }
 
 #[tauri::command]
-pub async fn get_fuzzwork_prices(app_handle: AppHandle, type_ids: Vec<u32>) -> Result<HashMap<String, serde_json::Value>, String> {
+pub async fn get_english_names(app_handle: AppHandle, item_names: Vec<String>) -> Result<HashMap<String, String>, String> {
+    let eve_api = app_handle.state::<Arc<Mutex<EVEApi>>>();
+    let api = eve_api.lock().await;
+    Ok(api.english_names(&item_names).await)
+}
+
+#[tauri::command]
+pub async fn get_market_prices(app_handle: AppHandle, type_ids: Vec<u32>) -> Result<HashMap<u32, PriceQuote>, String> {
     let eve_api = app_handle.state::<Arc<Mutex<EVEApi>>>();
-    let result = eve_api.lock().await.fetch_fuzzwork_prices(type_ids).await.map_err(|e| e.to_string());
+    let result = eve_api.lock().await.fetch_prices(type_ids).await.map_err(|e| e.to_string());
     result
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn get_price_overrides(app_handle: AppHandle) -> Result<HashMap<String, f64>, String> {
+    let eve_api = app_handle.state::<Arc<Mutex<EVEApi>>>();
+    let data_dir = eve_api.lock().await.data_dir().to_path_buf();
+    Ok(price_provider::load_price_overrides(&data_dir))
+}
+
+// price가 없으면 해당 아이템의 수동 가격 삭제
+#[tauri::command]
+pub async fn set_price_override(app_handle: AppHandle, item_name: String, price: Option<f64>) -> Result<(), String> {
+    let item_name = item_name.trim().to_string();
+    if item_name.is_empty() {
+        return Err("Item name is required".to_string());
+    }
+
+    let eve_api = app_handle.state::<Arc<Mutex<EVEApi>>>();
+    let data_dir = eve_api.lock().await.data_dir().to_path_buf();
+    let mut overrides = price_provider::load_price_overrides(&data_dir);
+    match price {
+        Some(price) if price.is_finite() && price >= 0.0 => {
+            overrides.insert(item_name.clone(), price);
+            info!("수동 가격 설정: {} = {} ISK", item_name, price);
+        }
+        Some(price) => return Err(format!("Invalid price for {}: {}", item_name, price)),
+        None => {
+            overrides.remove(&item_name);
+            info!("수동 가격 삭제: {}", item_name);
+        }
+    }
+    price_provider::save_price_overrides(&data_dir, &overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price_provider::PriceStats;
+
+    fn quote(type_id: u32, price: f64) -> PriceQuote {
+        PriceQuote { type_id, buy: PriceStats { max: price, min: price, ..PriceStats::default() }, sell: PriceStats { max: price, min: price, ..PriceStats::default() }, source: String::from("fuzzwork") }
+    }
+
+    #[test]
+    fn records_only_changed_snapshots() {
+        let mut history = PriceHistory::default();
+        let quotes = HashMap::from([(34, quote(34, 5.0)), (35, quote(35, 10.0))]);
+        assert!(history.record(String::from("jita"), "2024-05-01", &quotes));
+        assert!(!history.record(String::from("jita"), "2024-05-01", &quotes));
+
+        // 같은 날 바뀐 시세는 덮어쓰고, 다른 날은 새로 추가
+        assert!(history.record(String::from("jita"), "2024-05-01", &HashMap::from([(34, quote(34, 6.0))])));
+        assert!(history.record(String::from("jita"), "2024-05-02", &HashMap::from([(34, quote(34, 6.0))])));
+        let snapshots = &history.markets["jita"][&34];
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots["2024-05-01"].buy.max, 6.0);
+        assert!(history.record(String::from("amarr"), "2024-05-01", &quotes));
+    }
+
+    #[test]
+    fn prunes_snapshots_older_than_cutoff() {
+        let mut history = PriceHistory::default();
+        for date in ["2023-01-01", "2023-06-01", "2024-05-01", "2024-05-02"] {
+            history.record(String::from("jita"), date, &HashMap::from([(34, quote(34, 5.0))]));
+        }
+        history.record(String::from("jita"), "2023-02-01", &HashMap::from([(35, quote(35, 10.0))]));
+
+        assert!(history.prune("2024-01-01"));
+        // cutoff 이전의 마지막 시세 하나는 남김
+        assert_eq!(history.markets["jita"][&34].keys().collect::<Vec<_>>(), ["2023-06-01", "2024-05-01", "2024-05-02"]);
+        assert_eq!(history.markets["jita"][&35].keys().collect::<Vec<_>>(), ["2023-02-01"]);
+        assert!(!history.prune("2024-01-01"));
+    }
+}
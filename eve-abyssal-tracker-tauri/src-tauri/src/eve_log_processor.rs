@@ -1,145 +1,115 @@
 use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufReader, Read},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use encoding_rs::UTF_16LE;
 use regex::Regex;
 use walkdir::WalkDir;
+use log::*;
 
-// Python의 constants.py에서 가져온 패턴
-lazy_static::lazy_static! {
-    static ref SYSTEM_CHANGE_PATTERNS: HashMap<&'static str, HashMap<&'static str, &'static str>> = {
-        let mut outer_map = HashMap::new();
-        let mut ko_patterns = HashMap::new();
-        ko_patterns.insert("system_change", "이브 시스템 > 지역 : ");
-        ko_patterns.insert("channel_changed", "채널로 변경");
-        ko_patterns.insert("unknown_system", "알 수 없음");
-        outer_map.insert("ko", ko_patterns);
-
-        let mut en_patterns = HashMap::new();
-        en_patterns.insert("system_change", "EVE System > Channel changed to Local : ");
-        en_patterns.insert("channel_changed", "");
-        en_patterns.insert("unknown_system", "Unknown");
-        outer_map.insert("en", en_patterns);
-        outer_map
-    };
-
-    static ref LOG_FILENAME_PATTERNS: HashMap<&'static str, &'static str> = {
-        let mut map = HashMap::new();
-        map.insert("ko", "지역_*.txt");
-        map.insert("en", "Local_*.txt");
-        map
-    };
+use chrono::{DateTime, Utc};
+
+use crate::chat_log_reader::{self, ChatLogHeader, ChatLogReader};
+use crate::log_patterns::{self, LogPatternSet, LogPatterns, DEFAULT_LOG_LANGUAGE};
+
+// 언어 감지 시 성계 변경 줄을 찾는 최대 줄 수 (헤더와 MOTD 이후 보통 바로 나옴)
+const LANGUAGE_DETECTION_MAX_LINES: usize = 100;
 
+lazy_static::lazy_static! {
     static ref TIMESTAMP_REGEX: Regex = Regex::new(r"\[ *(\d{4}\.\d{2}\.\d{2} \d{2}:\d{2}:\d{2}) *\]").unwrap();
-    static ref CHARACTER_NAME_REGEX: Regex = Regex::new(r"Listener:\s*(.+)").unwrap();
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone)]
 pub struct EveLogProcessor {
     pub logs_path: PathBuf,
     pub current_log_file: Option<PathBuf>,
     pub language: Option<String>,
-    pub patterns: HashMap<String, String>,
+    pub patterns: LogPatternSet,
+    pattern_table: Option<Arc<LogPatterns>>, // 없으면 현재 적용된 전역 패턴 사용 (패턴 검증 시에만 지정)
 }
 
 impl EveLogProcessor {
     pub fn new(logs_path: PathBuf, language: Option<String>) -> Self {
-        let default_patterns: HashMap<String, String> = SYSTEM_CHANGE_PATTERNS.get("ko").unwrap()
-            .iter()
-            .map(|(&k, &v)| (k.to_string(), v.to_string()))
-            .collect();
+        let patterns = log_patterns::active().get_or_default(language.as_deref().unwrap_or(DEFAULT_LOG_LANGUAGE)).clone();
         EveLogProcessor {
             logs_path,
             current_log_file: None,
             language,
-            patterns: default_patterns,
+            patterns,
+            pattern_table: None,
         }
     }
 
-    pub fn find_all_log_files(&self) -> Vec<PathBuf> {
-        let mut files = Vec::new();
-        for pattern_str in LOG_FILENAME_PATTERNS.values() {
-            let full_pattern = self.logs_path.join(pattern_str);
-            if let Some(parent) = full_pattern.parent() {
-                if let Some(filename_glob) = full_pattern.file_name().and_then(|s| s.to_str()) {
-                    for entry in WalkDir::new(parent)
-                        .into_iter()
-                        .filter_map(|e| e.ok())
-                        .filter(|e| e.file_type().is_file())
-                    {
-                        if let Some(file_name) = entry.file_name().to_str() {
-                            if glob_match::glob_match(filename_glob, file_name) {
-                                files.push(entry.path().to_path_buf());
-                            }
-                        }
-                    }
-                }
-            }
+    // 적용되지 않은 패턴으로 처리 (패턴 파일 검증용)
+    pub fn with_patterns(logs_path: PathBuf, language: Option<String>, pattern_table: Arc<LogPatterns>) -> Self {
+        let patterns = pattern_table.get_or_default(language.as_deref().unwrap_or(DEFAULT_LOG_LANGUAGE)).clone();
+        EveLogProcessor {
+            logs_path,
+            current_log_file: None,
+            language,
+            patterns,
+            pattern_table: Some(pattern_table),
         }
-        files
+    }
+
+    fn pattern_table(&self) -> Arc<LogPatterns> {
+        self.pattern_table.clone().unwrap_or_else(log_patterns::active)
+    }
+
+    // 모든 언어의 지역 채널 로그 파일 (한 번만 순회)
+    pub fn find_all_log_files(&self) -> Vec<PathBuf> {
+        let pattern_table = self.pattern_table();
+        WalkDir::new(&self.logs_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|entry| entry.file_name().to_str().is_some_and(|file_name| pattern_table.matches_filename(file_name)))
+            .map(|entry| entry.path().to_path_buf())
+            .collect()
     }
 
     pub fn detect_log_language(&self, file_path: &Path) -> String {
-        if let Ok(file) = File::open(file_path) {
-            let mut reader = BufReader::new(file);
-            let mut buffer = Vec::new();
-            reader.read_to_end(&mut buffer).ok();
-
-            let (cow, _, _) = UTF_16LE.decode(&buffer);
-            let content = cow.into_owned();
-
-            for line in content.lines().take(20) {
-                for (lang, patterns) in SYSTEM_CHANGE_PATTERNS.iter() {
-                    if let Some(system_change_pattern) = patterns.get("system_change") {
-                        if line.contains(system_change_pattern) {
-                            return lang.to_string();
-                        }
-                    }
+        if let Ok(reader) = ChatLogReader::open(file_path) {
+            let pattern_table = self.pattern_table();
+            for line in reader.take(LANGUAGE_DETECTION_MAX_LINES) {
+                if let Some(language) = pattern_table.language_for_line(&line) {
+                    return language.to_string();
                 }
             }
         }
-        "ko".to_string() // Default to Korean if detection fails
+
+        // 앞부분에 성계 변경 줄이 없으면 지역 채널 파일 이름으로 판단
+        if let Some(file_name) = file_path.file_name().and_then(|s| s.to_str()) {
+            if let Some(language) = self.pattern_table().language_for_filename(file_name) {
+                return language.to_string();
+            }
+        }
+        DEFAULT_LOG_LANGUAGE.to_string() // Default to Korean if detection fails
     }
 
+    // language가 지정되지 않았으면 파일마다 언어를 감지 (클라이언트 언어를 바꾼 경우 파일마다 다를 수 있음)
+    // 다시 불러온 패턴 파일도 여기서 반영
     pub fn set_log_file(&mut self, file_path: PathBuf) {
-        self.current_log_file = Some(file_path.clone());
-        if self.language.is_none() {
-            self.language = Some(self.detect_log_language(&file_path));
-        }
-        self.patterns = SYSTEM_CHANGE_PATTERNS
-            .get(self.language.as_deref().unwrap_or("ko"))
-            .unwrap_or_else(|| SYSTEM_CHANGE_PATTERNS.get("ko").unwrap())
-            .iter()
-            .map(|(&k, &v)| (k.to_string(), v.to_string()))
-            .collect();
+        let language = self.language.clone().unwrap_or_else(|| self.detect_log_language(&file_path));
+        self.patterns = self.pattern_table().get_or_default(&language).clone();
+        self.current_log_file = Some(file_path);
     }
 
     pub fn iter_lines(&self, file_path: Option<&Path>) -> Box<dyn Iterator<Item = String>> {
         let target_file = file_path.unwrap_or_else(|| self.current_log_file.as_deref().expect("No log file specified for iteration."));
 
-        if let Ok(file) = File::open(target_file) {
-            let mut reader = BufReader::new(file);
-            let mut buffer = Vec::new();
-            reader.read_to_end(&mut buffer).ok();
-
-            let (cow, _, _) = UTF_16LE.decode(&buffer);
-            let content = cow.into_owned();
-
-            Box::new(content.lines().map(|s| s.trim().trim_start_matches('\u{feff}').to_string()).collect::<Vec<_>>().into_iter())
-        } else {
-            Box::new(std::iter::empty())
+        // 필요한 만큼만 읽으며 디코딩
+        match ChatLogReader::open(target_file) {
+            Ok(reader) => Box::new(reader),
+            Err(e) => {
+                warn!("로그 파일 열기 실패 {}: {}", target_file.display(), e);
+                Box::new(std::iter::empty())
+            }
         }
     }
 
     pub fn is_system_change_line(&self, line: &str) -> bool {
-        let system_change_pattern = self.patterns.get("system_change").map(|s| s.as_str()).unwrap_or("");
-        let channel_changed_pattern = self.patterns.get("channel_changed").map(|s| s.as_str()).unwrap_or("");
-        
-        line.contains(system_change_pattern) && line.contains(channel_changed_pattern)
+        self.patterns.is_system_change_line(line)
     }
 
     pub fn iter_system_changes(&self, file_path: Option<&Path>) -> Vec<(String, String, String)> {
@@ -159,22 +129,7 @@ impl EveLogProcessor {
     }
 
     pub fn parse_system_change(&self, line: &str) -> (Option<String>, Option<String>) {
-        let system_change_prefix = self.patterns.get("system_change").cloned().unwrap_or_default();
-        let channel_changed_suffix = self.patterns.get("channel_changed").cloned().unwrap_or_default();
-
-        if system_change_prefix.is_empty() || channel_changed_suffix.is_empty() {
-            return (None, None);
-        }
-
-        let system_name = if let Some(parts) = line.split(&system_change_prefix).nth(1) {
-            if let Some(name_part) = parts.split(&channel_changed_suffix).next() {
-                Some(name_part.trim().to_string())
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let system_name = self.patterns.parse_system_name(line);
 
         let ts = TIMESTAMP_REGEX.captures(line)
             .and_then(|caps| caps.get(1).map(|m| m.as_str().trim().to_string()));
@@ -183,32 +138,87 @@ impl EveLogProcessor {
     }
 
     pub fn is_unknown_system(&self, system_name: &str) -> bool {
-        system_name == self.patterns.get("unknown_system").cloned().unwrap_or_default().as_str()
+        self.patterns.is_unknown_system(system_name)
     }
 
     pub fn detect_character_name(&self, file_path: Option<&Path>) -> Option<String> {
         let target_file = file_path.unwrap_or_else(|| self.current_log_file.as_deref().expect("No log file specified for character name detection."));
 
-        if let Ok(file) = File::open(target_file) {
-            let mut reader = BufReader::new(file);
-            let mut buffer = Vec::new();
-            if let Err(_) = reader.read_to_end(&mut buffer) {
-                return None;
-            }
+        self.read_header(target_file).ok()?.listener
+    }
 
-            let (cow, _, _) = UTF_16LE.decode(&buffer);
-            let content = cow.into_owned();
+    // 헤더만 읽고 멈춤 (본문은 디코딩하지 않음)
+    // Listener는 해당 파일 언어의 listener 패턴을 먼저 적용 (패턴 파일로 바꾼 패턴이 우선),
+    // 찾지 못하면 기본 헤더 레이블, 그래도 없으면 다른 언어의 패턴으로 찾음
+    pub fn read_header(&self, file_path: &Path) -> std::io::Result<ChatLogHeader> {
+        let lines = ChatLogReader::open(file_path)?.read_header();
+        let mut header = ChatLogHeader::parse(&lines);
+
+        let pattern_table = self.pattern_table();
+        let language = self.language.clone().unwrap_or_else(|| self.detect_log_language(file_path));
+        let file_patterns = pattern_table.get_or_default(&language);
+        if let Some(listener) = lines.iter().find_map(|line| file_patterns.parse_listener(line)) {
+            header.listener = Some(listener);
+        } else if header.listener.is_none() {
+            header.listener = lines.iter().find_map(|line| pattern_table.parse_listener(line));
+        }
+        Ok(header)
+    }
 
-            for line in content.lines().take(20) {
-                let processed_line = line.trim_start_matches('\u{feff}').trim();
-                if let Some(captures) = CHARACTER_NAME_REGEX.captures(processed_line) {
-                    if let Some(name_match) = captures.get(1) {
-                        let detected_name = name_match.as_str().trim().to_string();
-                        return Some(detected_name);
-                    }
-                }
-            }
+    // 세션 시작 시각 순으로 정렬 - 다른 PC에서 복사한 로그는 mtime이 바뀌므로
+    // 파일 이름의 시각(I/O 없음), 헤더의 Session started, 수정 시각 순으로 사용 (셋 다 없으면 제외)
+    // 모니터 루프가 2초마다 호출하므로 헤더는 파일 이름에 시각이 없는 파일만 읽음
+    pub fn sort_by_session_start(files: Vec<PathBuf>, newest_first: bool) -> Vec<(PathBuf, DateTime<Utc>)> {
+        let mut files_by_session: Vec<(PathBuf, DateTime<Utc>)> = files.into_iter()
+            .filter_map(|file| {
+                let session_started = chat_log_reader::session_started_from_filename(&file)
+                    .or_else(|| ChatLogHeader::read(&file).ok().and_then(|header| header.session_started))
+                    .or_else(|| std::fs::metadata(&file).and_then(|metadata| metadata.modified()).ok().map(DateTime::<Utc>::from))?;
+                Some((file, session_started))
+            })
+            .collect();
+        // 같은 시각이면 파일 이름 순 (정렬 결과가 실행마다 달라지지 않도록)
+        files_by_session.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        if newest_first {
+            files_by_session.reverse();
         }
-        None
+        files_by_session
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_patterns::LogPatternDefinition;
+
+    #[test]
+    fn listener_pattern_override_takes_precedence() {
+        let path = std::env::temp_dir().join(format!("Local_20240501_123456_{}.txt", std::process::id()));
+        std::fs::write(&path, concat!(
+            "\u{feff}\n",
+            "  Channel ID:      local\n",
+            "  Channel Name:    Local\n",
+            "  Listener:        Some Pilot [CORP]\n",
+            "  Session started: 2024.05.01 12:34:56\n",
+            "[ 2024.05.01 12:34:56 ] EVE System > Channel changed to Local : Jita\n",
+        )).unwrap();
+
+        let builtin = EveLogProcessor::with_patterns(PathBuf::new(), None, Arc::new(LogPatterns::builtin()));
+        assert_eq!(builtin.read_header(&path).unwrap().listener.as_deref(), Some("Some Pilot [CORP]"));
+
+        // 패턴 파일에서 코퍼레이션 태그를 빼도록 바꾼 en 패턴이 기본 레이블보다 우선
+        let overridden = LogPatterns::from_definitions(vec![LogPatternDefinition {
+            language: String::from("en"),
+            regex: true,
+            system_change: String::from(r"EVE System > Channel changed to Local : (?P<system>.+)$"),
+            channel_changed: String::new(),
+            unknown_system: String::from("^Unknown$"),
+            log_filename: String::from("Local_*.txt"),
+            listener: String::from(r"^\s*Listener:\s*(?P<name>.+?)(?:\s*\[.*\])?$"),
+        }]);
+        let processor = EveLogProcessor::with_patterns(PathBuf::new(), None, Arc::new(overridden));
+        assert_eq!(processor.read_header(&path).unwrap().listener.as_deref(), Some("Some Pilot"));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
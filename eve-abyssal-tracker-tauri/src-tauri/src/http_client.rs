@@ -0,0 +1,325 @@
+use std::{sync::Arc, time::Duration};
+use serde::{Deserialize, Serialize};
+use reqwest::{Client, Proxy, RequestBuilder, Response, StatusCode};
+use tokio::{sync::Semaphore, time::Instant};
+use rand::Rng;
+use anyhow::{Result, anyhow};
+use log::*;
+
+pub const DEFAULT_ESI_BASE_URL: &str = "https://esi.evetech.net/latest";
+pub const DEFAULT_FUZZWORK_BASE_URL: &str = "https://market.fuzzwork.co.uk";
+pub const DEFAULT_IMAGE_BASE_URL: &str = "https://images.evetech.net";
+pub const DEFAULT_GITHUB_BASE_URL: &str = "https://github.com";
+pub const GITHUB_REPOSITORY: &str = "bbtarzan12/AbyssalTracker";
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 15;
+pub const DEFAULT_RETRIES: u32 = 3;
+pub const DEFAULT_MAX_PARALLEL_REQUESTS: usize = 8;
+const RETRY_BASE_DELAY_MS: u64 = 500; // 재시도마다 두 배씩 증가
+const RETRY_MAX_DELAY_SECONDS: u64 = 60;
+const RETRY_JITTER_MS: u64 = 250;
+// ESI 에러 한도(보통 60초당 100회)가 이만큼 남으면 리셋될 때까지 모든 요청 대기
+const ESI_ERROR_LIMIT_SAFETY_MARGIN: u64 = 10;
+// ESI가 420을 보냈지만 리셋 헤더가 없을 때 대기 시간
+const ESI_ERROR_LIMITED_WAIT_SECONDS: u64 = 60;
+
+// ESI, Fuzzwork, 이미지 서버, GitHub 요청에 공통으로 쓰는 HTTP 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpSettings {
+    pub esi_base_url: String,
+    pub fuzzwork_base_url: String,
+    pub image_base_url: String,
+    pub github_base_url: String,
+    pub timeout_seconds: u64,
+    pub proxy: String, // 비어 있으면 시스템 프록시 설정 사용 (예: http://proxy.corp:8080)
+    pub contact: String, // ESI 요청대로 User-Agent에 넣는 연락처 (캐릭터 이름이나 이메일)
+    pub retries: u32, // 연결 실패, 타임아웃, 420/429/5xx 응답 시 재시도 횟수
+    pub max_parallel_requests: usize, // 앱 전체 동시 요청 수 상한
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            esi_base_url: String::from(DEFAULT_ESI_BASE_URL),
+            fuzzwork_base_url: String::from(DEFAULT_FUZZWORK_BASE_URL),
+            image_base_url: String::from(DEFAULT_IMAGE_BASE_URL),
+            github_base_url: String::from(DEFAULT_GITHUB_BASE_URL),
+            timeout_seconds: DEFAULT_TIMEOUT_SECONDS,
+            proxy: String::new(),
+            contact: String::new(),
+            retries: DEFAULT_RETRIES,
+            max_parallel_requests: DEFAULT_MAX_PARALLEL_REQUESTS,
+        }
+    }
+}
+
+impl HttpSettings {
+    pub fn user_agent(&self) -> String {
+        let contact = self.contact.trim();
+        if contact.is_empty() {
+            format!("EVE-Abyssal-Tracker/{} (+{}/{})", env!("CARGO_PKG_VERSION"), DEFAULT_GITHUB_BASE_URL, GITHUB_REPOSITORY)
+        } else {
+            format!("EVE-Abyssal-Tracker/{} (+{}/{}; {})", env!("CARGO_PKG_VERSION"), DEFAULT_GITHUB_BASE_URL, GITHUB_REPOSITORY, contact)
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for (name, url) in [
+            ("esi_base_url", &self.esi_base_url),
+            ("fuzzwork_base_url", &self.fuzzwork_base_url),
+            ("image_base_url", &self.image_base_url),
+            ("github_base_url", &self.github_base_url),
+        ] {
+            reqwest::Url::parse(url.trim()).map_err(|e| anyhow!("Invalid {} '{}': {}", name, url.trim(), e))?;
+        }
+        if self.timeout_seconds == 0 {
+            return Err(anyhow!("timeout_seconds must be greater than 0"));
+        }
+        if self.max_parallel_requests == 0 {
+            return Err(anyhow!("max_parallel_requests must be greater than 0"));
+        }
+        if !self.proxy.trim().is_empty() {
+            Proxy::all(self.proxy.trim()).map_err(|e| anyhow!("Invalid proxy '{}': {}", self.proxy.trim(), e))?;
+        }
+        Ok(())
+    }
+}
+
+// 앱 상태로 하나만 두고 모든 HttpClient가 공유
+pub type SharedRequestScheduler = Arc<RequestScheduler>;
+
+// 모든 요청이 공유하는 동시 요청 수 제한과 ESI 에러 한도/레이트 리밋에 따른 일시 정지
+pub struct RequestScheduler {
+    permits: Semaphore,
+    max_parallel_requests: tokio::sync::Mutex<usize>,
+    paused_until: std::sync::Mutex<Option<Instant>>,
+}
+
+impl RequestScheduler {
+    pub fn new(max_parallel_requests: usize) -> Self {
+        let max_parallel_requests = max_parallel_requests.max(1);
+        Self {
+            permits: Semaphore::new(max_parallel_requests),
+            max_parallel_requests: tokio::sync::Mutex::new(max_parallel_requests),
+            paused_until: std::sync::Mutex::new(None),
+        }
+    }
+
+    // 동시 요청 수 상한 변경 (줄일 때는 진행 중인 요청이 허가를 반환할 때까지 기다림)
+    pub async fn set_max_parallel_requests(&self, max_parallel_requests: usize) {
+        let max_parallel_requests = max_parallel_requests.max(1);
+        let mut current = self.max_parallel_requests.lock().await;
+        if max_parallel_requests > *current {
+            self.permits.add_permits(max_parallel_requests - *current);
+        } else if max_parallel_requests < *current {
+            match self.permits.acquire_many((*current - max_parallel_requests) as u32).await {
+                Ok(permits) => permits.forget(),
+                Err(_) => return,
+            }
+        }
+        *current = max_parallel_requests;
+    }
+
+    async fn wait_if_paused(&self) {
+        loop {
+            let paused_until = *self.paused_until.lock().unwrap_or_else(|e| e.into_inner());
+            match paused_until {
+                Some(until) if until > Instant::now() => tokio::time::sleep_until(until).await,
+                _ => return,
+            }
+        }
+    }
+
+    fn pause_for(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut paused_until = self.paused_until.lock().unwrap_or_else(|e| e.into_inner());
+        if paused_until.map_or(true, |current| current < until) {
+            *paused_until = Some(until);
+        }
+    }
+
+    // ESI 응답의 X-ESI-Error-Limit-Remain/Reset 헤더로 에러 한도 소진 전에 멈춤
+    fn observe(&self, response: &Response) {
+        let Some(remain) = header_u64(response, "x-esi-error-limit-remain") else {
+            return;
+        };
+        if remain <= ESI_ERROR_LIMIT_SAFETY_MARGIN {
+            let reset_seconds = header_u64(response, "x-esi-error-limit-reset").unwrap_or(ESI_ERROR_LIMITED_WAIT_SECONDS);
+            warn!("ESI 에러 한도 {}회 남음, {}초 동안 요청 중지", remain, reset_seconds + 1);
+            self.pause_for(Duration::from_secs(reset_seconds + 1));
+        }
+    }
+}
+
+fn header_u64(response: &Response, name: &str) -> Option<u64> {
+    response.headers().get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+}
+
+// 지수 백오프 (최대 60초) + 동시에 실패한 요청이 같은 시각에 몰리지 않도록 지터
+fn backoff_delay(attempt: u32) -> Duration {
+    let delay_ms = RETRY_BASE_DELAY_MS.saturating_mul(1 << attempt.min(16)).min(RETRY_MAX_DELAY_SECONDS * 1000);
+    Duration::from_millis(delay_ms + rand::thread_rng().gen_range(0..=RETRY_JITTER_MS))
+}
+
+// 설정이 적용된 공유 reqwest 클라이언트 (Client는 내부적으로 Arc라 복제 비용이 작고, 스케줄러는 앱 전체에서 공유)
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+    settings: HttpSettings,
+    scheduler: SharedRequestScheduler,
+}
+
+impl HttpClient {
+    pub fn new(settings: HttpSettings, scheduler: SharedRequestScheduler) -> Result<Self> {
+        settings.validate()?;
+        let mut builder = Client::builder()
+            .user_agent(settings.user_agent())
+            .timeout(Duration::from_secs(settings.timeout_seconds))
+            .connect_timeout(Duration::from_secs(settings.timeout_seconds.min(10)));
+        if !settings.proxy.trim().is_empty() {
+            builder = builder.proxy(Proxy::all(settings.proxy.trim())?);
+        }
+        let client = builder.build()?;
+        Ok(Self { client, settings, scheduler })
+    }
+
+    // 설정이 잘못된 경우 기본 HTTP 설정 사용
+    pub fn new_or_default(settings: HttpSettings, scheduler: SharedRequestScheduler) -> Self {
+        Self::new(settings, scheduler.clone()).unwrap_or_else(|e| {
+            warn!("HTTP 설정 오류, 기본 설정 사용: {}", e);
+            Self::new(HttpSettings::default(), scheduler.clone()).unwrap_or_else(|_| Self {
+                client: Client::new(),
+                settings: HttpSettings::default(),
+                scheduler,
+            })
+        })
+    }
+
+    pub fn settings(&self) -> &HttpSettings {
+        &self.settings
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    // 동시 요청 수를 제한하고, 연결 실패/타임아웃/5xx는 지수 백오프로,
+    // 420(ESI 에러 한도)/429는 서버가 알려준 시간만큼 모든 요청을 멈춘 뒤 재시도 (그 외 4xx는 그대로 반환)
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            self.scheduler.wait_if_paused().await;
+            let permit = self.scheduler.permits.acquire().await?;
+
+            // 본문이 스트림이라 복제할 수 없는 요청은 한 번만 전송
+            let Some(attempt_request) = request.try_clone() else {
+                let response = request.send().await?;
+                self.scheduler.observe(&response);
+                return Ok(response);
+            };
+
+            let retry_delay = match attempt_request.send().await {
+                Ok(response) => {
+                    self.scheduler.observe(&response);
+                    let status = response.status();
+                    let rate_limited = status.as_u16() == 420 || status == StatusCode::TOO_MANY_REQUESTS;
+                    if !(rate_limited || status.is_server_error()) || attempt >= self.settings.retries {
+                        return Ok(response);
+                    }
+
+                    if rate_limited {
+                        let wait_seconds = header_u64(&response, "retry-after")
+                            .or_else(|| header_u64(&response, "x-esi-error-limit-reset"))
+                            .unwrap_or(ESI_ERROR_LIMITED_WAIT_SECONDS);
+                        let delay = Duration::from_secs(wait_seconds + 1);
+                        self.scheduler.pause_for(delay);
+                        warn!("HTTP {} 응답, {}초 후 재시도 {}/{}: {}", status, delay.as_secs(), attempt + 1, self.settings.retries, response.url());
+                        Duration::ZERO
+                    } else {
+                        let delay = backoff_delay(attempt);
+                        warn!("HTTP {} 응답, {}ms 후 재시도 {}/{}: {}", status, delay.as_millis(), attempt + 1, self.settings.retries, response.url());
+                        delay
+                    }
+                }
+                Err(e) if (e.is_timeout() || e.is_connect()) && attempt < self.settings.retries => {
+                    let delay = backoff_delay(attempt);
+                    warn!("HTTP 요청 실패, {}ms 후 재시도 {}/{}: {}", delay.as_millis(), attempt + 1, self.settings.retries, e);
+                    delay
+                }
+                Err(e) => return Err(e.into()),
+            };
+            // 백오프 동안에는 다른 요청이 쓸 수 있도록 허가 반환
+            drop(permit);
+            tokio::time::sleep(retry_delay).await;
+            attempt += 1;
+        }
+    }
+
+    pub fn max_parallel_requests(&self) -> usize {
+        self.settings.max_parallel_requests.max(1)
+    }
+
+    pub fn esi_url(&self, path: &str) -> String {
+        join_url(&self.settings.esi_base_url, path)
+    }
+
+    pub fn fuzzwork_url(&self, path: &str) -> String {
+        join_url(&self.settings.fuzzwork_base_url, path)
+    }
+
+    pub fn image_url(&self, path: &str) -> String {
+        join_url(&self.settings.image_base_url, path)
+    }
+
+    pub fn github_url(&self, path: &str) -> String {
+        join_url(&self.settings.github_base_url, &format!("{}/{}", GITHUB_REPOSITORY, path.trim_start_matches('/')))
+    }
+}
+
+fn join_url(base_url: &str, path: &str) -> String {
+    format!("{}/{}", base_url.trim().trim_end_matches('/'), path.trim_start_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn scheduler_resizes_shared_permits() {
+        let scheduler = RequestScheduler::new(4);
+        scheduler.set_max_parallel_requests(6).await;
+        assert_eq!(scheduler.permits.available_permits(), 6);
+
+        // 진행 중인 요청이 있어도 반환된 허가로 줄어듦
+        let in_flight = scheduler.permits.acquire_many(3).await.unwrap();
+        let shrink = scheduler.set_max_parallel_requests(2);
+        tokio::pin!(shrink);
+        assert!(tokio::time::timeout(Duration::from_millis(20), &mut shrink).await.is_err());
+        drop(in_flight);
+        shrink.await;
+        assert_eq!(scheduler.permits.available_permits(), 2);
+
+        scheduler.set_max_parallel_requests(0).await;
+        assert_eq!(scheduler.permits.available_permits(), 1);
+    }
+
+    #[test]
+    fn pause_keeps_the_latest_deadline() {
+        let scheduler = RequestScheduler::new(1);
+        scheduler.pause_for(Duration::from_secs(60));
+        let first = scheduler.paused_until.lock().unwrap().unwrap();
+        scheduler.pause_for(Duration::from_secs(1));
+        assert_eq!(scheduler.paused_until.lock().unwrap().unwrap(), first);
+    }
+
+    #[test]
+    fn joins_base_urls_and_paths() {
+        assert_eq!(join_url("https://esi.evetech.net/latest/", "/markets/prices/"), "https://esi.evetech.net/latest/markets/prices/");
+        assert_eq!(join_url(" https://images.evetech.net ", "types/34/icon"), "https://images.evetech.net/types/34/icon");
+    }
+}
@@ -2,22 +2,43 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use serde_json::Value;
 use tokio::fs;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use base64::Engine;
 use log::*;
 
+use crate::http_client::HttpClient;
+use crate::static_types::SharedStaticTypes;
+
+// 이미지 서버에서 받은 아이콘을 저장하는 데이터 디렉토리 하위 폴더
+const ICON_DIR_NAME: &str = "icons";
+
 pub struct IconCache {
     data_dir: PathBuf,
     type_id_cache: HashMap<String, u32>,
+    http: HttpClient,
+    static_types: SharedStaticTypes,
+    offline: bool, // 오프라인 모드 - 저장된 아이콘만 사용
 }
 
 impl IconCache {
-    pub fn new(data_dir: PathBuf) -> Self {
+    pub fn new(data_dir: PathBuf, http: HttpClient, static_types: SharedStaticTypes) -> Self {
         Self {
             data_dir,
             type_id_cache: HashMap::new(),
+            http,
+            static_types,
+            offline: false,
         }
     }
 
+    pub fn set_http_client(&mut self, http: HttpClient) {
+        self.http = http;
+    }
+
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
     pub async fn initialize(&mut self) -> Result<()> {
         // typeid_cache.json 로드
         self.load_type_id_cache().await?;
@@ -58,6 +79,11 @@ impl IconCache {
     pub fn get_type_id(&self, item_name: &str) -> Option<u32> {
         // 아이템 이름에서 * 제거 후 조회
         let cleaned_name = item_name.replace('*', "").trim().to_string();
+
+        // 정적 타입 DB 먼저 (모든 언어 이름, 대소문자 무시)
+        if let Some(type_id) = self.static_types.read().unwrap_or_else(|e| e.into_inner()).get_type_id(&cleaned_name) {
+            return Some(type_id);
+        }
         
         // 원본 이름으로 먼저 시도
         if let Some(type_id) = self.type_id_cache.get(item_name) {
@@ -102,9 +128,12 @@ impl IconCache {
     }
 
     pub async fn get_available_image_types(&self, type_id: u32) -> Result<Vec<String>> {
-        let url = format!("https://images.evetech.net/types/{}", type_id);
+        if self.offline {
+            return Ok(vec!["icon".to_string()]); // 기본값으로 icon 반환
+        }
+        let url = self.http.image_url(&format!("types/{}", type_id));
         
-        let response = reqwest::get(&url).await?;
+        let response = self.http.send(self.http.get(&url)).await?;
         if response.status().is_success() {
             let types: Vec<String> = response.json().await?;
             Ok(types)
@@ -113,19 +142,53 @@ impl IconCache {
         }
     }
 
+    // 저장된 아이콘이 있으면 data URL, 없으면 받아서 저장 (받지 못하면 이미지 서버 주소)
+    // 오프라인 모드에서 저장된 아이콘이 없으면 빈 문자열 - 프론트엔드는 기본 아이콘 표시
     pub async fn get_best_image_url(&self, type_id: u32, item_name: &str) -> Result<String> {
         // 아이템 이름에 Blueprint가 있으면 bp, 아니면 icon
-        if item_name.contains("Blueprint") {
-            let bp_url = format!("https://images.evetech.net/types/{}/bp", type_id);
-            Ok(bp_url)
-        } else {
-            let icon_url = format!("https://images.evetech.net/types/{}/icon", type_id);
-            Ok(icon_url)
+        let kind = if item_name.contains("Blueprint") { "bp" } else { "icon" };
+        let cached_path = self.data_dir.join(ICON_DIR_NAME).join(format!("{}_{}.png", type_id, kind));
+        if let Ok(bytes) = fs::read(&cached_path).await {
+            return Ok(Self::data_url(&bytes));
+        }
+        if self.offline {
+            return Ok(String::new());
+        }
+
+        let url = self.http.image_url(&format!("types/{}/{}", type_id, kind));
+        match self.download_icon(&url, &cached_path).await {
+            Ok(bytes) => Ok(Self::data_url(&bytes)),
+            Err(e) => {
+                debug!("IconCache: Failed to download {}: {}", url, e);
+                Ok(url)
+            }
+        }
+    }
+
+    async fn download_icon(&self, url: &str, path: &PathBuf) -> Result<Vec<u8>> {
+        let response = self.http.send(self.http.get(url)).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("HTTP {}", response.status()));
+        }
+        let bytes = response.bytes().await?.to_vec();
+
+        // 잘린 파일이 남지 않도록 임시 파일에 쓴 후 교체
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await?;
         }
+        let temp_path = path.with_extension("png.tmp");
+        fs::write(&temp_path, &bytes).await?;
+        fs::rename(&temp_path, path).await?;
+        Ok(bytes)
+    }
+
+    fn data_url(bytes: &[u8]) -> String {
+        let mime = if bytes.starts_with(b"\x89PNG") { "image/png" } else { "image/jpeg" };
+        format!("data:{};base64,{}", mime, base64::engine::general_purpose::STANDARD.encode(bytes))
     }
 
     pub fn get_icon_url(&self, type_id: u32) -> String {
-        format!("https://images.evetech.net/types/{}/icon", type_id)
+        self.http.image_url(&format!("types/{}/icon", type_id))
     }
 
     pub fn get_multiple_type_ids(&self, item_names: &[String]) -> HashMap<String, u32> {
@@ -137,4 +200,14 @@ impl IconCache {
         }
         result
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_url_detects_png() {
+        assert_eq!(IconCache::data_url(b"\x89PNG\r\n"), "data:image/png;base64,iVBORw0K");
+        assert_eq!(IconCache::data_url(&[0xFF, 0xD8, 0xFF]), "data:image/jpeg;base64,/9j/");
+    }
+}
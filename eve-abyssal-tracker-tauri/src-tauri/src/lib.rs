@@ -4,7 +4,7 @@ use tokio::sync::Mutex;
 use std::sync::atomic::{AtomicBool, Ordering};
 use polars::prelude::*;
 use polars::io::json::JsonWriter;
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Utc};
 use serde_json;
 use tokio::fs;
 use tauri_plugin_log::{Target, TargetKind};
@@ -24,7 +24,7 @@ mod system_change_processor; // system_change_processor 모듈 선언
 use system_change_processor::SystemChangeProcessor;
 
 mod abyssal_data_analyzer;
-use abyssal_data_analyzer::{AbyssalDataAnalyzer, AnalysisResult};
+use abyssal_data_analyzer::{AbyssalDataAnalyzer, AnalysisResult, PeriodStats};
 
 mod abyssal_run_tracker;
 use abyssal_run_tracker::AbyssalRunTracker;
@@ -32,6 +32,17 @@ use abyssal_run_tracker::AbyssalRunTracker;
 mod icon_cache; // 아이콘 캐싱 모듈 추가
 use icon_cache::IconCache;
 
+mod time_display; // UTC 저장 시각과 표시 시간대 변환
+mod price_provider; // 시세 제공자 (Fuzzwork, ESI, 정적 파일)
+mod http_client; // ESI, Fuzzwork, 이미지, GitHub 공통 HTTP 설정
+use http_client::{HttpClient, HttpSettings, RequestScheduler, SharedRequestScheduler};
+pub mod static_types; // SDE 기반 정적 타입 DB (모든 언어 이름 → type_id)
+use static_types::StaticTypeDb;
+mod log_patterns; // 언어별 로그 패턴 (앱 데이터 디렉토리의 log_patterns.json으로 덮어쓰기)
+mod chat_log_reader; // BOM으로 인코딩을 판단하는 스트리밍 채팅 로그 리더
+
+const INSTALLER_DOWNLOAD_TIMEOUT_SECONDS: u64 = 600;
+
 // 어비셜 윈도우 활성화 상태 (전역)
 static ABYSSAL_WINDOW_ENABLED: AtomicBool = AtomicBool::new(true);
 
@@ -41,14 +52,11 @@ static ABYSSAL_WINDOW_ENABLED: AtomicBool = AtomicBool::new(true);
 async fn analyze_abyssal_data_command(app_handle: AppHandle) -> Result<AnalysisResult, String> {
     let abyssal_data_analyzer = app_handle.state::<Arc<Mutex<AbyssalDataAnalyzer>>>();
     
-    // AbyssalDataAnalyzer에 AppHandle을 설정
+    // AbyssalDataAnalyzer에 AppHandle을 설정 (진행 상황 이벤트용, 분석 상태와 시세 설정은 유지)
     {
         let mut analyzer = abyssal_data_analyzer.lock().await;
         if analyzer.app_handle.is_none() {
-            let eve_api = app_handle.state::<Arc<Mutex<EVEApi>>>();
-            let abyssal_data_manager = app_handle.state::<Arc<Mutex<AbyssalDataManager>>>();
-            *analyzer = AbyssalDataAnalyzer::new(eve_api.inner().clone(), abyssal_data_manager.inner().clone())
-                .with_app_handle(app_handle.clone());
+            analyzer.app_handle = Some(app_handle.clone());
         }
     }
     
@@ -124,6 +132,14 @@ async fn find_all_log_files_command(app_handle: AppHandle) -> Result<Vec<String>
     Ok(files.into_iter().map(|p| p.to_string_lossy().into_owned()).collect())
 }
 
+#[tauri::command]
+async fn get_chat_log_header_command(app_handle: AppHandle, file_path: String) -> Result<chat_log_reader::ChatLogHeader, String> {
+    let eve_log_processor = app_handle.state::<Arc<Mutex<EveLogProcessor>>>();
+    let path = std::path::PathBuf::from(file_path);
+    let processor = eve_log_processor.lock().await;
+    processor.read_header(&path).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn detect_character_name_command(app_handle: AppHandle, file_path: String) -> Result<Option<String>, String> {
     let eve_log_processor = app_handle.state::<Arc<Mutex<EveLogProcessor>>>();
@@ -152,16 +168,16 @@ async fn save_abyssal_result_command(
     acquired_items: String, 
     abyssal_type: String,
     ship_class: i32
-) -> Result<(), String> {
+) -> Result<String, String> {
     let abyssal_data_manager = app_handle.state::<Arc<Mutex<AbyssalDataManager>>>();
     
-    // 문자열을 DateTime으로 변환
+    // 문자열을 UTC DateTime으로 변환
     let start_dt = chrono::DateTime::parse_from_str(&start_time, "%Y-%m-%d %H:%M:%S %z")
         .map_err(|e| format!("Failed to parse start_time: {}", e))?
-        .with_timezone(&chrono::Local);
+        .with_timezone(&Utc);
     let end_dt = chrono::DateTime::parse_from_str(&end_time, "%Y-%m-%d %H:%M:%S %z")
         .map_err(|e| format!("Failed to parse end_time: {}", e))?
-        .with_timezone(&chrono::Local);
+        .with_timezone(&Utc);
     
     let result = abyssal_data_manager.lock().await.save_abyssal_result(start_dt, end_dt, acquired_items, abyssal_type, ship_class)
         .map_err(|e| e.to_string());
@@ -177,27 +193,20 @@ async fn save_abyssal_result(
     end_time: String,
     _duration: String,
     ship_class: i32
-) -> Result<(), String> {
+) -> Result<String, String> {
     let abyssal_data_manager = app_handle.state::<Arc<Mutex<AbyssalDataManager>>>();
+    let manager = abyssal_data_manager.lock().await;
     
-    // 시간 문자열을 NaiveTime으로 변환 (KST)
-    let start_time_naive = chrono::NaiveTime::parse_from_str(&start_time, "%H:%M:%S")
-        .map_err(|e| format!("Failed to parse start_time '{}': {}", start_time, e))?;
-    let end_time_naive = chrono::NaiveTime::parse_from_str(&end_time, "%H:%M:%S")
-        .map_err(|e| format!("Failed to parse end_time '{}': {}", end_time, e))?;
-    
-    // 오늘 날짜로 DateTime 생성
-    let today = chrono::Local::now().date_naive();
-    let start_datetime = today.and_time(start_time_naive).and_local_timezone(chrono::Local).unwrap();
-    let end_datetime = today.and_time(end_time_naive).and_local_timezone(chrono::Local).unwrap();
+    let (start_datetime, end_datetime) = manager.time_display().resolve_run_times(&start_time, &end_time, Utc::now())?;
     
-    let result = abyssal_data_manager.lock().await
+    let result = manager
         .save_abyssal_result(start_datetime, end_datetime, items, abyssal_type, ship_class)
         .map_err(|e| e.to_string());
+    drop(manager);
     
     match &result {
-        Ok(_) => {
-            info!("Abyssal result saved successfully");
+        Ok(run_id) => {
+            info!("Abyssal result saved successfully (run_id: {})", run_id);
             // 새 런이 저장되면 프론트엔드에 이벤트 발생
             let _ = app_handle.emit("abyssal_run_completed", ());
                          info!("Emitted abyssal_run_completed event");
@@ -215,12 +224,20 @@ async fn open_abyssal_result_window(
     end_time: String,
     duration: String,
 ) -> Result<(), String> {
+    // 시작/종료 시각은 UTC로 받고, 창에 표시할 시각은 설정된 시간대로 변환
+    let time_display = app_handle.state::<Arc<Mutex<AbyssalDataManager>>>().lock().await.time_display();
+    let to_display = |value: &str| time_display::parse_utc(value)
+        .map(|utc| time_display.format(utc))
+        .unwrap_or_else(|| value.to_string());
+    
     // URL 파라미터 생성
     let url = format!(
-        "abyssal-result.html?start_time={}&end_time={}&duration={}",
+        "abyssal-result.html?start_time={}&end_time={}&duration={}&start_time_display={}&end_time_display={}",
         urlencoding::encode(&start_time),
         urlencoding::encode(&end_time),
-        urlencoding::encode(&duration)
+        urlencoding::encode(&duration),
+        urlencoding::encode(&to_display(&start_time)),
+        urlencoding::encode(&to_display(&end_time))
     );
     
     // 새 윈도우 생성 (Tauri 2 방식)
@@ -245,7 +262,6 @@ async fn open_abyssal_result_window(
 
 #[tauri::command]
 async fn test_abyssal_window(app_handle: AppHandle) -> Result<(), String> {
-    use chrono::{Local, Timelike};
     use rand::Rng;
     
     // 랜덤 값들을 미리 생성 (Send 문제 해결)
@@ -260,18 +276,19 @@ async fn test_abyssal_window(app_handle: AppHandle) -> Result<(), String> {
         )
     };
     
-    // 시작 시간 생성
-    let today = Local::now().date_naive();
-    let start_time = today
+    // 시작 시간 생성 (표시 시간대 기준 오늘)
+    let time_display = app_handle.state::<Arc<Mutex<AbyssalDataManager>>>().lock().await.time_display();
+    let today = time_display.date_of(Utc::now());
+    let start_time = time_display.from_display(today
         .and_hms_opt(start_hour, start_minute, start_second)
-        .unwrap();
+        .unwrap());
     
     // 종료 시간 계산
     let end_time = start_time + chrono::Duration::minutes(duration_minutes) + chrono::Duration::seconds(duration_seconds);
     
-    // 문자열 포맷
-    let start_time_str = format!("{:02}:{:02}:{:02}", start_time.hour(), start_time.minute(), start_time.second());
-    let end_time_str = format!("{:02}:{:02}:{:02}", end_time.hour(), end_time.minute(), end_time.second());
+    // 문자열 포맷 (UTC)
+    let start_time_str = time_display::format_utc(start_time);
+    let end_time_str = time_display::format_utc(end_time);
     let duration_str = format!("{}m {}s", duration_minutes, duration_seconds);
     
     if let Err(e) = open_abyssal_result_window(
@@ -290,13 +307,12 @@ async fn test_abyssal_window(app_handle: AppHandle) -> Result<(), String> {
 #[tauri::command]
 async fn delete_abyssal_run_command(
     app_handle: AppHandle,
-    start_time_kst: String,
-    end_time_kst: String
+    run_id: String
 ) -> Result<(), String> {
     let abyssal_data_manager = app_handle.state::<Arc<Mutex<AbyssalDataManager>>>();
     let manager = abyssal_data_manager.lock().await;
     
-    let result = manager.delete_abyssal_run(&start_time_kst, &end_time_kst)
+    let result = manager.delete_abyssal_run(&run_id)
         .map_err(|e| e.to_string());
     
     match &result {
@@ -308,23 +324,67 @@ async fn delete_abyssal_run_command(
 }
 
 #[tauri::command]
-async fn light_refresh_abyssal_data_command(app_handle: AppHandle) -> Result<AnalysisResult, String> {
+async fn update_abyssal_run_command(
+    app_handle: AppHandle,
+    run_id: String,
+    start_time: String,
+    end_time: String,
+    acquired_items: String,
+    abyssal_type: String,
+    ship_class: i32
+) -> Result<(), String> {
     let abyssal_data_manager = app_handle.state::<Arc<Mutex<AbyssalDataManager>>>();
-    let abyssal_data_analyzer = app_handle.state::<Arc<Mutex<AbyssalDataAnalyzer>>>();
+    let manager = abyssal_data_manager.lock().await;
     
-    // CSV 데이터만 다시 로드
-    let df = {
-        let manager = abyssal_data_manager.lock().await;
-        manager.load_abyssal_results().map_err(|e| e.to_string())?
-    };
+    // 표시 시간대의 "YYYY-MM-DD HH:MM:SS" 시각을 UTC로 변환
+    let time_display = manager.time_display();
+    let start_dt = time_display.parse_display(&start_time)?;
+    let end_dt = time_display.parse_display(&end_time)?;
     
-    // 기존 캐시된 가격 정보로 빠른 분석 (새로운 아이템 발견 시에만 API 호출)
+    let result = manager
+        .update_abyssal_run(&run_id, start_dt, end_dt, acquired_items, abyssal_type, ship_class);
+    drop(manager);
+    
+    match &result {
+        Ok(_) => {
+            info!("Abyssal run updated successfully (run_id: {})", run_id);
+            // 수정된 런을 반영하도록 프론트엔드에 이벤트 발생
+            let _ = app_handle.emit("abyssal_run_completed", ());
+        },
+        Err(e) => warn!("Failed to update abyssal run: {}", e),
+    }
+    
+    result
+}
+
+#[tauri::command]
+async fn light_refresh_abyssal_data_command(app_handle: AppHandle) -> Result<AnalysisResult, String> {
+    let abyssal_data_analyzer = app_handle.state::<Arc<Mutex<AbyssalDataAnalyzer>>>();
+    
+    // 저장/수정/삭제된 런만 다시 계산 (새로운 아이템 발견 시에만 API 호출)
     let mut analyzer = abyssal_data_analyzer.lock().await;
-    let result = analyzer.light_analyze_data(df).await.map_err(|e| e.to_string())?;
+    let result = analyzer.refresh().await.map_err(|e| e.to_string())?;
     
     Ok(result)
 }
 
+#[tauri::command]
+async fn get_range_stats_command(app_handle: AppHandle, start_date: String, end_date: String) -> Result<PeriodStats, String> {
+    // "YYYY-MM-DD" 형식, 시작일과 종료일 포함
+    let parse_date = |value: &str| chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|e| format!("Failed to parse date '{}': {}", value, e));
+    let start = parse_date(&start_date)?;
+    let end = parse_date(&end_date)?;
+    if end < start {
+        return Err(format!("End date {} is before start date {}", end_date, start_date));
+    }
+    
+    let abyssal_data_analyzer = app_handle.state::<Arc<Mutex<AbyssalDataAnalyzer>>>();
+    let mut analyzer = abyssal_data_analyzer.lock().await;
+    analyzer.refresh().await.map_err(|e| e.to_string())?;
+    Ok(analyzer.range_stats(start, end))
+}
+
 #[tauri::command]
 async fn process_log_line_command(app_handle: AppHandle, line: String) -> Result<(), String> {
     let system_change_processor = app_handle.state::<Arc<Mutex<SystemChangeProcessor>>>();
@@ -378,6 +438,7 @@ async fn get_abyssal_window_enabled() -> Result<bool, String> {
 
 #[tauri::command]
 async fn get_current_log_file_info(app_handle: AppHandle) -> Result<Option<serde_json::Value>, String> {
+    let time_display = app_handle.state::<Arc<Mutex<ConfigManager>>>().lock().await.time_display();
     let log_monitor = app_handle.state::<Arc<Mutex<LogMonitor>>>();
     let monitor = log_monitor.lock().await;
     
@@ -393,12 +454,9 @@ async fn get_current_log_file_info(app_handle: AppHandle) -> Result<Option<serde
         let (file_size, modified_time) = if let Ok(metadata) = std::fs::metadata(&log_file) {
             let size = metadata.len();
             let modified_time_str = if let Ok(modified) = metadata.modified() {
-                // 시스템 시간을 chrono DateTime으로 변환
+                // 시스템 시간을 chrono DateTime으로 변환 후 표시 시간대로 포맷
                 let datetime: chrono::DateTime<chrono::Utc> = modified.into();
-                // 한국 시간(KST = UTC+9)으로 변환
-                let kst_datetime = datetime.with_timezone(&chrono::FixedOffset::east_opt(9 * 3600).unwrap());
-                // 문자열로 포맷
-                kst_datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+                time_display.format(datetime)
             } else {
                 "정보 없음".to_string()
             };
@@ -468,8 +526,10 @@ async fn get_filament_name(app_handle: AppHandle, abyssal_type: String) -> Resul
 }
 
 #[tauri::command]
-async fn get_icon_url(type_id: u32) -> Result<String, String> {
-    Ok(format!("https://images.evetech.net/types/{}/icon", type_id))
+async fn get_icon_url(app_handle: AppHandle, type_id: u32) -> Result<String, String> {
+    let icon_cache = app_handle.state::<Arc<Mutex<IconCache>>>();
+    let cache = icon_cache.lock().await;
+    Ok(cache.get_icon_url(type_id))
 }
 
 #[tauri::command]
@@ -496,7 +556,8 @@ async fn check_for_update_command(app_handle: AppHandle) -> Result<serde_json::V
     let current_version = app_handle.package_info().version.to_string();
     
     // GitHub API를 통해 최신 버전 정보 가져오기
-    let latest_version = match get_latest_github_version().await {
+    let http = http_client_from_config(&app_handle).await;
+    let latest_version = match get_latest_github_version(&http).await {
         Ok(version) => version,
         Err(e) => {
             warn!("Failed to get latest version from GitHub: {}", e);
@@ -553,22 +614,27 @@ fn compare_versions(current: &str, latest: &str) -> bool {
     false
 }
 
-async fn get_latest_github_version() -> Result<String, String> {
-    let (version, _) = get_latest_github_release().await?;
+// 설정 로드 전에 호출되면 기본 HTTP 설정 사용 (스케줄러는 항상 앱 상태의 것을 공유)
+async fn http_client_from_config(app_handle: &AppHandle) -> HttpClient {
+    let scheduler = app_handle.state::<SharedRequestScheduler>().inner().clone();
+    match app_handle.try_state::<Arc<Mutex<ConfigManager>>>() {
+        Some(config_manager) => config_manager.lock().await.http_client(&scheduler),
+        None => HttpClient::new_or_default(HttpSettings::default(), scheduler),
+    }
+}
+
+async fn get_latest_github_version(http: &HttpClient) -> Result<String, String> {
+    let (version, _) = get_latest_github_release(http).await?;
     Ok(version)
 }
 
-async fn get_latest_github_release() -> Result<(String, String), String> {
+async fn get_latest_github_release(http: &HttpClient) -> Result<(String, String), String> {
     use quick_xml::events::Event;
     use quick_xml::Reader;
     
     info!("Fetching latest release info from GitHub RSS feed...");
     
-    let client = reqwest::Client::new();
-    let response = client
-        .get("https://github.com/bbtarzan12/AbyssalTracker/releases.atom")
-        .header("User-Agent", "EVE-Abyssal-Tracker")
-        .send()
+    let response = http.send(http.get(&http.github_url("releases.atom")))
         .await
         .map_err(|e| format!("Failed to fetch RSS feed: {}", e))?;
         
@@ -631,10 +697,10 @@ async fn get_latest_github_release() -> Result<(String, String), String> {
     info!("Found latest version from RSS: {}", version);
     
     // 다운로드 URL 생성 (GitHub 릴리즈 패턴 사용)
-    let download_url = format!(
-        "https://github.com/bbtarzan12/AbyssalTracker/releases/download/v{}/EVE.Abyssal.Tracker_{}_x64-setup.exe",
+    let download_url = http.github_url(&format!(
+        "releases/download/v{}/EVE.Abyssal.Tracker_{}_x64-setup.exe",
         version, version
-    );
+    ));
     
     Ok((version, download_url))
 }
@@ -659,7 +725,8 @@ async fn download_and_install_update_command(app_handle: AppHandle) -> Result<St
     let current_version = app_handle.package_info().version.to_string();
     
     // GitHub API를 통해 최신 버전 정보와 다운로드 URL 가져오기
-    let (latest_version, download_url) = match get_latest_github_release().await {
+    let http = http_client_from_config(&app_handle).await;
+    let (latest_version, download_url) = match get_latest_github_release(&http).await {
         Ok((version, url)) => (version, url),
         Err(e) => {
             warn!("Failed to get latest version from GitHub during install: {}", e);
@@ -682,8 +749,8 @@ async fn download_and_install_update_command(app_handle: AppHandle) -> Result<St
     let installer_path = temp_dir.join(format!("EVE.Abyssal.Tracker_{}_x64-setup.exe", latest_version));
     
     // 다운로드
-    let client = reqwest::Client::new();
-    let response = client.get(&download_url).send().await
+    // 설치 파일은 크므로 공통 타임아웃 대신 넉넉한 타임아웃 사용
+    let response = http.send(http.get(&download_url).timeout(std::time::Duration::from_secs(INSTALLER_DOWNLOAD_TIMEOUT_SECONDS))).await
         .map_err(|e| format!("다운로드 실패: {}", e))?;
     
     if !response.status().is_success() {
@@ -790,9 +857,11 @@ async fn export_daily_analysis(
 ) -> Result<String, String> {
     use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
     
+    let time_display = app_handle.state::<Arc<Mutex<AbyssalDataManager>>>().lock().await.time_display();
+    
     // 분석 데이터 가져오기
     let abyssal_data_analyzer = app_handle.state::<Arc<Mutex<AbyssalDataAnalyzer>>>();
-    let analysis_result = abyssal_data_analyzer.lock().await.analyze_data().await
+    let analysis_result = abyssal_data_analyzer.lock().await.refresh().await
         .map_err(|e| format!("Failed to analyze data: {}", e))?;
     
     // 선택된 날짜의 데이터 필터링
@@ -819,7 +888,10 @@ async fn export_daily_analysis(
         
         // CSV 형식으로 변환 (순수한 테이블 데이터만)
         let mut csv_content = String::new();
-        csv_content.push_str("시작시각(KST),종료시각(KST),런 소요(분),어비셜 종류,함급,실수익,ISK/h,획득 아이템,드롭,입장료\n");
+        csv_content.push_str(&format!(
+            "시작시각({tz}),종료시각({tz}),런 소요(분),어비셜 종류,함급,실수익,ISK/h,획득 아이템,드롭,입장료\n",
+            tz = time_display.timezone_name()
+        ));
         
         for run in &daily_data.runs {
             csv_content.push_str(&format!(
@@ -872,6 +944,10 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .setup(|app| {
+            // 모든 HTTP 요청이 공유하는 스케줄러 - 동시 요청 수는 설정 로드 후 반영
+            let request_scheduler: SharedRequestScheduler = Arc::new(RequestScheduler::new(http_client::DEFAULT_MAX_PARALLEL_REQUESTS));
+            app.manage(request_scheduler.clone());
+
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
                 // Python과 동일한 초기화 순서
@@ -883,35 +959,47 @@ pub fn run() {
                 app_handle.manage(config_manager.clone());
 
                 // 2. AbyssalDataManager 초기화
+                let time_display = config_manager.lock().await.time_display();
                 let abyssal_data_manager = Arc::new(Mutex::new(
-                    AbyssalDataManager::new(app_handle.clone())
+                    AbyssalDataManager::new(app_handle.clone(), time_display)
                 ));
                 app_handle.manage(abyssal_data_manager.clone());
 
-                // 3. EVEApi 초기화
-                let eve_api = Arc::new(Mutex::new(
-                    EVEApi::new(&app_handle).await.expect("Failed to initialize EVEApi")
-                ));
-                app_handle.manage(eve_api.clone());
-
-                // 4. IconCache 초기화
+                // 3. 정적 타입 DB와 EVEApi 초기화
                 // 앱 데이터 디렉토리 사용
                 let data_dir = match app_handle.path().app_data_dir() {
                     Ok(app_data_dir) => {
                         let data_dir = app_data_dir.join("data");
                         if let Err(e) = std::fs::create_dir_all(&data_dir) {
-                            warn!("Warning: Failed to create app data directory for IconCache: {}", e);
+                            warn!("Warning: Failed to create app data directory: {}", e);
                             std::path::PathBuf::from("data")
                         } else {
                             data_dir
                         }
                     },
                     Err(e) => {
-                        warn!("Warning: Failed to get app data directory for IconCache: {}, using local data directory", e);
+                        warn!("Warning: Failed to get app data directory: {}, using local data directory", e);
                         std::path::PathBuf::from("data")
                     }
                 };
-                let mut icon_cache = IconCache::new(data_dir);
+                let static_types = Arc::new(std::sync::RwLock::new(StaticTypeDb::load(&data_dir)));
+                log_patterns::install(log_patterns::LogPatterns::load(&data_dir));
+                app_handle.manage(static_types.clone());
+
+                let (http, price_sources, market, offline) = {
+                    let config = config_manager.lock().await;
+                    (config.http_client(&request_scheduler), config.price_sources(), config.market(), config.config.prices.offline)
+                };
+                request_scheduler.set_max_parallel_requests(http.max_parallel_requests()).await;
+                let mut eve_api = EVEApi::new(&app_handle, http.clone(), static_types.clone(), &price_sources, market).await.expect("Failed to initialize EVEApi");
+                eve_api.set_offline(offline);
+                eve_api.set_time_display(time_display);
+                let eve_api = Arc::new(Mutex::new(eve_api));
+                app_handle.manage(eve_api.clone());
+
+                // 4. IconCache 초기화
+                let mut icon_cache = IconCache::new(data_dir, http, static_types);
+                icon_cache.set_offline(offline);
                 if let Err(e) = icon_cache.initialize().await {
                     error!("Failed to initialize IconCache: {}", e);
                 }
@@ -919,8 +1007,15 @@ pub fn run() {
                 app_handle.manage(icon_cache_arc);
 
                 // 5. AbyssalDataAnalyzer 초기화
+                let config_lock = config_manager.lock().await;
+                let (loot_basis, filament_basis) = config_lock.price_basis();
+                let valuation_mode = config_lock.valuation_mode();
+                drop(config_lock);
                 let abyssal_data_analyzer = Arc::new(Mutex::new(
                     AbyssalDataAnalyzer::new(eve_api.clone(), abyssal_data_manager.clone())
+                        .with_price_basis(loot_basis, filament_basis)
+                        .with_valuation_mode(valuation_mode)
+                        .with_app_handle(app_handle.clone())
                 ));
                 app_handle.manage(abyssal_data_analyzer.clone());
 
@@ -937,7 +1032,7 @@ pub fn run() {
                 // 7. SystemChangeProcessor 초기화 (Python과 동일한 콜백 구조)
                 let abyssal_data_manager_for_callback = abyssal_data_manager.clone();
                 let app_handle_for_callback = app_handle.clone();
-                let on_abyssal_run_end = Box::new(move |start_time: DateTime<Local>, end_time: DateTime<Local>| {
+                let on_abyssal_run_end = Box::new(move |start_time: DateTime<Utc>, end_time: DateTime<Utc>| {
                     // Python의 _on_abyssal_run_end와 동일한 로직
                     info!("Abyssal run ended: {} to {}", start_time, end_time);
                     
@@ -949,8 +1044,8 @@ pub fn run() {
                     
                     // 새 윈도우 열기 (원본 Python의 팝업과 동일)
                     let app_handle_clone = app_handle_for_callback.clone();
-                    let start_time_str = start_time.format("%H:%M:%S").to_string();
-                    let end_time_str = end_time.format("%H:%M:%S").to_string();
+                    let start_time_str = time_display::format_utc(start_time);
+                    let end_time_str = time_display::format_utc(end_time);
                     let duration_str_clone = duration_str.clone();
                     
                     tauri::async_runtime::spawn(async move {
@@ -990,19 +1085,22 @@ pub fn run() {
                 });
 
                 let system_change_processor_for_file_change = system_change_processor.clone();
+                let abyssal_data_manager_for_file_change = abyssal_data_manager.clone();
                 let on_log_file_change = Box::new(move || {
                     // Python의 _on_log_file_change와 동일한 로직
                     info!("Log file changed. Re-scanning past runs.");
                     let system_change_processor = system_change_processor_for_file_change.clone();
                     let config_manager = config_manager_for_callback.clone();
+                    let abyssal_data_manager = abyssal_data_manager_for_file_change.clone();
                     tauri::async_runtime::spawn(async move {
                         let config_lock = config_manager.lock().await;
                         let logs_path = config_lock.get_logs_path();
                         let character_name = config_lock.get_character_name();
                         drop(config_lock);
+                        let time_display = abyssal_data_manager.lock().await.time_display();
                         
                         system_change_processor.lock().await.scan_past_runs(&logs_path, &character_name).await;
-                        system_change_processor.lock().await.print_past_runs();
+                        system_change_processor.lock().await.print_past_runs(&time_display);
                     });
                 });
 
@@ -1042,6 +1140,7 @@ pub fn run() {
                 // Python과 동일하게 초기 스캔 수행 (에러가 발생해도 계속 진행)
                 let config_manager_clone = config_manager.clone();
                 let system_change_processor_clone = system_change_processor.clone();
+                let abyssal_data_manager_clone = abyssal_data_manager.clone();
                 
                 tokio::spawn(async move {
                     if let Ok(config_lock) = config_manager_clone.try_lock() {
@@ -1050,9 +1149,10 @@ pub fn run() {
                         drop(config_lock);
                         
                         if !logs_path.is_empty() && !character_name.is_empty() {
+                            let time_display = abyssal_data_manager_clone.lock().await.time_display();
                             if let Ok(mut processor) = system_change_processor_clone.try_lock() {
                                 processor.scan_past_runs(&logs_path, &character_name).await;
-                                processor.print_past_runs();
+                                processor.print_past_runs(&time_display);
                             }
                         }
                     } else {
@@ -1065,21 +1165,39 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             analyze_abyssal_data_command,
             light_refresh_abyssal_data_command,
+            get_range_stats_command,
             config_manager::get_config,
             config_manager::set_log_path,
             config_manager::set_character_name,
+            config_manager::set_timezone,
+            config_manager::set_day_boundary,
+            config_manager::set_price_providers,
+            config_manager::set_trade_hub,
+            config_manager::set_price_basis,
+            config_manager::set_valuation_mode,
+            config_manager::set_offline_mode,
+            config_manager::set_http_settings,
             config_manager::get_ui_config,
             config_manager::set_ui_preferences,
             load_abyssal_results_command,
             save_abyssal_result_command,
             save_abyssal_result,
             delete_abyssal_run_command,
+            update_abyssal_run_command,
             open_abyssal_result_window,
             test_abyssal_window,
             eve_api::get_type_ids,
-            eve_api::get_fuzzwork_prices,
+            eve_api::get_english_names,
+            eve_api::get_market_prices,
+            eve_api::get_price_overrides,
+            eve_api::set_price_override,
+            static_types::get_static_types_info,
+            static_types::update_static_types,
+            log_patterns::reload_log_patterns,
+            log_patterns::validate_log_patterns,
             find_all_log_files_command,
             detect_character_name_command,
+            get_chat_log_header_command,
             start_log_monitor_command,
             stop_log_monitor_command,
             process_log_line_command,
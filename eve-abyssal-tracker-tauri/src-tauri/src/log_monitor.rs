@@ -5,19 +5,75 @@ use std::{
 };
 use tokio::{
     fs::File,
-    io::{AsyncBufReadExt, BufReader, AsyncSeekExt},
+    io::{AsyncReadExt, AsyncSeekExt, SeekFrom},
     sync::{mpsc, Mutex},
     task::JoinHandle,
 };
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use crossbeam_channel as channel;
-use encoding_rs::UTF_16LE;
+use encoding_rs::{Decoder, Encoding, UTF_16BE, UTF_16LE};
 use log::*;
 
+use crate::chat_log_reader;
 use crate::config_manager::ConfigManager;
 use crate::eve_log_processor::EveLogProcessor;
 use crate::system_change_processor::SystemChangeProcessor;
 
+// 인코딩 판단과 파일 교체 감지에 쓰는 파일 앞부분 바이트 수 (헤더의 Session started 줄까지 포함)
+const FILE_PREFIX_SIZE: u64 = 1024;
+
+// 로그 파일 끝에 추가된 바이트만 디코딩하는 상태
+// 읽기 경계에서 잘린 UTF-16 코드 유닛은 디코더가, 줄바꿈이 아직 오지 않은 줄은 partial_line이 다음 읽기까지 보관
+struct LogTail {
+    decoder: Decoder,
+    partial_line: String,
+    pending_bytes: u64, // 아직 완성된 줄로 반환하지 않은 바이트 수 (디코더가 보관 중인 바이트 포함)
+    prefix: Vec<u8>, // 처음 열었을 때의 파일 앞부분 - 같은 파일인지 확인용
+}
+
+impl LogTail {
+    fn new(encoding: &'static Encoding, prefix: Vec<u8>) -> Self {
+        Self {
+            decoder: encoding.new_decoder_without_bom_handling(),
+            partial_line: String::new(),
+            pending_bytes: 0,
+            prefix,
+        }
+    }
+
+    // 추가된 바이트를 디코딩하고 완성된 줄만 반환
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        if let Some(capacity) = self.decoder.max_utf8_buffer_length(bytes.len()) {
+            self.partial_line.reserve(capacity);
+        }
+        let _ = self.decoder.decode_to_string(bytes, &mut self.partial_line, false);
+        self.pending_bytes += bytes.len() as u64;
+
+        let mut lines = Vec::new();
+        while let Some(newline) = self.partial_line.find('\n') {
+            let line: String = self.partial_line.drain(..=newline).collect();
+            self.pending_bytes = self.pending_bytes.saturating_sub(self.encoded_length(&line));
+            lines.push(line.trim().trim_start_matches('\u{feff}').to_string());
+        }
+        lines
+    }
+
+    fn encoded_length(&self, text: &str) -> u64 {
+        let encoding = self.decoder.encoding();
+        if encoding == UTF_16LE || encoding == UTF_16BE {
+            text.encode_utf16().count() as u64 * 2
+        } else {
+            text.len() as u64
+        }
+    }
+
+    // 지금 파일 앞부분이 처음 열었을 때와 같은지 (같은 길이 이상으로 다시 쓰인 파일 감지)
+    // 처음 열었을 때 파일이 짧았으면 그 길이만큼만 비교
+    fn is_same_file(&self, current_prefix: &[u8]) -> bool {
+        current_prefix.len() >= self.prefix.len() && current_prefix.starts_with(&self.prefix)
+    }
+}
+
 pub struct LogMonitor {
     config_manager: Arc<Mutex<ConfigManager>>,
     log_processor: EveLogProcessor, // 더 이상 Arc<Mutex>가 아님!
@@ -25,8 +81,8 @@ pub struct LogMonitor {
     character_name: String,
     language: Option<String>,
     log_file: Option<PathBuf>,
-    last_position: u64,
-    last_line_count: usize,  // 마지막으로 읽은 라인 수
+    last_position: u64, // 다음에 읽을 바이트 위치
+    tail: Option<LogTail>, // 없으면 다음 읽기에서 last_position부터 새로 시작
     pub monitoring: bool,
     observer: Option<RecommendedWatcher>,
     monitor_task: Arc<Mutex<Option<JoinHandle<()>>>>,
@@ -50,7 +106,7 @@ impl LogMonitor {
             language: None,
             log_file: None,
             last_position: 0,
-            last_line_count: 0,
+            tail: None,
             monitoring: false,
             observer: None,
             monitor_task: Arc::new(Mutex::new(None)),
@@ -85,28 +141,20 @@ impl LogMonitor {
             return None;
         }
 
-        // 파일과 수정 시간을 수집
-        let mut files_with_mtime: Vec<(PathBuf, std::time::SystemTime)> = Vec::new();
-        for file in files {
-            if let Ok(metadata) = std::fs::metadata(&file) {
-                if let Ok(mtime) = metadata.modified() {
-                    files_with_mtime.push((file, mtime));
-                }
-            }
-        }
-        files_with_mtime.sort_by(|a, b| b.1.cmp(&a.1)); // 최신 순으로 정렬
+        // 세션 시작 시각 최신 순으로 정렬
+        let files_by_session = EveLogProcessor::sort_by_session_start(files, true);
 
         // 캐릭터 이름이 설정된 경우: 해당 캐릭터의 최신 로그 찾기
         if !self.character_name.is_empty() {
-            for (file_path, mtime) in &files_with_mtime {
+            for (file_path, session_started) in &files_by_session {
                 // 각 파일에서 캐릭터 이름 확인
                 let detected_name = self.log_processor.detect_character_name(Some(file_path));
                 if let Some(detected) = detected_name {
                     if detected == self.character_name {
-                        info!("Found matching log for character '{}': {} (modified: {:?})", 
+                        info!("Found matching log for character '{}': {} (session started: {})", 
                             self.character_name,
                             file_path.file_name().unwrap_or_default().to_string_lossy(),
-                            mtime
+                            session_started
                         );
                         self.log_processor.set_log_file(file_path.clone());
                         return Some(file_path.clone());
@@ -115,7 +163,7 @@ impl LogMonitor {
             }
             
             warn!("No log files found for character '{}'. Available files:", self.character_name);
-            for (file_path, _) in files_with_mtime.iter().take(5) {
+            for (file_path, _) in files_by_session.iter().take(5) {
                 let detected_name = self.log_processor.detect_character_name(Some(file_path));
                 warn!("  - {} (character: {:?})", 
                     file_path.file_name().unwrap_or_default().to_string_lossy(),
@@ -126,7 +174,7 @@ impl LogMonitor {
         }
         
         // 캐릭터 이름이 설정되지 않은 경우: 최신 파일에서 자동 감지
-        if let Some((latest_file, _)) = files_with_mtime.first() {
+        if let Some((latest_file, _)) = files_by_session.first() {
             self.log_processor.set_log_file(latest_file.clone());
             
             let detected_name = self.log_processor.detect_character_name(Some(latest_file));
@@ -148,55 +196,87 @@ impl LogMonitor {
         }
     }
 
-    async fn process_new_lines(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        if self.log_file.is_none() {
-            return Ok(());
+    // 현재 파일 끝에서부터 꼬리 읽기 시작 (이미 있는 줄은 처리하지 않음)
+    async fn tail_from_end(&mut self, file_path: &Path) {
+        self.last_position = match tokio::fs::metadata(file_path).await {
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        };
+        self.tail = None;
+    }
+
+    // 마지막으로 완성된 줄 끝의 바이트 위치 (아직 줄바꿈이 오지 않은 줄은 다시 읽어야 함)
+    fn resume_position(&self) -> u64 {
+        match self.tail.as_ref() {
+            Some(tail) => self.last_position.saturating_sub(tail.pending_bytes),
+            None => self.last_position,
         }
+    }
 
-        if self.log_processor.patterns.is_empty() {
-            return Ok(());
+    async fn read_prefix(file: &mut File) -> std::io::Result<Vec<u8>> {
+        let mut prefix = Vec::new();
+        file.seek(SeekFrom::Start(0)).await?;
+        (&mut *file).take(FILE_PREFIX_SIZE).read_to_end(&mut prefix).await?;
+        Ok(prefix)
+    }
+
+    // 파일 앞부분의 BOM으로 인코딩을 판단하고, 시작 위치를 BOM 뒤 코드 유닛 경계로 맞춤
+    async fn open_tail(file: &mut File, position: u64) -> std::io::Result<(LogTail, u64)> {
+        let prefix = Self::read_prefix(file).await?;
+        let (encoding, bom_length) = chat_log_reader::detect_encoding(&prefix);
+
+        let bom_length = bom_length as u64;
+        let mut position = position.max(bom_length);
+        if encoding == UTF_16LE || encoding == UTF_16BE {
+            position -= (position - bom_length) % 2;
         }
+        Ok((LogTail::new(encoding, prefix), position))
+    }
 
-        let file_path = self.log_file.as_ref().unwrap();
-        
-        // Python과 동일한 UTF-16LE 인코딩 처리
-        let file_content = tokio::fs::read(file_path).await?;
-        let (cow, _, _) = UTF_16LE.decode(&file_content);
-        let content = cow.into_owned();
-        
-        let lines: Vec<&str> = content.lines().collect();
+    // 마지막으로 읽은 바이트 위치부터 추가된 부분만 읽음
+    async fn process_new_lines(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(file_path) = self.log_file.clone() else {
+            return Ok(());
+        };
+
+        let mut file = File::open(&file_path).await?;
+        let length = file.metadata().await?.len();
 
-        // 현재 파일의 총 라인 수와 이전에 읽은 라인 수 비교
-        if lines.len() < self.last_line_count {
-            // 파일이 잘렸거나 새로 시작된 경우
-            warn!("로그 파일이 잘렸거나 새로 시작되었습니다. 라인 카운트를 0으로 재설정합니다.");
-            self.last_line_count = 0;
+        // 파일이 잘렸거나, 같은 이름으로 다시 쓰여 앞부분(BOM, 헤더)이 달라진 경우
+        let rewritten = match self.tail.as_ref() {
+            Some(tail) => !tail.is_same_file(&Self::read_prefix(&mut file).await?),
+            None => false,
+        };
+        if length < self.last_position || rewritten {
+            warn!("로그 파일이 잘렸거나 새로 시작되었습니다. 처음부터 다시 읽습니다.");
+            self.last_position = 0;
+            self.tail = None;
         }
 
-        // 새로운 라인이 있는지 확인
-        if lines.len() == self.last_line_count {
-            return Ok(()); // 새 라인 없음
+        if self.tail.is_none() {
+            let (tail, position) = Self::open_tail(&mut file, self.last_position).await?;
+            self.tail = Some(tail);
+            self.last_position = position;
         }
 
-        // 마지막으로 읽은 라인 이후의 새로운 라인들만 추출
-        let start_line = self.last_line_count;
+        if length == self.last_position {
+            return Ok(()); // 새 내용 없음
+        }
 
-        if start_line < lines.len() {
-            let new_lines: Vec<String> = lines[start_line..]
-                .iter()
-                .map(|line| line.trim().trim_start_matches('\u{feff}').to_string())
-                .collect();
-            
-            if !new_lines.is_empty() {
-                if let Some(ref callback) = self.on_new_log_lines {
-                    callback(new_lines);
-                }
+        let mut appended = Vec::with_capacity((length - self.last_position) as usize);
+        file.seek(SeekFrom::Start(self.last_position)).await?;
+        (&mut file).take(length - self.last_position).read_to_end(&mut appended).await?;
+        self.last_position += appended.len() as u64;
+
+        let new_lines = match self.tail.as_mut() {
+            Some(tail) => tail.push(&appended),
+            None => Vec::new(),
+        };
+        if !new_lines.is_empty() {
+            if let Some(ref callback) = self.on_new_log_lines {
+                callback(new_lines);
             }
         }
-
-        // 현재 총 라인 수를 기억
-        self.last_line_count = lines.len();
-        self.last_position = file_content.len() as u64;
         Ok(())
     }
 
@@ -209,19 +289,8 @@ impl LogMonitor {
                     latest_path.file_name().unwrap_or_default().to_string_lossy());
                 self.log_file = Some(latest_path.clone());
                 
-                // 파일 크기와 라인 카운트 재설정 - 끝부터 시작
-                if let Ok(metadata) = tokio::fs::metadata(&latest_path).await {
-                    self.last_position = metadata.len();
-                    
-                    // 새 파일도 끝부터 모니터링
-                    let file_content = tokio::fs::read(&latest_path).await.unwrap_or_default();
-                    let (cow, _, _) = UTF_16LE.decode(&file_content);
-                    let content = cow.into_owned();
-                    let lines: Vec<&str> = content.lines().collect();
-                    self.last_line_count = lines.len();
-                } else {
-                    self.last_line_count = 0;
-                }
+                // 새 파일도 끝부터 모니터링
+                self.tail_from_end(&latest_path).await;
                 
                 if let Some(ref callback) = self.on_log_file_change {
                     callback();
@@ -232,7 +301,7 @@ impl LogMonitor {
                 self.log_file.as_ref().unwrap().file_name().unwrap_or_default().to_string_lossy());
             self.log_file = None;
             self.last_position = 0;
-            self.last_line_count = 0;
+            self.tail = None;
             
             if let Some(ref callback) = self.on_log_file_change {
                 callback();
@@ -261,18 +330,9 @@ impl LogMonitor {
                                 log_file.file_name().and_then(|n| n.to_str()).unwrap_or("unknown"),
                                 log_file.display());
                             
-                            if let Ok(metadata) = tokio::fs::metadata(log_file).await {
-                                monitor.last_position = metadata.len();
-                                
-                                // 파일 끝부터 모니터링 시작
-                                let file_content = tokio::fs::read(log_file).await.unwrap_or_default();
-                                let (cow, _, _) = UTF_16LE.decode(&file_content);
-                                let content = cow.into_owned();
-                                let lines: Vec<&str> = content.lines().collect();
-                                monitor.last_line_count = lines.len();
-                            } else {
-                                monitor.last_line_count = 0;
-                            }
+                            // 파일 끝부터 모니터링 시작
+                            let log_file = log_file.clone();
+                            monitor.tail_from_end(&log_file).await;
                         } else {
                             info!("Waiting for a suitable log file...");
                             tokio::time::sleep(Duration::from_secs(5)).await;
@@ -288,18 +348,8 @@ impl LogMonitor {
                             let latest = monitor.find_latest_local_log().await;
                             if let Some(latest_path) = latest {
                                 monitor.log_file = Some(latest_path.clone());
-                                if let Ok(metadata) = tokio::fs::metadata(&latest_path).await {
-                                    monitor.last_position = metadata.len();
-                                    
-                                    // 새 파일을 끝부터 모니터링하도록 설정
-                                    let file_content = tokio::fs::read(&latest_path).await.unwrap_or_default();
-                                    let (cow, _, _) = UTF_16LE.decode(&file_content);
-                                    let content = cow.into_owned();
-                                    let lines: Vec<&str> = content.lines().collect();
-                                    monitor.last_line_count = lines.len();
-                                } else {
-                                    monitor.last_line_count = 0;
-                                }
+                                // 새 파일을 끝부터 모니터링하도록 설정
+                                monitor.tail_from_end(&latest_path).await;
                             }
                         } else {
                             // 현재 파일이 존재하더라도 더 최신 파일이 있는지 확인
@@ -309,18 +359,8 @@ impl LogMonitor {
                                     info!("Found newer log file, switching from {:?} to {:?}", 
                                         current_file.file_name(), latest_path.file_name());
                                     monitor.log_file = Some(latest_path.clone());
-                                    if let Ok(metadata) = tokio::fs::metadata(&latest_path).await {
-                                        monitor.last_position = metadata.len();
-                                        
-                                        // 새 파일을 끝부터 모니터링하도록 설정
-                                        let file_content = tokio::fs::read(&latest_path).await.unwrap_or_default();
-                                        let (cow, _, _) = UTF_16LE.decode(&file_content);
-                                        let content = cow.into_owned();
-                                        let lines: Vec<&str> = content.lines().collect();
-                                        monitor.last_line_count = lines.len();
-                                    } else {
-                                        monitor.last_line_count = 0;
-                                    }
+                                    // 새 파일을 끝부터 모니터링하도록 설정
+                                    monitor.tail_from_end(&latest_path).await;
                                 }
                             }
                         }
@@ -338,19 +378,10 @@ impl LogMonitor {
         self.monitoring = true;
         self.log_file = self.find_latest_local_log().await;
         
-        if let Some(ref log_file) = self.log_file {
-            if let Ok(metadata) = tokio::fs::metadata(log_file).await {
-                self.last_position = metadata.len();
-                
-                // 파일 끝부터 시작하도록 현재 라인 수를 설정
-                let file_content = tokio::fs::read(log_file).await.unwrap_or_default();
-                let (cow, _, _) = UTF_16LE.decode(&file_content);
-                let content = cow.into_owned();
-                let lines: Vec<&str> = content.lines().collect();
-                self.last_line_count = lines.len();
-                
-                info!("Starting monitoring from end of file (line {})", self.last_line_count);
-            }
+        if let Some(log_file) = self.log_file.clone() {
+            // 파일 끝부터 시작
+            self.tail_from_end(&log_file).await;
+            info!("Starting monitoring from end of file (byte {})", self.last_position);
         }
 
         // 캐릭터 이름이 설정되어 있으면 모니터링 시작 (로그 파일이 없어도 대기)
@@ -377,8 +408,8 @@ impl LogMonitor {
                 character_name: self.character_name.clone(),
                 language: self.language.clone(),
                 log_file: self.log_file.clone(),
-                last_position: self.last_position,
-                last_line_count: self.last_line_count,
+                last_position: self.resume_position(),
+                tail: None, // 복사본은 마지막 완성된 줄 끝에서 새로 디코딩 시작 (미완성 줄은 다시 읽음)
                 monitoring: self.monitoring,
                 observer: None,
                 monitor_task: Arc::new(Mutex::new(None)),
@@ -426,4 +457,57 @@ impl LogMonitor {
     pub fn get_current_log_file_info(&self) -> Option<(std::path::PathBuf, bool)> {
         self.log_file.as_ref().map(|path| (path.clone(), self.monitoring))
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::UTF_8;
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(|unit| unit.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn push_joins_code_units_split_across_reads() {
+        let bytes = utf16le("[ 2024.05.01 12:34:56 ] 이브 시스템 > 지역 : 주이타 채널로 변경\r\n");
+        for split in 1..bytes.len() {
+            let mut tail = LogTail::new(UTF_16LE, Vec::new());
+            let mut lines = tail.push(&bytes[..split]);
+            lines.extend(tail.push(&bytes[split..]));
+            assert_eq!(lines, vec!["[ 2024.05.01 12:34:56 ] 이브 시스템 > 지역 : 주이타 채널로 변경"], "split at {}", split);
+            assert_eq!(tail.pending_bytes, 0);
+        }
+    }
+
+    #[test]
+    fn push_holds_line_without_newline() {
+        let mut tail = LogTail::new(UTF_16BE, Vec::new());
+        let bytes: Vec<u8> = "first\nsecond".encode_utf16().flat_map(|unit| unit.to_be_bytes()).collect();
+        assert_eq!(tail.push(&bytes[..bytes.len() - 1]), vec!["first"]);
+        // 미완성 줄과 디코더가 보관 중인 홀수 바이트 모두 다시 읽을 대상
+        assert_eq!(tail.pending_bytes, "second".len() as u64 * 2 - 1);
+        assert!(tail.push(&bytes[bytes.len() - 1..]).is_empty());
+        assert_eq!(tail.push(b"\0\n"), vec!["second"]);
+        assert_eq!(tail.pending_bytes, 0);
+    }
+
+    #[test]
+    fn push_counts_utf8_pending_bytes() {
+        let mut tail = LogTail::new(UTF_8, Vec::new());
+        let bytes = "한 줄\n둘".as_bytes();
+        assert_eq!(tail.push(&bytes[..bytes.len() - 1]), vec!["한 줄"]);
+        assert_eq!(tail.pending_bytes, "둘".len() as u64 - 1);
+        assert!(tail.push(&bytes[bytes.len() - 1..]).is_empty());
+        assert_eq!(tail.partial_line, "둘");
+    }
+
+    #[test]
+    fn detects_rewritten_file_from_prefix() {
+        let original = utf16le("\u{feff}Session started: 2024.05.01 12:30:00");
+        let tail = LogTail::new(UTF_16LE, original[..20].to_vec());
+        assert!(tail.is_same_file(&original));
+        assert!(!tail.is_same_file(&utf16le("\u{feff}Session started: 2024.05.02 08:00:00")[..10]));
+        assert!(!tail.is_same_file(&utf16le("\u{feff}Channel ID: local")));
+        assert!(!tail.is_same_file(b"Session started: 2024.05.01"));
+    }
+}
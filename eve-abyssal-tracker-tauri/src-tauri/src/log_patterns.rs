@@ -0,0 +1,571 @@
+use std::{path::{Path, PathBuf}, sync::{Arc, RwLock}};
+use serde::{Deserialize, Serialize};
+use regex::Regex;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+use anyhow::{Result, anyhow};
+use log::*;
+use crate::eve_api::EVEApi;
+use crate::eve_log_processor::EveLogProcessor;
+
+// 앱 데이터 디렉토리의 패턴 파일 - 없으면 기본 패턴으로 만들어 두고, 같은 language 항목은 기본 패턴을 덮어씀
+pub const LOG_PATTERNS_FILE_NAME: &str = "log_patterns.json";
+pub const DEFAULT_LOG_LANGUAGE: &str = "ko";
+const DEFAULT_LISTENER_PATTERN: &str = "Listener:";
+// 검증 결과에 담는 최대 매치 수
+const VALIDATION_MAX_MATCHES: usize = 50;
+
+fn default_listener() -> String {
+    String::from(DEFAULT_LISTENER_PATTERN)
+}
+
+// 클라이언트 언어 하나의 지역(Local) 채널 로그 패턴
+// 일반 모드: system_change 뒤부터 channel_changed 앞까지가 성계 이름 (channel_changed가 비어 있으면 줄 끝까지),
+//           unknown_system은 성계 이름과 일치, listener는 헤더 줄의 접두사 (헤더의 기본 Listener 레이블보다 먼저 적용)
+// 정규식 모드(regex: true): system_change의 system 이름 그룹(없으면 첫 번째 그룹)이 성계 이름,
+//           channel_changed도 일치해야 하고, unknown_system은 성계 이름에, listener의 name 그룹(없으면 첫 번째 그룹)이 캐릭터 이름
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogPatternDefinition {
+    pub language: String,
+    #[serde(default)]
+    pub regex: bool,
+    pub system_change: String,
+    #[serde(default)]
+    pub channel_changed: String,
+    pub unknown_system: String, // 어비셜 공간의 성계 이름
+    pub log_filename: String, // 글롭 (예: Local_*.txt)
+    #[serde(default = "default_listener")]
+    pub listener: String,
+}
+
+impl LogPatternDefinition {
+    fn literal(language: &str, system_change: &str, channel_changed: &str, unknown_system: &str, log_filename: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            regex: false,
+            system_change: system_change.to_string(),
+            channel_changed: channel_changed.to_string(),
+            unknown_system: unknown_system.to_string(),
+            log_filename: log_filename.to_string(),
+            listener: default_listener(),
+        }
+    }
+}
+
+// 언어 감지는 이 순서로 시도 (en, fr, es는 파일 이름이 같아 파일 이름만으로는 en으로 감지)
+fn builtin_definitions() -> Vec<LogPatternDefinition> {
+    vec![
+        LogPatternDefinition::literal("ko", "이브 시스템 > 지역 : ", "채널로 변경", "알 수 없음", "지역_*.txt"),
+        LogPatternDefinition::literal("en", "EVE System > Channel changed to Local : ", "", "Unknown", "Local_*.txt"),
+        LogPatternDefinition::literal("de", "EVE-System > Chatkanal geändert zu Lokal : ", "", "Unbekannt", "Lokal_*.txt"),
+        LogPatternDefinition::literal("fr", "Système EVE > Canal changé en Local : ", "", "Inconnu", "Local_*.txt"),
+        LogPatternDefinition::literal("ru", "Система EVE > Канал изменен на Локальный : ", "", "Неизвестно", "Локальный_*.txt"),
+        LogPatternDefinition::literal("ja", "EVEシステム > ローカル : ", "チャンネルに変更", "不明", "ローカル_*.txt"),
+        LogPatternDefinition::literal("zh", "EVE系统 > 频道更换为本地 : ", "", "未知", "本地_*.txt"),
+        LogPatternDefinition::literal("es", "Sistema EVE > Canal cambiado a Local : ", "", "Desconocido", "Local_*.txt"),
+    ]
+}
+
+fn compile_regex(field: &str, pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| anyhow!("Invalid {} regex '{}': {}", field, pattern, e))
+}
+
+fn first_capture(regex: &Regex, group_name: &str, text: &str) -> Option<String> {
+    let captures = regex.captures(text)?;
+    captures.name(group_name).or_else(|| captures.get(1))
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRegexes {
+    system_change: Regex,
+    channel_changed: Option<Regex>,
+    unknown_system: Regex,
+    listener: Regex,
+}
+
+// 검증을 거친 패턴 세트 (정규식 모드면 컴파일된 정규식 포함)
+#[derive(Debug, Clone)]
+pub struct LogPatternSet {
+    definition: LogPatternDefinition,
+    regexes: Option<CompiledRegexes>,
+}
+
+impl LogPatternSet {
+    pub fn compile(definition: LogPatternDefinition) -> Result<Self> {
+        let language = definition.language.trim();
+        if language.is_empty() {
+            return Err(anyhow!("language is required"));
+        }
+        if definition.system_change.is_empty() {
+            return Err(anyhow!("[{}] system_change is required", language));
+        }
+        if definition.log_filename.trim().is_empty() {
+            return Err(anyhow!("[{}] log_filename is required", language));
+        }
+        // 비어 있으면 정규식 모드에서는 모든 성계가 어비셜 공간으로 판정됨
+        if definition.unknown_system.trim().is_empty() {
+            return Err(anyhow!("[{}] unknown_system is required", language));
+        }
+        if definition.listener.trim().is_empty() {
+            return Err(anyhow!("[{}] listener is required", language));
+        }
+
+        let regexes = if definition.regex {
+            let system_change = compile_regex("system_change", &definition.system_change)
+                .map_err(|e| anyhow!("[{}] {}", language, e))?;
+            if system_change.captures_len() < 2 {
+                return Err(anyhow!("[{}] system_change regex needs a capture group for the system name", language));
+            }
+            let listener = compile_regex("listener", &definition.listener)
+                .map_err(|e| anyhow!("[{}] {}", language, e))?;
+            if listener.captures_len() < 2 {
+                return Err(anyhow!("[{}] listener regex needs a capture group for the character name", language));
+            }
+            let channel_changed = if definition.channel_changed.is_empty() {
+                None
+            } else {
+                Some(compile_regex("channel_changed", &definition.channel_changed).map_err(|e| anyhow!("[{}] {}", language, e))?)
+            };
+            let unknown_system = compile_regex("unknown_system", &definition.unknown_system)
+                .map_err(|e| anyhow!("[{}] {}", language, e))?;
+            Some(CompiledRegexes { system_change, channel_changed, unknown_system, listener })
+        } else {
+            None
+        };
+
+        Ok(Self { definition, regexes })
+    }
+
+    pub fn language(&self) -> &str {
+        self.definition.language.trim()
+    }
+
+    pub fn matches_filename(&self, file_name: &str) -> bool {
+        glob_match::glob_match(self.definition.log_filename.trim(), file_name)
+    }
+
+    pub fn is_system_change_line(&self, line: &str) -> bool {
+        match &self.regexes {
+            Some(regexes) => regexes.system_change.is_match(line)
+                && regexes.channel_changed.as_ref().map_or(true, |channel_changed| channel_changed.is_match(line)),
+            None => line.contains(&self.definition.system_change) && line.contains(&self.definition.channel_changed),
+        }
+    }
+
+    pub fn parse_system_name(&self, line: &str) -> Option<String> {
+        if let Some(regexes) = &self.regexes {
+            return first_capture(&regexes.system_change, "system", line);
+        }
+
+        // 접미사가 없는 언어(영어 등)는 줄 끝까지가 성계 이름
+        line.split(&self.definition.system_change).nth(1).map(|parts| {
+            let name_part = if self.definition.channel_changed.is_empty() {
+                parts
+            } else {
+                parts.split(&self.definition.channel_changed).next().unwrap_or(parts)
+            };
+            name_part.trim().to_string()
+        }).filter(|name| !name.is_empty())
+    }
+
+    pub fn is_unknown_system(&self, system_name: &str) -> bool {
+        match &self.regexes {
+            Some(regexes) => regexes.unknown_system.is_match(system_name.trim()),
+            None => system_name.trim() == self.definition.unknown_system.trim(),
+        }
+    }
+
+    pub fn parse_listener(&self, line: &str) -> Option<String> {
+        match &self.regexes {
+            Some(regexes) => first_capture(&regexes.listener, "name", line),
+            None => line.trim().strip_prefix(self.definition.listener.trim())
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogPatterns {
+    sets: Vec<LogPatternSet>,
+    errors: Vec<String>, // 패턴 파일에서 무시한 항목
+}
+
+impl LogPatterns {
+    pub fn builtin() -> Self {
+        Self {
+            sets: builtin_definitions().into_iter()
+                .map(|definition| LogPatternSet::compile(definition).expect("built-in log patterns must be valid"))
+                .collect(),
+            errors: Vec::new(),
+        }
+    }
+
+    // 기본 패턴에 패턴 파일 항목을 합침 - 잘못된 항목은 건너뛰고 errors에 기록 (해당 언어는 기본 패턴 유지)
+    pub fn from_definitions(definitions: Vec<LogPatternDefinition>) -> Self {
+        let mut patterns = Self::builtin();
+        for definition in definitions {
+            match LogPatternSet::compile(definition) {
+                Ok(set) => match patterns.sets.iter_mut().find(|existing| existing.language() == set.language()) {
+                    Some(existing) => *existing = set,
+                    None => patterns.sets.push(set),
+                },
+                Err(e) => patterns.errors.push(e.to_string()),
+            }
+        }
+        patterns
+    }
+
+    // 패턴 파일이 없으면 기본 패턴으로 만들어 두고 (직접 고칠 수 있도록), 읽을 수 없으면 기본 패턴 사용
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(LOG_PATTERNS_FILE_NAME);
+        if !path.exists() {
+            if let Err(e) = save_definitions(&path, &builtin_definitions()) {
+                warn!("{} 생성 실패: {}", path.display(), e);
+            }
+            return Self::builtin();
+        }
+
+        match read_definitions(&path) {
+            Ok(definitions) => {
+                let patterns = Self::from_definitions(definitions);
+                for error in &patterns.errors {
+                    warn!("{} 항목 무시: {}", path.display(), error);
+                }
+                info!("로그 패턴 로드: {} ({}개 언어)", path.display(), patterns.sets.len());
+                patterns
+            }
+            Err(e) => {
+                warn!("{} 읽기 실패, 기본 로그 패턴을 사용합니다: {}", path.display(), e);
+                let mut patterns = Self::builtin();
+                patterns.errors.push(e.to_string());
+                patterns
+            }
+        }
+    }
+
+    pub fn sets(&self) -> &[LogPatternSet] {
+        &self.sets
+    }
+
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    // 없는 언어면 기본 언어, 그것도 없으면 첫 번째 세트
+    pub fn get_or_default(&self, language: &str) -> &LogPatternSet {
+        self.sets.iter().find(|set| set.language() == language)
+            .or_else(|| self.sets.iter().find(|set| set.language() == DEFAULT_LOG_LANGUAGE))
+            .unwrap_or(&self.sets[0])
+    }
+
+    pub fn language_for_line(&self, line: &str) -> Option<&str> {
+        self.sets.iter().find(|set| set.is_system_change_line(line) && set.parse_system_name(line).is_some())
+            .map(|set| set.language())
+    }
+
+    pub fn language_for_filename(&self, file_name: &str) -> Option<&str> {
+        self.sets.iter().find(|set| set.matches_filename(file_name)).map(|set| set.language())
+    }
+
+    pub fn matches_filename(&self, file_name: &str) -> bool {
+        self.sets.iter().any(|set| set.matches_filename(file_name))
+    }
+
+    // 헤더의 Listener 줄은 언어를 모르는 상태에서 읽으므로 모든 세트로 시도
+    pub fn parse_listener(&self, line: &str) -> Option<String> {
+        self.sets.iter().find_map(|set| set.parse_listener(line))
+    }
+}
+
+fn read_definitions(path: &Path) -> Result<Vec<LogPatternDefinition>> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn save_definitions(path: &Path, definitions: &[LogPatternDefinition]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(definitions)?)?;
+    Ok(())
+}
+
+// EveLogProcessor는 여러 곳에서 임시로 만들어지므로 현재 패턴은 전역으로 공유 (다시 불러오면 교체)
+lazy_static::lazy_static! {
+    static ref ACTIVE_LOG_PATTERNS: RwLock<Arc<LogPatterns>> = RwLock::new(Arc::new(LogPatterns::builtin()));
+}
+
+pub fn active() -> Arc<LogPatterns> {
+    ACTIVE_LOG_PATTERNS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+pub fn install(patterns: LogPatterns) {
+    *ACTIVE_LOG_PATTERNS.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(patterns);
+}
+
+async fn patterns_file_path(app_handle: &AppHandle) -> PathBuf {
+    let eve_api = app_handle.state::<Arc<Mutex<EVEApi>>>();
+    let data_dir = eve_api.lock().await.data_dir().to_path_buf();
+    data_dir.join(LOG_PATTERNS_FILE_NAME)
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogPatternsInfo {
+    pub file_path: String,
+    pub languages: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+// 패턴 파일을 다시 읽어 적용 (새로 여는 로그 파일부터 반영)
+#[tauri::command]
+pub async fn reload_log_patterns(app_handle: AppHandle) -> Result<LogPatternsInfo, String> {
+    let path = patterns_file_path(&app_handle).await;
+    let data_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    let patterns = LogPatterns::load(&data_dir);
+    let info = LogPatternsInfo {
+        file_path: path.to_string_lossy().to_string(),
+        languages: patterns.sets().iter().map(|set| set.language().to_string()).collect(),
+        errors: patterns.errors().to_vec(),
+    };
+    install(patterns);
+    Ok(info)
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogPatternMatch {
+    pub line_number: usize,
+    pub line: String,
+    pub system_name: Option<String>,
+    pub timestamp: Option<String>,
+    pub unknown_system: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogPatternValidation {
+    pub pattern_file: String,
+    pub errors: Vec<String>, // 패턴 파일에서 무시된 항목
+    pub filename_matched: bool, // 로그 파일 이름이 log_filename 글롭과 일치하는지
+    pub detected_language: String,
+    pub listener: Option<String>,
+    pub lines_scanned: usize,
+    pub system_change_count: usize,
+    pub unknown_system_count: usize,
+    pub matches: Vec<LogPatternMatch>, // 앞에서부터 최대 VALIDATION_MAX_MATCHES개
+}
+
+// 패턴 파일을 적용하지 않고 읽어서 선택한 로그 파일에 시험 (감지된 언어, 캐릭터, 성계 변경 줄)
+#[tauri::command]
+pub async fn validate_log_patterns(app_handle: AppHandle, file_path: String) -> Result<LogPatternValidation, String> {
+    let log_path = PathBuf::from(file_path.trim());
+    if !log_path.is_file() {
+        return Err(format!("Log file not found: {}", log_path.display()));
+    }
+
+    let pattern_path = patterns_file_path(&app_handle).await;
+    let patterns = if pattern_path.exists() {
+        let definitions = read_definitions(&pattern_path)
+            .map_err(|e| format!("Failed to read {}: {}", pattern_path.display(), e))?;
+        LogPatterns::from_definitions(definitions)
+    } else {
+        LogPatterns::builtin()
+    };
+
+    let file_name = log_path.file_name().and_then(|s| s.to_str()).unwrap_or_default();
+    let mut processor = EveLogProcessor::with_patterns(PathBuf::new(), None, Arc::new(patterns.clone()));
+    processor.set_log_file(log_path.clone());
+    let detected_language = processor.patterns.language().to_string();
+    let listener = processor.detect_character_name(Some(&log_path));
+
+    let mut validation = LogPatternValidation {
+        pattern_file: pattern_path.to_string_lossy().to_string(),
+        errors: patterns.errors().to_vec(),
+        filename_matched: patterns.matches_filename(file_name),
+        detected_language,
+        listener,
+        lines_scanned: 0,
+        system_change_count: 0,
+        unknown_system_count: 0,
+        matches: Vec::new(),
+    };
+    for (index, line) in processor.iter_lines(Some(&log_path)).enumerate() {
+        validation.lines_scanned += 1;
+        if !processor.is_system_change_line(&line) {
+            continue;
+        }
+        let (system_name, timestamp) = processor.parse_system_change(&line);
+        let unknown_system = system_name.as_deref().is_some_and(|name| processor.is_unknown_system(name));
+        validation.system_change_count += 1;
+        if unknown_system {
+            validation.unknown_system_count += 1;
+        }
+        if validation.matches.len() < VALIDATION_MAX_MATCHES {
+            validation.matches.push(LogPatternMatch { line_number: index + 1, line, system_name, timestamp, unknown_system });
+        }
+    }
+    Ok(validation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regex_definition() -> LogPatternDefinition {
+        LogPatternDefinition {
+            language: String::from("xx"),
+            regex: true,
+            system_change: String::from(r"Local : (?P<system>[^*]+)\*?$"),
+            channel_changed: String::new(),
+            unknown_system: String::from("^Unknown$"),
+            log_filename: String::from("Local_*.txt"),
+            listener: String::from(r"^Listener:\s*(?P<name>.+)$"),
+        }
+    }
+
+    // 일반 성계와 어비셜 공간(알 수 없는 성계) 진입 줄을 모두 확인하고, 다른 언어로 잘못 감지되지 않는지 확인
+    fn assert_local_channel_line(language: &str, line: &str, system_name: &str, abyss_line: &str) {
+        let patterns = LogPatterns::builtin();
+        let set = patterns.get_or_default(language);
+        assert_eq!(set.language(), language);
+        assert!(set.is_system_change_line(line));
+        assert_eq!(set.parse_system_name(line).as_deref(), Some(system_name));
+        assert!(!set.is_unknown_system(system_name));
+        assert_eq!(patterns.language_for_line(line), Some(language));
+
+        assert!(set.is_system_change_line(abyss_line));
+        assert!(set.is_unknown_system(&set.parse_system_name(abyss_line).unwrap()));
+        assert_eq!(patterns.language_for_line(abyss_line), Some(language));
+    }
+
+    #[test]
+    fn korean_local_channel_line() {
+        assert_local_channel_line("ko",
+            "[ 2024.05.01 12:34:56 ] 이브 시스템 > 지역 : 주이타 채널로 변경", "주이타",
+            "[ 2024.05.01 12:40:00 ] 이브 시스템 > 지역 : 알 수 없음 채널로 변경");
+        let patterns = LogPatterns::builtin();
+        assert!(!patterns.get_or_default("ko").is_system_change_line("[ 2024.05.01 12:34:56 ] Pilot > 지역 : 안녕하세요"));
+        assert_eq!(patterns.language_for_filename("지역_20240501_123456_1234567890.txt"), Some("ko"));
+    }
+
+    #[test]
+    fn english_local_channel_line() {
+        assert_local_channel_line("en",
+            "[ 2024.05.01 12:34:56 ] EVE System > Channel changed to Local : Jita", "Jita",
+            "[ 2024.05.01 12:40:00 ] EVE System > Channel changed to Local : Unknown");
+        let patterns = LogPatterns::builtin();
+        assert!(!patterns.get_or_default("en").is_system_change_line("[ 2024.05.01 12:34:56 ] Pilot > o7"));
+        assert_eq!(patterns.language_for_filename("Local_20240501_123456_1234567890.txt"), Some("en"));
+    }
+
+    #[test]
+    fn german_local_channel_line() {
+        assert_local_channel_line("de",
+            "[ 2024.05.01 12:34:56 ] EVE-System > Chatkanal geändert zu Lokal : Jita", "Jita",
+            "[ 2024.05.01 12:40:00 ] EVE-System > Chatkanal geändert zu Lokal : Unbekannt");
+        assert_eq!(LogPatterns::builtin().language_for_filename("Lokal_20240501_123456_1234567890.txt"), Some("de"));
+    }
+
+    #[test]
+    fn french_local_channel_line() {
+        assert_local_channel_line("fr",
+            "[ 2024.05.01 12:34:56 ] Système EVE > Canal changé en Local : Jita", "Jita",
+            "[ 2024.05.01 12:40:00 ] Système EVE > Canal changé en Local : Inconnu");
+    }
+
+    #[test]
+    fn russian_local_channel_line() {
+        assert_local_channel_line("ru",
+            "[ 2024.05.01 12:34:56 ] Система EVE > Канал изменен на Локальный : Jita", "Jita",
+            "[ 2024.05.01 12:40:00 ] Система EVE > Канал изменен на Локальный : Неизвестно");
+        assert_eq!(LogPatterns::builtin().language_for_filename("Локальный_20240501_123456_1234567890.txt"), Some("ru"));
+    }
+
+    #[test]
+    fn japanese_local_channel_line() {
+        assert_local_channel_line("ja",
+            "[ 2024.05.01 12:34:56 ] EVEシステム > ローカル : Jita チャンネルに変更", "Jita",
+            "[ 2024.05.01 12:40:00 ] EVEシステム > ローカル : 不明 チャンネルに変更");
+        assert_eq!(LogPatterns::builtin().language_for_filename("ローカル_20240501_123456_1234567890.txt"), Some("ja"));
+    }
+
+    #[test]
+    fn chinese_local_channel_line() {
+        assert_local_channel_line("zh",
+            "[ 2024.05.01 12:34:56 ] EVE系统 > 频道更换为本地 : 吉他", "吉他",
+            "[ 2024.05.01 12:40:00 ] EVE系统 > 频道更换为本地 : 未知");
+        assert_eq!(LogPatterns::builtin().language_for_filename("本地_20240501_123456_1234567890.txt"), Some("zh"));
+    }
+
+    #[test]
+    fn spanish_local_channel_line() {
+        assert_local_channel_line("es",
+            "[ 2024.05.01 12:34:56 ] Sistema EVE > Canal cambiado a Local : Jita", "Jita",
+            "[ 2024.05.01 12:40:00 ] Sistema EVE > Canal cambiado a Local : Desconocido");
+    }
+
+    #[test]
+    fn compile_rejects_missing_fields() {
+        let valid = LogPatternDefinition::literal("xx", "Local : ", "", "Unknown", "Local_*.txt");
+        assert!(LogPatternSet::compile(valid.clone()).is_ok());
+
+        for definition in [
+            LogPatternDefinition { language: String::from(" "), ..valid.clone() },
+            LogPatternDefinition { system_change: String::new(), ..valid.clone() },
+            LogPatternDefinition { log_filename: String::from(" "), ..valid.clone() },
+            LogPatternDefinition { unknown_system: String::new(), ..valid.clone() },
+            LogPatternDefinition { listener: String::new(), ..valid.clone() },
+        ] {
+            assert!(LogPatternSet::compile(definition).is_err());
+        }
+    }
+
+    #[test]
+    fn compile_rejects_invalid_regexes() {
+        assert!(LogPatternSet::compile(regex_definition()).is_ok());
+        for definition in [
+            LogPatternDefinition { system_change: String::from("Local : ("), ..regex_definition() },
+            LogPatternDefinition { system_change: String::from("Local : .+"), ..regex_definition() }, // 캡처 그룹 없음
+            LogPatternDefinition { listener: String::from("Listener:.+"), ..regex_definition() },
+            LogPatternDefinition { unknown_system: String::from("["), ..regex_definition() },
+            LogPatternDefinition { unknown_system: String::new(), ..regex_definition() },
+            LogPatternDefinition { channel_changed: String::from(")"), ..regex_definition() },
+        ] {
+            assert!(LogPatternSet::compile(definition).is_err());
+        }
+    }
+
+    #[test]
+    fn regex_mode_uses_named_groups() {
+        let set = LogPatternSet::compile(regex_definition()).unwrap();
+        let line = "[ 2024.05.01 12:34:56 ] EVE System > Channel changed to Local : Jita*";
+        assert!(set.is_system_change_line(line));
+        assert_eq!(set.parse_system_name(line).as_deref(), Some("Jita"));
+        assert!(set.is_unknown_system("Unknown"));
+        assert!(!set.is_unknown_system("Unknown Space"));
+        assert_eq!(set.parse_listener("Listener:        Some Pilot").as_deref(), Some("Some Pilot"));
+        assert_eq!(set.parse_listener("Channel Name:    Local"), None);
+    }
+
+    #[test]
+    fn literal_listener_strips_prefix() {
+        let set = LogPatternSet::compile(LogPatternDefinition::literal("xx", "Local : ", "", "Unknown", "Local_*.txt")).unwrap();
+        assert_eq!(set.parse_listener("  Listener:        Some Pilot  ").as_deref(), Some("Some Pilot"));
+        assert_eq!(set.parse_listener("Listener:"), None);
+        assert_eq!(set.parse_listener("Channel ID:      local"), None);
+    }
+
+    #[test]
+    fn pattern_file_entries_override_builtin_sets() {
+        let patterns = LogPatterns::from_definitions(vec![
+            LogPatternDefinition { language: String::from("en"), ..regex_definition() },
+            LogPatternDefinition { language: String::from("yy"), ..regex_definition() },
+            LogPatternDefinition { language: String::from("zz"), unknown_system: String::new(), ..regex_definition() },
+        ]);
+        assert_eq!(patterns.sets().len(), LogPatterns::builtin().sets().len() + 1);
+        assert_eq!(patterns.get_or_default("en").parse_system_name("Local : Amarr*").as_deref(), Some("Amarr"));
+        assert_eq!(patterns.errors().len(), 1);
+        assert_eq!(patterns.get_or_default("zz").language(), DEFAULT_LOG_LANGUAGE);
+    }
+}
@@ -0,0 +1,774 @@
+use std::{collections::{BTreeMap, HashMap}, io::Write, path::{Path, PathBuf}, sync::Arc, time::{Duration, Instant}};
+use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use anyhow::{Result, anyhow};
+use log::*;
+
+use crate::http_client::HttpClient;
+
+// 앞쪽 제공자부터 조회하고, 실패하거나 시세가 없는 아이템은 다음 제공자로 넘김
+pub const DEFAULT_PRICE_PROVIDERS: &str = "fuzzwork,esi_orders,esi_average,static";
+pub const STATIC_PRICE_FILE_NAME: &str = "static_prices.json";
+pub const DEFAULT_TRADE_HUB: &str = "jita";
+pub const DEFAULT_MARKET_SCOPE: &str = "region";
+pub const DEFAULT_LOOT_BASIS: &str = "buy_max";
+pub const DEFAULT_FILAMENT_BASIS: &str = "sell_min";
+pub const DEFAULT_VALUATION_MODE: &str = "historical";
+// 아이템 이름별 수동 가격: { "<아이템 이름>": 1500000.0 }
+pub const PRICE_OVERRIDES_FILE_NAME: &str = "price_overrides.json";
+// Fuzzwork의 percentile과 동일하게 가장 좋은 가격부터 거래량 5%의 평균
+const PERCENTILE_VOLUME_FRACTION: f64 = 0.05;
+// ESI 전체 평균가 목록은 약 1시간마다 갱신되므로 그동안은 받은 목록을 재사용
+const ESI_AVERAGE_PRICES_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TradeHub {
+    Jita,
+    Amarr,
+    Dodixie,
+    Rens,
+    Hek,
+}
+
+impl TradeHub {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "jita" => Ok(TradeHub::Jita),
+            "amarr" => Ok(TradeHub::Amarr),
+            "dodixie" => Ok(TradeHub::Dodixie),
+            "rens" => Ok(TradeHub::Rens),
+            "hek" => Ok(TradeHub::Hek),
+            _ => Err(format!("Unknown trade hub '{}': use jita, amarr, dodixie, rens or hek", value.trim())),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TradeHub::Jita => "jita",
+            TradeHub::Amarr => "amarr",
+            TradeHub::Dodixie => "dodixie",
+            TradeHub::Rens => "rens",
+            TradeHub::Hek => "hek",
+        }
+    }
+
+    pub fn region_id(&self) -> u32 {
+        match self {
+            TradeHub::Jita => 10000002,    // The Forge
+            TradeHub::Amarr => 10000043,   // Domain
+            TradeHub::Dodixie => 10000032, // Sinq Laison
+            TradeHub::Rens => 10000030,    // Heimatar
+            TradeHub::Hek => 10000042,     // Metropolis
+        }
+    }
+
+    pub fn station_id(&self) -> u64 {
+        match self {
+            TradeHub::Jita => 60003760,    // Jita IV - Moon 4 - Caldari Navy Assembly Plant
+            TradeHub::Amarr => 60008494,   // Amarr VIII (Oris) - Emperor Family Academy
+            TradeHub::Dodixie => 60011866, // Dodixie IX - Moon 20 - Federation Navy Assembly Plant
+            TradeHub::Rens => 60004588,    // Rens VI - Moon 8 - Brutor Tribe Treasury
+            TradeHub::Hek => 60005686,     // Hek VIII - Moon 12 - Boundless Creation Factory
+        }
+    }
+}
+
+// 시세를 조회할 시장 (거래 허브와 지역 전체/허브 정거장만)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Market {
+    pub hub: TradeHub,
+    pub station_only: bool,
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Market { hub: TradeHub::Jita, station_only: false }
+    }
+}
+
+impl Market {
+    // scope: "region" 또는 "station"
+    pub fn parse(hub: &str, scope: &str) -> Result<Self, String> {
+        let hub = TradeHub::parse(hub)?;
+        let station_only = match scope.trim().to_lowercase().as_str() {
+            "" | "region" => false,
+            "station" => true,
+            _ => return Err(format!("Unknown market scope '{}': use region or station", scope.trim())),
+        };
+        Ok(Market { hub, station_only })
+    }
+
+    pub fn scope_name(&self) -> &'static str {
+        if self.station_only { "station" } else { "region" }
+    }
+
+    // 가격 캐시 구분용 (예: "jita-region")
+    pub fn key(&self) -> String {
+        format!("{}-{}", self.hub.name(), self.scope_name())
+    }
+}
+
+// 한쪽(매수 또는 매도) 주문의 시세 요약
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceStats {
+    pub weighted_average: f64,
+    pub max: f64,
+    pub min: f64,
+    pub stddev: f64,
+    pub median: f64,
+    pub volume: f64,
+    pub order_count: u64,
+    pub percentile: f64,
+}
+
+impl PriceStats {
+    // 주문이 없는 평균가만 아는 경우 모든 값을 같은 가격으로
+    fn flat(price: f64) -> Self {
+        PriceStats {
+            weighted_average: price,
+            max: price,
+            min: price,
+            median: price,
+            percentile: price,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceQuote {
+    pub type_id: u32,
+    pub buy: PriceStats,
+    pub sell: PriceStats,
+    pub source: String, // 시세를 제공한 제공자 이름
+}
+
+impl PriceQuote {
+    pub fn has_price(&self) -> bool {
+        self.buy.max > 0.0 || self.sell.min > 0.0 || self.buy.weighted_average > 0.0 || self.sell.weighted_average > 0.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriceSide {
+    Buy,
+    Sell,
+}
+
+// 시세에서 아이템 가치로 사용할 값
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriceBasis {
+    BuyMax,
+    SellMin,
+    WeightedAverage(PriceSide),
+    Median(PriceSide),
+    Percentile(PriceSide), // Fuzzwork percentile (가장 좋은 가격부터 거래량 5% 평균)
+    Split, // buy max와 sell min의 중간값
+}
+
+impl PriceBasis {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "buy_max" => Ok(PriceBasis::BuyMax),
+            "sell_min" => Ok(PriceBasis::SellMin),
+            "buy_weighted_average" => Ok(PriceBasis::WeightedAverage(PriceSide::Buy)),
+            "sell_weighted_average" => Ok(PriceBasis::WeightedAverage(PriceSide::Sell)),
+            "buy_median" => Ok(PriceBasis::Median(PriceSide::Buy)),
+            "sell_median" => Ok(PriceBasis::Median(PriceSide::Sell)),
+            "buy_percentile" => Ok(PriceBasis::Percentile(PriceSide::Buy)),
+            "sell_percentile" => Ok(PriceBasis::Percentile(PriceSide::Sell)),
+            "split" => Ok(PriceBasis::Split),
+            _ => Err(format!(
+                "Unknown price basis '{}': use buy_max, sell_min, buy/sell_weighted_average, buy/sell_median, buy/sell_percentile or split",
+                value.trim()
+            )),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PriceBasis::BuyMax => "buy_max",
+            PriceBasis::SellMin => "sell_min",
+            PriceBasis::WeightedAverage(PriceSide::Buy) => "buy_weighted_average",
+            PriceBasis::WeightedAverage(PriceSide::Sell) => "sell_weighted_average",
+            PriceBasis::Median(PriceSide::Buy) => "buy_median",
+            PriceBasis::Median(PriceSide::Sell) => "sell_median",
+            PriceBasis::Percentile(PriceSide::Buy) => "buy_percentile",
+            PriceBasis::Percentile(PriceSide::Sell) => "sell_percentile",
+            PriceBasis::Split => "split",
+        }
+    }
+
+    pub fn price(&self, quote: &PriceQuote) -> f64 {
+        let side = |side: &PriceSide| match side {
+            PriceSide::Buy => &quote.buy,
+            PriceSide::Sell => &quote.sell,
+        };
+        match self {
+            PriceBasis::BuyMax => quote.buy.max,
+            PriceBasis::SellMin => quote.sell.min,
+            PriceBasis::WeightedAverage(s) => side(s).weighted_average,
+            PriceBasis::Median(s) => side(s).median,
+            PriceBasis::Percentile(s) => side(s).percentile,
+            // 한쪽 주문이 없으면 있는 쪽 가격 사용
+            PriceBasis::Split => match (quote.buy.max > 0.0, quote.sell.min > 0.0) {
+                (true, true) => (quote.buy.max + quote.sell.min) / 2.0,
+                (true, false) => quote.buy.max,
+                (false, _) => quote.sell.min,
+            },
+        }
+    }
+}
+
+// 런의 실수익/ISK/h에 사용할 시세
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValuationMode {
+    Current,    // 현재 시세
+    Historical, // 런 날짜에 가장 가까운 일별 시세
+}
+
+impl ValuationMode {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "current" => Ok(ValuationMode::Current),
+            "historical" => Ok(ValuationMode::Historical),
+            _ => Err(format!("Unknown valuation mode '{}': use current or historical", value.trim())),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ValuationMode::Current => "current",
+            ValuationMode::Historical => "historical",
+        }
+    }
+}
+
+pub fn load_price_overrides(data_dir: &Path) -> HashMap<String, f64> {
+    let path = data_dir.join(PRICE_OVERRIDES_FILE_NAME);
+    if !path.exists() {
+        return HashMap::new();
+    }
+    match std::fs::read_to_string(&path).map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str::<HashMap<String, f64>>(&content).map_err(|e| e.to_string()))
+    {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            warn!("{} 읽기 실패, 수동 가격을 사용하지 않습니다: {}", path.display(), e);
+            HashMap::new()
+        }
+    }
+}
+
+pub fn save_price_overrides(data_dir: &Path, overrides: &HashMap<String, f64>) -> Result<(), String> {
+    let path = data_dir.join(PRICE_OVERRIDES_FILE_NAME);
+    // 직접 편집하기 쉽도록 이름순으로 저장
+    let sorted: BTreeMap<&String, &f64> = overrides.iter().collect();
+    let content = serde_json::to_string_pretty(&sorted)
+        .map_err(|e| format!("Failed to serialize price overrides: {}", e))?;
+
+    // 직접 입력한 값이라 다시 만들 수 없으므로 임시 파일에 쓴 뒤 교체 (쓰는 도중 종료되어도 기존 파일 유지)
+    let temp_path = path.with_extension("json.tmp");
+    let mut file = std::fs::File::create(&temp_path)
+        .map_err(|e| format!("Failed to create {}: {}", temp_path.display(), e))?;
+    file.write_all(content.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    drop(file);
+    std::fs::rename(&temp_path, &path)
+        .map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriceSource {
+    Fuzzwork,
+    EsiOrders,  // ESI 지역 주문서
+    EsiAverage, // ESI /markets/prices/ 전체 평균가
+    StaticFile, // 데이터 폴더의 static_prices.json
+}
+
+impl PriceSource {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "fuzzwork" => Ok(PriceSource::Fuzzwork),
+            "esi_orders" => Ok(PriceSource::EsiOrders),
+            "esi_average" => Ok(PriceSource::EsiAverage),
+            "static" => Ok(PriceSource::StaticFile),
+            _ => Err(format!("Unknown price provider '{}': use fuzzwork, esi_orders, esi_average or static", value.trim())),
+        }
+    }
+
+    // "fuzzwork,esi_orders" 형식의 우선순위 목록
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, String> {
+        let mut sources = Vec::new();
+        for name in value.split(',').filter(|name| !name.trim().is_empty()) {
+            let source = Self::parse(name)?;
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        if sources.is_empty() {
+            return Err("At least one price provider is required".to_string());
+        }
+        Ok(sources)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PriceSource::Fuzzwork => "fuzzwork",
+            PriceSource::EsiOrders => "esi_orders",
+            PriceSource::EsiAverage => "esi_average",
+            PriceSource::StaticFile => "static",
+        }
+    }
+
+    pub fn list_to_string(sources: &[Self]) -> String {
+        sources.iter().map(|source| source.name()).collect::<Vec<_>>().join(",")
+    }
+}
+
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    // 네트워크 없이 동작하는 제공자 (오프라인 모드에서도 사용)
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    // 시세를 찾은 type_id만 반환, 제공자 자체가 동작하지 않으면 Err
+    async fn fetch_quotes(&self, http: &HttpClient, type_ids: &[u32]) -> Result<HashMap<u32, PriceQuote>>;
+}
+
+pub fn build_providers(sources: &[PriceSource], market: Market, data_dir: &Path) -> Vec<Box<dyn PriceProvider>> {
+    sources.iter()
+        .map(|source| -> Box<dyn PriceProvider> {
+            match source {
+                PriceSource::Fuzzwork => Box::new(FuzzworkProvider { market }),
+                PriceSource::EsiOrders => Box::new(EsiOrderBookProvider { market }),
+                PriceSource::EsiAverage => Box::new(EsiAveragePriceProvider::default()),
+                PriceSource::StaticFile => Box::new(StaticFileProvider { path: data_dir.join(STATIC_PRICE_FILE_NAME) }),
+            }
+        })
+        .collect()
+}
+
+// 우선순위대로 조회 - 모든 제공자가 실패하면 0 ISK로 계산하지 않도록 Err 반환
+pub async fn fetch_with_fallback(providers: &[Box<dyn PriceProvider>], http: &HttpClient, type_ids: &[u32]) -> Result<HashMap<u32, PriceQuote>> {
+    let mut quotes = HashMap::new();
+    let mut remaining: Vec<u32> = type_ids.to_vec();
+    let mut errors = Vec::new();
+
+    for provider in providers {
+        if remaining.is_empty() {
+            break;
+        }
+        match provider.fetch_quotes(http, &remaining).await {
+            Ok(found) => {
+                let found_count = found.len();
+                for (type_id, quote) in found {
+                    if quote.has_price() && remaining.contains(&type_id) {
+                        quotes.insert(type_id, quote);
+                    }
+                }
+                remaining.retain(|type_id| !quotes.contains_key(type_id));
+                info!("{} 시세 조회: {}개 아이템 (남은 아이템: {}개)", provider.name(), found_count, remaining.len());
+            }
+            Err(e) => {
+                warn!("{} 시세 조회 실패, 다음 제공자로 넘어갑니다: {}", provider.name(), e);
+                errors.push(format!("{}: {}", provider.name(), e));
+            }
+        }
+    }
+
+    if !type_ids.is_empty() && quotes.is_empty() && errors.len() == providers.len() {
+        return Err(anyhow!("모든 시세 제공자 조회 실패 ({})", errors.join("; ")));
+    }
+    Ok(quotes)
+}
+
+// Fuzzwork는 숫자를 문자열로 반환
+fn json_f64(value: Option<&serde_json::Value>) -> f64 {
+    match value {
+        Some(serde_json::Value::String(s)) => s.parse().unwrap_or(0.0),
+        Some(v) => v.as_f64().unwrap_or(0.0),
+        None => 0.0,
+    }
+}
+
+pub struct FuzzworkProvider {
+    market: Market,
+}
+
+impl FuzzworkProvider {
+    fn parse_stats(value: Option<&serde_json::Value>) -> PriceStats {
+        let field = |name: &str| json_f64(value.and_then(|v| v.get(name)));
+        PriceStats {
+            weighted_average: field("weightedAverage"),
+            max: field("max"),
+            min: field("min"),
+            stddev: field("stddev"),
+            median: field("median"),
+            volume: field("volume"),
+            order_count: field("orderCount") as u64,
+            percentile: field("percentile"),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for FuzzworkProvider {
+    fn name(&self) -> &'static str {
+        "fuzzwork"
+    }
+
+    async fn fetch_quotes(&self, http: &HttpClient, type_ids: &[u32]) -> Result<HashMap<u32, PriceQuote>> {
+        let ids_str: Vec<String> = type_ids.iter().map(|id| id.to_string()).collect();
+        let location = if self.market.station_only {
+            format!("station={}", self.market.hub.station_id())
+        } else {
+            format!("region={}", self.market.hub.region_id())
+        };
+        let url = format!("{}?{}&types={}", http.fuzzwork_url("aggregates/"), location, ids_str.join(","));
+
+        let response = http.send(http.get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Fuzzwork API HTTP 오류: {}", response.status()));
+        }
+
+        let data: HashMap<String, serde_json::Value> = response.json().await
+            .map_err(|e| anyhow!("Fuzzwork API 응답 JSON 디코딩 오류: {}", e))?;
+
+        Ok(data.into_iter()
+            .filter_map(|(id, price_data)| {
+                let type_id = id.parse::<u32>().ok()?;
+                Some((type_id, PriceQuote {
+                    type_id,
+                    buy: Self::parse_stats(price_data.get("buy")),
+                    sell: Self::parse_stats(price_data.get("sell")),
+                    source: self.name().to_string(),
+                }))
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EsiMarketOrder {
+    location_id: u64,
+    price: f64,
+    volume_remain: f64,
+    is_buy_order: bool,
+}
+
+pub struct EsiOrderBookProvider {
+    market: Market,
+}
+
+impl EsiOrderBookProvider {
+    async fn fetch_orders(&self, http: &HttpClient, type_id: u32) -> Result<Vec<EsiMarketOrder>> {
+        let mut orders = Vec::new();
+        let mut page = 1;
+        loop {
+            let url = http.esi_url(&format!(
+                "markets/{}/orders/?datasource=tranquility&order_type=all&type_id={}&page={}",
+                self.market.hub.region_id(), type_id, page
+            ));
+            let response = http.send(http.get(&url)).await?;
+            if !response.status().is_success() {
+                return Err(anyhow!("ESI 주문서 HTTP 오류: {}", response.status()));
+            }
+
+            let pages = response.headers().get("x-pages")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(1);
+            orders.extend(response.json::<Vec<EsiMarketOrder>>().await?);
+
+            if page >= pages {
+                return Ok(orders);
+            }
+            page += 1;
+        }
+    }
+
+    // best_first: 좋은 가격부터 정렬된 (가격, 수량)
+    fn summarize(mut orders: Vec<(f64, f64)>, best_first: impl Fn(&f64, &f64) -> std::cmp::Ordering) -> PriceStats {
+        if orders.is_empty() {
+            return PriceStats::default();
+        }
+        orders.sort_by(|a, b| best_first(&a.0, &b.0));
+
+        let volume: f64 = orders.iter().map(|(_, qty)| qty).sum();
+        let weighted_average = if volume > 0.0 {
+            orders.iter().map(|(price, qty)| price * qty).sum::<f64>() / volume
+        } else {
+            0.0
+        };
+        let mean = orders.iter().map(|(price, _)| price).sum::<f64>() / orders.len() as f64;
+        let stddev = (orders.iter().map(|(price, _)| (price - mean).powi(2)).sum::<f64>() / orders.len() as f64).sqrt();
+
+        let mut prices: Vec<f64> = orders.iter().map(|(price, _)| *price).collect();
+        prices.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let mid = prices.len() / 2;
+        let median = if prices.len() % 2 == 0 { (prices[mid - 1] + prices[mid]) / 2.0 } else { prices[mid] };
+
+        // 가장 좋은 가격부터 전체 수량의 5%까지의 가중 평균
+        let target = volume * PERCENTILE_VOLUME_FRACTION;
+        let mut taken = 0.0;
+        let mut taken_value = 0.0;
+        for (price, qty) in &orders {
+            let take = qty.min(target - taken);
+            if take <= 0.0 {
+                break;
+            }
+            taken += take;
+            taken_value += price * take;
+        }
+        let percentile = if taken > 0.0 { taken_value / taken } else { orders[0].0 };
+
+        PriceStats {
+            weighted_average,
+            max: prices[prices.len() - 1],
+            min: prices[0],
+            stddev,
+            median,
+            volume,
+            order_count: orders.len() as u64,
+            percentile,
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for EsiOrderBookProvider {
+    fn name(&self) -> &'static str {
+        "esi_orders"
+    }
+
+    async fn fetch_quotes(&self, http: &HttpClient, type_ids: &[u32]) -> Result<HashMap<u32, PriceQuote>> {
+        // 아이템별 주문서를 병렬로 조회 (동시 요청 수와 에러 한도 대기는 HTTP 스케줄러가 관리)
+        let results: Vec<_> = stream::iter(type_ids.iter().copied())
+            .map(|type_id| async move { (type_id, self.fetch_orders(http, type_id).await) })
+            .buffer_unordered(http.max_parallel_requests())
+            .collect()
+            .await;
+
+        let mut quotes = HashMap::new();
+        let mut first_error = None;
+        for (type_id, result) in results {
+            let orders = match result {
+                Ok(orders) => orders,
+                Err(e) => {
+                    warn!("ESI 주문서 조회 실패 (type_id {}): {}", type_id, e);
+                    first_error.get_or_insert(e);
+                    continue;
+                }
+            };
+
+            let station_id = self.market.hub.station_id();
+            let (buy_orders, sell_orders): (Vec<_>, Vec<_>) = orders.into_iter()
+                .filter(|order| !self.market.station_only || order.location_id == station_id)
+                .partition(|order| order.is_buy_order);
+            let to_pairs = |orders: Vec<EsiMarketOrder>| orders.into_iter().map(|o| (o.price, o.volume_remain)).collect::<Vec<_>>();
+            quotes.insert(type_id, PriceQuote {
+                type_id,
+                buy: Self::summarize(to_pairs(buy_orders), |a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal)),
+                sell: Self::summarize(to_pairs(sell_orders), |a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)),
+                source: self.name().to_string(),
+            });
+        }
+
+        // 모든 아이템이 실패하면 ESI 장애로 판단
+        match first_error {
+            Some(e) if quotes.is_empty() => Err(e),
+            _ => Ok(quotes),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EsiAveragePrice {
+    type_id: u32,
+    average_price: Option<f64>,
+    adjusted_price: Option<f64>,
+}
+
+// 지역 구분 없는 전체 평균가 - 주문서가 없는 아이템의 마지막 수단
+// 목록 하나에 모든 타입이 있으므로 한 번 받아서 청크마다 재사용
+#[derive(Default)]
+pub struct EsiAveragePriceProvider {
+    prices: tokio::sync::Mutex<Option<(Instant, Arc<HashMap<u32, f64>>)>>, // 받은 시각, type_id → 평균가
+}
+
+impl EsiAveragePriceProvider {
+    async fn prices(&self, http: &HttpClient) -> Result<Arc<HashMap<u32, f64>>> {
+        // 동시에 조회하는 청크는 먼저 받은 목록을 기다렸다가 사용
+        let mut cached = self.prices.lock().await;
+        if let Some((fetched_at, prices)) = cached.as_ref() {
+            if fetched_at.elapsed() < ESI_AVERAGE_PRICES_TTL {
+                return Ok(prices.clone());
+            }
+        }
+
+        let url = http.esi_url("markets/prices/?datasource=tranquility");
+        let response = http.send(http.get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("ESI 평균가 HTTP 오류: {}", response.status()));
+        }
+
+        let prices: Vec<EsiAveragePrice> = response.json().await?;
+        let prices: Arc<HashMap<u32, f64>> = Arc::new(prices.into_iter()
+            .filter_map(|price| Some((price.type_id, price.average_price.or(price.adjusted_price)?)))
+            .collect());
+        *cached = Some((Instant::now(), prices.clone()));
+        Ok(prices)
+    }
+}
+
+#[async_trait]
+impl PriceProvider for EsiAveragePriceProvider {
+    fn name(&self) -> &'static str {
+        "esi_average"
+    }
+
+    async fn fetch_quotes(&self, http: &HttpClient, type_ids: &[u32]) -> Result<HashMap<u32, PriceQuote>> {
+        let prices = self.prices(http).await?;
+        Ok(type_ids.iter()
+            .filter_map(|type_id| {
+                let value = *prices.get(type_id)?;
+                Some((*type_id, PriceQuote {
+                    type_id: *type_id,
+                    buy: PriceStats::flat(value),
+                    sell: PriceStats::flat(value),
+                    source: self.name().to_string(),
+                }))
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StaticPrice {
+    buy: f64,
+    sell: f64,
+}
+
+// 직접 관리하는 시세 파일: { "<type_id>": { "buy": 1000.0, "sell": 1200.0 } }
+pub struct StaticFileProvider {
+    path: PathBuf,
+}
+
+#[async_trait]
+impl PriceProvider for StaticFileProvider {
+    fn name(&self) -> &'static str {
+        "static"
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    async fn fetch_quotes(&self, _http: &HttpClient, type_ids: &[u32]) -> Result<HashMap<u32, PriceQuote>> {
+        let content = tokio::fs::read_to_string(&self.path).await
+            .map_err(|e| anyhow!("{} 읽기 실패: {}", self.path.display(), e))?;
+        let prices: HashMap<String, StaticPrice> = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("{} 형식 오류: {}", self.path.display(), e))?;
+
+        Ok(prices.into_iter()
+            .filter_map(|(id, price)| {
+                let type_id = id.trim().parse::<u32>().ok()?;
+                if !type_ids.contains(&type_id) {
+                    return None;
+                }
+                Some((type_id, PriceQuote {
+                    type_id,
+                    buy: PriceStats::flat(price.buy),
+                    sell: PriceStats::flat(price.sell),
+                    source: self.name().to_string(),
+                }))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::{HttpSettings, RequestScheduler};
+
+    fn stats(weighted_average: f64, max: f64, min: f64, median: f64, percentile: f64) -> PriceStats {
+        PriceStats { weighted_average, max, min, median, percentile, ..PriceStats::default() }
+    }
+
+    fn quote(buy: PriceStats, sell: PriceStats) -> PriceQuote {
+        PriceQuote { type_id: 34, buy, sell, source: String::from("fuzzwork") }
+    }
+
+    #[test]
+    fn price_reads_the_configured_side_and_statistic() {
+        let quote = quote(stats(90.0, 100.0, 50.0, 95.0, 99.0), stats(130.0, 200.0, 110.0, 120.0, 111.0));
+        let cases = [
+            ("buy_max", 100.0),
+            ("sell_min", 110.0),
+            ("buy_weighted_average", 90.0),
+            ("sell_weighted_average", 130.0),
+            ("buy_median", 95.0),
+            ("sell_median", 120.0),
+            ("buy_percentile", 99.0),
+            ("sell_percentile", 111.0),
+            ("split", 105.0),
+        ];
+        for (name, expected) in cases {
+            let basis = PriceBasis::parse(name).unwrap();
+            assert_eq!(basis.name(), name);
+            assert_eq!(basis.price(&quote), expected, "{}", name);
+        }
+        assert!(PriceBasis::parse("average").is_err());
+    }
+
+    #[test]
+    fn split_falls_back_to_the_side_with_orders() {
+        let buy_only = quote(stats(0.0, 100.0, 0.0, 0.0, 0.0), PriceStats::default());
+        assert_eq!(PriceBasis::Split.price(&buy_only), 100.0);
+        let sell_only = quote(PriceStats::default(), stats(0.0, 0.0, 110.0, 0.0, 0.0));
+        assert_eq!(PriceBasis::Split.price(&sell_only), 110.0);
+        let empty = quote(PriceStats::default(), PriceStats::default());
+        assert_eq!(PriceBasis::Split.price(&empty), 0.0);
+        assert!(!empty.has_price());
+    }
+
+    #[tokio::test]
+    async fn esi_average_reuses_the_downloaded_list() {
+        // 연결할 수 없는 주소 - 목록을 다시 받으려 하면 실패
+        let settings = HttpSettings { esi_base_url: String::from("http://127.0.0.1:9/"), retries: 0, ..HttpSettings::default() };
+        let http = HttpClient::new(settings, Arc::new(RequestScheduler::new(1))).unwrap();
+        let provider = EsiAveragePriceProvider::default();
+        *provider.prices.lock().await = Some((Instant::now(), Arc::new(HashMap::from([(34, 5.0), (35, 10.0)]))));
+
+        let quotes = provider.fetch_quotes(&http, &[34, 36]).await.unwrap();
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[&34].sell.min, 5.0);
+        assert_eq!(quotes[&34].source, "esi_average");
+
+        // 만료된 목록은 다시 받음
+        if let Some(expired_at) = Instant::now().checked_sub(ESI_AVERAGE_PRICES_TTL) {
+            *provider.prices.lock().await = Some((expired_at, Arc::new(HashMap::new())));
+            assert!(provider.fetch_quotes(&http, &[34]).await.is_err());
+        }
+    }
+
+    #[test]
+    fn saves_price_overrides_atomically() {
+        let dir = std::env::temp_dir().join(format!("price_overrides_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let overrides = HashMap::from([(String::from("Triglavian Survey Database"), 100_000.0)]);
+
+        save_price_overrides(&dir, &overrides).unwrap();
+        assert_eq!(load_price_overrides(&dir), overrides);
+        assert!(!dir.join(PRICE_OVERRIDES_FILE_NAME).with_extension("json.tmp").exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,304 @@
+use std::{collections::HashMap, fs::File, io::{BufRead, BufReader}, path::{Path, PathBuf}, sync::{Arc, RwLock}};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+use crate::eve_api::EVEApi;
+use anyhow::{Result, anyhow};
+use log::*;
+
+// 앱 데이터 디렉토리에 있으면 번들 테이블 대신 사용 (SDE 업데이트 결과)
+pub const STATIC_TYPES_FILE_NAME: &str = "static_types.json";
+// 빌드 시 포함되는 타입 테이블 - cargo run --example generate_static_types -- <SDE 폴더>로 생성
+const BUNDLED_STATIC_TYPES: &str = include_str!("../resources/static_types.json");
+// SDE JSONL 덤프의 파일 이름
+const SDE_TYPES_FILE: &str = "types.jsonl";
+const SDE_GROUPS_FILE: &str = "groups.jsonl";
+const SDE_CATEGORIES_FILE: &str = "categories.jsonl";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaticType {
+    pub id: u32,
+    pub names: HashMap<String, String>, // 언어 코드(en, de, fr, ja, ru, zh, ko, es) → 이름
+    pub group_id: u32,
+    pub group: String, // 영어 그룹 이름
+    pub category_id: u32,
+    pub category: String, // 영어 카테고리 이름
+    pub marketable: bool, // 시장 그룹이 있는 아이템
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StaticTypeTable {
+    pub source: String, // 원본 SDE 경로
+    pub built_at: String, // UTC RFC 3339
+    pub types: Vec<StaticType>,
+}
+
+// EVEApi와 IconCache가 함께 쓰는 타입 DB (SDE 업데이트 시 교체)
+pub type SharedStaticTypes = Arc<RwLock<StaticTypeDb>>;
+
+#[derive(Default)]
+pub struct StaticTypeDb {
+    types: HashMap<u32, StaticType>,
+    name_to_id: HashMap<String, u32>, // 모든 언어의 소문자 이름 → type_id
+    built_at: String,
+}
+
+impl StaticTypeDb {
+    // 앱 데이터 디렉토리의 업데이트된 테이블, 없거나 읽을 수 없으면 번들 테이블
+    pub fn load(data_dir: &Path) -> Self {
+        let updated_path = data_dir.join(STATIC_TYPES_FILE_NAME);
+        if updated_path.exists() {
+            match std::fs::read_to_string(&updated_path).map_err(|e| anyhow!(e))
+                .and_then(|content| Ok(serde_json::from_str::<StaticTypeTable>(&content)?))
+            {
+                Ok(table) => {
+                    info!("정적 타입 DB 로드: {} ({}개 타입)", updated_path.display(), table.types.len());
+                    return Self::from_table(table);
+                }
+                Err(e) => warn!("정적 타입 DB 읽기 실패, 번들 테이블 사용: {}", e),
+            }
+        }
+
+        match serde_json::from_str::<StaticTypeTable>(BUNDLED_STATIC_TYPES) {
+            Ok(table) => {
+                info!("번들 정적 타입 DB 로드 ({}개 타입)", table.types.len());
+                Self::from_table(table)
+            }
+            Err(e) => {
+                error!("번들 정적 타입 DB 파싱 실패: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn from_table(table: StaticTypeTable) -> Self {
+        let mut name_to_id = HashMap::new();
+        for static_type in &table.types {
+            for name in static_type.names.values() {
+                name_to_id.entry(name.trim().to_lowercase()).or_insert(static_type.id);
+            }
+        }
+        Self {
+            types: table.types.into_iter().map(|static_type| (static_type.id, static_type)).collect(),
+            name_to_id,
+            built_at: table.built_at,
+        }
+    }
+
+    // 어느 언어의 이름이든 대소문자 구분 없이 조회
+    pub fn get_type_id(&self, name: &str) -> Option<u32> {
+        self.name_to_id.get(&name.trim().to_lowercase()).copied()
+    }
+
+    pub fn get(&self, type_id: u32) -> Option<&StaticType> {
+        self.types.get(&type_id)
+    }
+
+    pub fn english_name(&self, type_id: u32) -> Option<&str> {
+        self.get(type_id)?.names.get("en").map(|name| name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    pub fn built_at(&self) -> &str {
+        &self.built_at
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SdeType {
+    #[serde(rename = "_key")]
+    id: u32,
+    #[serde(default)]
+    name: HashMap<String, String>,
+    #[serde(rename = "groupID")]
+    group_id: u32,
+    #[serde(rename = "marketGroupID")]
+    market_group_id: Option<u32>,
+    #[serde(default)]
+    published: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SdeGroup {
+    #[serde(rename = "_key")]
+    id: u32,
+    #[serde(default)]
+    name: HashMap<String, String>,
+    #[serde(rename = "categoryID")]
+    category_id: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct SdeCategory {
+    #[serde(rename = "_key")]
+    id: u32,
+    #[serde(default)]
+    name: HashMap<String, String>,
+}
+
+fn read_jsonl<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Vec<T>> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open {}: {}", path.display(), e))?;
+    let mut rows = Vec::new();
+    for (line_number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        rows.push(serde_json::from_str(&line)
+            .map_err(|e| anyhow!("{}:{}: {}", path.display(), line_number + 1, e))?);
+    }
+    Ok(rows)
+}
+
+// 로컬 SDE JSONL 덤프(types.jsonl, groups.jsonl, categories.jsonl)에서 공개된 타입만 모아 테이블 생성
+// marketable_only: 시세가 있는 타입만 남김 (번들 테이블 크기 절감용 - 어비셜 루트/필라멘트는 모두 시장 아이템)
+pub fn build_from_sde(sde_dir: &Path, marketable_only: bool) -> Result<StaticTypeTable> {
+    let categories: HashMap<u32, String> = read_jsonl::<SdeCategory>(&sde_dir.join(SDE_CATEGORIES_FILE))?
+        .into_iter()
+        .map(|category| (category.id, category.name.get("en").cloned().unwrap_or_default()))
+        .collect();
+    let groups: HashMap<u32, (String, u32)> = read_jsonl::<SdeGroup>(&sde_dir.join(SDE_GROUPS_FILE))?
+        .into_iter()
+        .map(|group| (group.id, (group.name.get("en").cloned().unwrap_or_default(), group.category_id)))
+        .collect();
+
+    let mut types: Vec<StaticType> = read_jsonl::<SdeType>(&sde_dir.join(SDE_TYPES_FILE))?
+        .into_iter()
+        .filter(|sde_type| sde_type.published && sde_type.name.contains_key("en"))
+        .filter(|sde_type| !marketable_only || sde_type.market_group_id.is_some())
+        .map(|sde_type| {
+            let (group, category_id) = groups.get(&sde_type.group_id).cloned().unwrap_or_default();
+            StaticType {
+                id: sde_type.id,
+                names: sde_type.name,
+                group_id: sde_type.group_id,
+                group,
+                category_id,
+                category: categories.get(&category_id).cloned().unwrap_or_default(),
+                marketable: sde_type.market_group_id.is_some(),
+            }
+        })
+        .collect();
+    types.sort_by_key(|static_type| static_type.id);
+
+    if types.is_empty() {
+        return Err(anyhow!("No published types found in {}", sde_dir.display()));
+    }
+    Ok(StaticTypeTable {
+        source: sde_dir.display().to_string(),
+        built_at: chrono::Utc::now().to_rfc3339(),
+        types,
+    })
+}
+
+pub fn save_table(data_dir: &Path, table: &StaticTypeTable) -> Result<()> {
+    std::fs::create_dir_all(data_dir)?;
+    // 크기를 줄이기 위해 들여쓰기 없이 저장
+    std::fs::write(data_dir.join(STATIC_TYPES_FILE_NAME), serde_json::to_string(table)?)?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct StaticTypesInfo {
+    pub type_count: usize,
+    pub built_at: String,
+}
+
+#[tauri::command]
+pub async fn get_static_types_info(app_handle: AppHandle) -> Result<StaticTypesInfo, String> {
+    let static_types = app_handle.state::<SharedStaticTypes>();
+    let db = static_types.read().unwrap_or_else(|e| e.into_inner());
+    Ok(StaticTypesInfo { type_count: db.len(), built_at: db.built_at().to_string() })
+}
+
+// SDE 덤프 폴더에서 테이블을 다시 만들어 앱 데이터 디렉토리에 저장하고 바로 교체
+#[tauri::command]
+pub async fn update_static_types(app_handle: AppHandle, sde_path: String) -> Result<usize, String> {
+    let sde_dir = PathBuf::from(sde_path.trim());
+    if !sde_dir.is_dir() {
+        return Err(format!("SDE directory not found: {}", sde_dir.display()));
+    }
+    let data_dir = app_handle.state::<Arc<Mutex<EVEApi>>>().lock().await.data_dir().to_path_buf();
+
+    // 수십만 줄 JSONL 파싱이라 블로킹 스레드에서 실행
+    let table = tokio::task::spawn_blocking(move || -> Result<StaticTypeTable> {
+        let table = build_from_sde(&sde_dir, false)?;
+        save_table(&data_dir, &table)?;
+        Ok(table)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let type_count = table.types.len();
+    info!("정적 타입 DB 업데이트: {} ({}개 타입)", table.source, type_count);
+    let static_types = app_handle.state::<SharedStaticTypes>();
+    *static_types.write().unwrap_or_else(|e| e.into_inner()) = StaticTypeDb::from_table(table);
+    Ok(type_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_sde(dir: &Path) {
+        std::fs::write(dir.join(SDE_CATEGORIES_FILE), concat!(
+            r#"{"_key":17,"name":{"en":"Commodity"}}"#, "\n",
+        )).unwrap();
+        std::fs::write(dir.join(SDE_GROUPS_FILE), concat!(
+            r#"{"_key":1,"name":{"en":"Loot"},"categoryID":17}"#, "\n",
+        )).unwrap();
+        std::fs::write(dir.join(SDE_TYPES_FILE), concat!(
+            r#"{"_key":3,"name":{"en":"Sellable","ko":"판매 가능"},"groupID":1,"marketGroupID":10,"published":true}"#, "\n",
+            "\n",
+            r#"{"_key":2,"name":{"en":"Not On Market"},"groupID":1,"published":true}"#, "\n",
+            r#"{"_key":1,"name":{"en":"Unpublished"},"groupID":1,"marketGroupID":10,"published":false}"#, "\n",
+        )).unwrap();
+    }
+
+    #[test]
+    fn builds_table_from_sde_jsonl() {
+        let dir = std::env::temp_dir().join(format!("static_types_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        write_sde(&dir);
+
+        let table = build_from_sde(&dir, false).unwrap();
+        assert_eq!(table.types.iter().map(|t| t.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(table.types[1].category, "Commodity");
+        assert!(table.types[1].marketable);
+
+        let table = build_from_sde(&dir, true).unwrap();
+        assert_eq!(table.types.iter().map(|t| t.id).collect::<Vec<_>>(), vec![3]);
+
+        let db = StaticTypeDb::from_table(table);
+        assert_eq!(db.get_type_id(" sellable "), Some(3));
+        assert_eq!(db.get_type_id("판매 가능"), Some(3));
+        assert_eq!(db.english_name(3), Some("Sellable"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    // 새로 설치해도 네트워크 없이 대표적인 어비셜 드롭을 찾을 수 있어야 함
+    #[test]
+    fn bundled_table_resolves_known_drops() {
+        let table: StaticTypeTable = serde_json::from_str(BUNDLED_STATIC_TYPES).unwrap();
+        let db = StaticTypeDb::from_table(table);
+        assert!(!db.is_empty(), "bundled static_types.json is empty");
+
+        for name in ["Calm Dark Filament", "Triglavian Survey Database"] {
+            let type_id = db.get_type_id(name).unwrap_or_else(|| panic!("{} not found", name));
+            assert_eq!(db.english_name(type_id), Some(name));
+            // 한국어 클라이언트 로그의 이름으로도 같은 타입을 찾아야 함
+            let korean_name = db.get(type_id).and_then(|t| t.names.get("ko"))
+                .unwrap_or_else(|| panic!("{} has no ko name", name));
+            assert_eq!(db.get_type_id(korean_name), Some(type_id));
+        }
+    }
+}
@@ -3,16 +3,17 @@ use std::{
     path::PathBuf,
     sync::Arc,
 };
-use chrono::{DateTime, Local, NaiveDateTime, Duration, TimeZone};
+use chrono::{DateTime, Utc, Duration};
 use serde::{Serialize, Deserialize};
 use log::*;
 
 use crate::eve_log_processor::EveLogProcessor;
+use crate::time_display::{self, TimeDisplay};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AbyssalRunData {
-    start: DateTime<Local>,
-    end: DateTime<Local>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
     duration: Duration,
     duration_str: String,
 }
@@ -21,32 +22,30 @@ pub struct AbyssalRunData {
 pub struct LocationInfo {
     pub current_system: Option<String>,
     pub previous_system: Option<String>,
-    pub last_updated: Option<DateTime<Local>>,
+    pub last_updated: Option<DateTime<Utc>>,
 }
 
 pub struct SystemChangeProcessor {
     log_processor: Arc<tokio::sync::Mutex<EveLogProcessor>>,
-    on_abyssal_run_end: Option<Box<dyn Fn(DateTime<Local>, DateTime<Local>) + Send + Sync>>,
+    on_abyssal_run_end: Option<Box<dyn Fn(DateTime<Utc>, DateTime<Utc>) + Send + Sync>>,
     
-    abyssal_run_start: Option<DateTime<Local>>,
-    abyssal_run_start_kst: Option<DateTime<Local>>,
+    abyssal_run_start: Option<DateTime<Utc>>,
     abyssal_run_count: u32,
     current_system: Option<String>,
     previous_system: Option<String>,
-    last_system_change_time: Option<DateTime<Local>>,
+    last_system_change_time: Option<DateTime<Utc>>,
     runs_by_date: HashMap<String, Vec<AbyssalRunData>>,
 }
 
 impl SystemChangeProcessor {
     pub fn new(
         log_processor: Arc<tokio::sync::Mutex<EveLogProcessor>>,
-        on_abyssal_run_end: Option<Box<dyn Fn(DateTime<Local>, DateTime<Local>) + Send + Sync>>,
+        on_abyssal_run_end: Option<Box<dyn Fn(DateTime<Utc>, DateTime<Utc>) + Send + Sync>>,
     ) -> Self {
         SystemChangeProcessor {
             log_processor,
             on_abyssal_run_end,
             abyssal_run_start: None,
-            abyssal_run_start_kst: None,
             abyssal_run_count: 0,
             current_system: None,
             previous_system: None,
@@ -65,28 +64,15 @@ impl SystemChangeProcessor {
 
     // 로그 파일을 스캔하여 최신 위치 정보를 업데이트하는 메서드
     pub async fn scan_latest_location(&mut self, character_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let (files, language) = {
-            let log_processor = self.log_processor.lock().await;
-            let files = log_processor.find_all_log_files();
-            let language = log_processor.language.clone();
-            (files, language)
-        };
+        let files = self.log_processor.lock().await.find_all_log_files();
         
-        // 수정 시간으로 정렬 (최신 파일부터)
-        let mut files_with_mtime: Vec<(PathBuf, std::time::SystemTime)> = Vec::new();
-        for file in files {
-            if let Ok(metadata) = std::fs::metadata(&file) {
-                if let Ok(mtime) = metadata.modified() {
-                    files_with_mtime.push((file, mtime));
-                }
-            }
-        }
-        files_with_mtime.sort_by(|a, b| b.1.cmp(&a.1)); // 최신 순으로 정렬
+        // 세션 시작 시각으로 정렬 (최신 파일부터)
+        let files_by_session = EveLogProcessor::sort_by_session_start(files, true);
         
-        let mut recent_systems: Vec<(String, DateTime<Local>)> = Vec::new();
+        let mut recent_systems: Vec<(String, DateTime<Utc>)> = Vec::new();
         
         // 최신 파일부터 확인하되, 충분한 위치 정보를 얻을 때까지만 스캔
-        for (file, _) in files_with_mtime.iter().take(5) { // 최신 5개 파일만 스캔
+        for (file, _) in files_by_session.iter().take(5) { // 최신 5개 파일만 스캔
             // 기존 log_processor를 사용하여 파일 설정
             {
                 let mut log_processor = self.log_processor.lock().await;
@@ -101,7 +87,7 @@ impl SystemChangeProcessor {
             }
             
             // 해당 파일의 모든 시스템 변경을 수집
-            let mut file_systems: Vec<(String, DateTime<Local>)> = Vec::new();
+            let mut file_systems: Vec<(String, DateTime<Utc>)> = Vec::new();
             
             // 기존 log_processor를 사용하여 라인 읽기
             let lines = {
@@ -127,9 +113,9 @@ impl SystemChangeProcessor {
                 };
                 
                 if let Some((system_name, ts_str, is_unknown)) = parsed_data {
-                    let event_time = match NaiveDateTime::parse_from_str(&ts_str, "%Y.%m.%d %H:%M:%S") {
-                        Ok(naive_dt) => Local.from_local_datetime(&naive_dt).unwrap(),
-                        Err(_) => continue,
+                    let event_time = match time_display::parse_eve_log_timestamp(&ts_str) {
+                        Some(event_time) => event_time,
+                        None => continue,
                     };
                     
                     // 알 수 없는 시스템은 제외 (어비셜 데드스페이스)
@@ -189,41 +175,34 @@ impl SystemChangeProcessor {
         }
         
         if let Some((system_name, ts_str, is_unknown)) = parsed_data {
-            // Python과 동일한 시간 파싱
-            let event_time = match NaiveDateTime::parse_from_str(&ts_str, "%Y.%m.%d %H:%M:%S") {
-                Ok(naive_dt) => naive_dt,
-                Err(_) => return, // Python과 동일하게 파싱 실패 시 리턴
+            // EVE 로그 타임스탬프는 UTC
+            let event_time = match time_display::parse_eve_log_timestamp(&ts_str) {
+                Some(event_time) => event_time,
+                None => return, // Python과 동일하게 파싱 실패 시 리턴
             };
             
-            // Python과 동일한 시간대 처리 (UTC로 가정하고 KST로 변환)
-            let event_time_local = Local.from_local_datetime(&event_time).unwrap();
-            let event_time_kst = event_time_local + Duration::hours(9);
-            
             if is_unknown {
                 if self.abyssal_run_start.is_none() {
-                    self.abyssal_run_start = Some(event_time_local);
-                    self.abyssal_run_start_kst = Some(event_time_kst);
-                    info!("[START] Abyssal Deadspace entered at {} (KST)", 
-                        self.abyssal_run_start_kst.unwrap().format("%Y-%m-%d %H:%M:%S"));
+                    self.abyssal_run_start = Some(event_time);
+                    info!("[START] Abyssal Deadspace entered at {} (UTC)", 
+                        event_time.format("%Y-%m-%d %H:%M:%S"));
                 }
             } else {
                 if let Some(start_time) = self.abyssal_run_start {
-                    let end_time = event_time_local;
-                    let end_time_kst = event_time_kst;
+                    let end_time = event_time;
                     let duration = end_time - start_time;
                     let mins = duration.num_minutes();
                     let secs = duration.num_seconds() % 60;
                     self.abyssal_run_count += 1;
                     
-                    info!("[END] Returned to normal space at {} (KST). Run duration: {}m {}s. Total runs: {}", 
-                        end_time_kst.format("%Y-%m-%d %H:%M:%S"), mins, secs, self.abyssal_run_count);
+                    info!("[END] Returned to normal space at {} (UTC). Run duration: {}m {}s. Total runs: {}", 
+                        end_time.format("%Y-%m-%d %H:%M:%S"), mins, secs, self.abyssal_run_count);
                     
                     if let Some(ref callback) = self.on_abyssal_run_end {
-                        callback(self.abyssal_run_start_kst.unwrap(), end_time_kst);
+                        callback(start_time, end_time);
                     }
                     
                     self.abyssal_run_start = None;
-                    self.abyssal_run_start_kst = None;
                 }
             }
             
@@ -232,7 +211,7 @@ impl SystemChangeProcessor {
             }
             
             self.current_system = Some(system_name);
-            self.last_system_change_time = Some(event_time_local);
+            self.last_system_change_time = Some(event_time);
         }
     }
 
@@ -241,18 +220,10 @@ impl SystemChangeProcessor {
         let files = log_processor.find_all_log_files();
         drop(log_processor);
         
-        // Python과 동일하게 수정 시간으로 정렬
-        let mut files_with_mtime: Vec<(PathBuf, std::time::SystemTime)> = Vec::new();
-        for file in files {
-            if let Ok(metadata) = std::fs::metadata(&file) {
-                if let Ok(mtime) = metadata.modified() {
-                    files_with_mtime.push((file, mtime));
-                }
-            }
-        }
-        files_with_mtime.sort_by(|a, b| a.1.cmp(&b.1)); // 오래된 순으로 정렬 (Python과 동일)
+        // 세션 시작 시각으로 정렬 (오래된 순)
+        let files_by_session = EveLogProcessor::sort_by_session_start(files, false);
         
-        info!("[INFO] Scanning {} past log files for runs...", files_with_mtime.len());
+        info!("[INFO] Scanning {} past log files for runs...", files_by_session.len());
         
         // 루프 밖에서 한 번만 language 가져오기
         let language = {
@@ -260,7 +231,7 @@ impl SystemChangeProcessor {
             log_processor.language.clone()
         };
         
-        for (file, _) in files_with_mtime {
+        for (file, _) in files_by_session {
             let logs_path_buf = PathBuf::from(logs_path);
             
             let mut temp_log_processor = EveLogProcessor::new(logs_path_buf, language.clone());
@@ -273,7 +244,7 @@ impl SystemChangeProcessor {
                 }
             }
             
-            let mut abyssal_run_start: Option<DateTime<Local>> = None;
+            let mut abyssal_run_start: Option<DateTime<Utc>> = None;
             
             for line in temp_log_processor.iter_lines(Some(&file)) {
                 if temp_log_processor.is_system_change_line(&line) {
@@ -286,9 +257,9 @@ impl SystemChangeProcessor {
                     let system_name = system_name.unwrap();
                     let ts_str = ts.unwrap();
                     
-                    let event_time = match NaiveDateTime::parse_from_str(&ts_str, "%Y.%m.%d %H:%M:%S") {
-                        Ok(naive_dt) => Local.from_local_datetime(&naive_dt).unwrap(),
-                        Err(_) => continue,
+                    let event_time = match time_display::parse_eve_log_timestamp(&ts_str) {
+                        Some(event_time) => event_time,
+                        None => continue,
                     };
                     
                     if temp_log_processor.is_unknown_system(&system_name) {
@@ -302,6 +273,7 @@ impl SystemChangeProcessor {
                             let mins = duration.num_minutes();
                             let secs = duration.num_seconds() % 60;
                             
+                            // 표시 시간대와 무관하게 UTC 날짜로 보관 (출력 시 다시 묶음)
                             let date_str = start_time.format("%Y-%m-%d").to_string();
                             let run_data = AbyssalRunData {
                                 start: start_time,
@@ -319,22 +291,22 @@ impl SystemChangeProcessor {
         }
     }
 
-    pub fn print_past_runs(&self) {
+    pub fn print_past_runs(&self, time_display: &TimeDisplay) {
         if !self.runs_by_date.is_empty() {
             info!("[PAST RUNS]");
-            let mut sorted_dates: Vec<_> = self.runs_by_date.keys().collect();
-            sorted_dates.sort();
+            // 하루 기준(자정 또는 다운타임) 날짜로 다시 묶어서 출력
+            let mut runs_by_display_date: std::collections::BTreeMap<chrono::NaiveDate, Vec<&AbyssalRunData>> = std::collections::BTreeMap::new();
+            for run in self.runs_by_date.values().flatten() {
+                runs_by_display_date.entry(time_display.day_of(run.start)).or_insert_with(Vec::new).push(run);
+            }
             
-            for date in sorted_dates {
-                if let Some(runs) = self.runs_by_date.get(date) {
-                    info!("{}:", date);
-                    for run in runs {
-                        let start_kst = run.start + Duration::hours(9);
-                        let end_kst = run.end + Duration::hours(9);
-                        let start_str = start_kst.format("%H:%M:%S");
-                        let end_str = end_kst.format("%H:%M:%S");
-                        info!("  - {} ~ {} ({}) (KST)", start_str, end_str, run.duration_str);
-                    }
+            for (date, mut runs) in runs_by_display_date {
+                runs.sort_by_key(|run| run.start);
+                info!("{}:", date.format("%Y-%m-%d"));
+                for run in runs {
+                    let start_str = time_display.to_display(run.start).format("%H:%M:%S");
+                    let end_str = time_display.to_display(run.end).format("%H:%M:%S");
+                    info!("  - {} ~ {} ({}) ({})", start_str, end_str, run.duration_str, time_display.timezone_name());
                 }
             }
         } else {
@@ -0,0 +1,270 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+
+pub const DEFAULT_TIMEZONE: &str = "Asia/Seoul";
+pub const DEFAULT_DAY_BOUNDARY: &str = "midnight";
+pub const DISPLAY_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+// DB와 CSV에 저장하는 UTC 시각 형식 (문자열 비교로 시간순 정렬 가능)
+pub const UTC_STORAGE_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+// EVE 채팅 로그의 타임스탬프 형식 (항상 UTC)
+pub const EVE_LOG_TIMESTAMP_FORMAT: &str = "%Y.%m.%d %H:%M:%S";
+// 이전 버전은 모든 시각을 KST(UTC+9)로 저장
+const LEGACY_KST_OFFSET_SECONDS: i32 = 9 * 3600;
+// EVE 서버 다운타임 (매일 11:00 UTC)
+const EVE_DOWNTIME_UTC: (u32, u32) = (11, 0);
+
+// 통계의 "하루"가 시작되는 기준
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DayBoundary {
+    // 표시 시간대의 자정
+    Midnight,
+    // 매일 지정한 UTC 시각 (예: EVE 다운타임 11:00)
+    UtcCutoff(NaiveTime),
+}
+
+impl DayBoundary {
+    // "midnight", "downtime" 또는 UTC 기준 "HH:MM"
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "midnight" => Ok(DayBoundary::Midnight),
+            "downtime" => Ok(DayBoundary::UtcCutoff(
+                NaiveTime::from_hms_opt(EVE_DOWNTIME_UTC.0, EVE_DOWNTIME_UTC.1, 0).unwrap(),
+            )),
+            other => NaiveTime::parse_from_str(other, "%H:%M")
+                .map(DayBoundary::UtcCutoff)
+                .map_err(|_| format!("Invalid day boundary '{}': use midnight, downtime or HH:MM (UTC)", value)),
+        }
+    }
+
+    pub fn to_config_string(&self) -> String {
+        match self {
+            DayBoundary::Midnight => "midnight".to_string(),
+            DayBoundary::UtcCutoff(time) if (time.hour(), time.minute()) == EVE_DOWNTIME_UTC => "downtime".to_string(),
+            DayBoundary::UtcCutoff(time) => time.format("%H:%M").to_string(),
+        }
+    }
+}
+
+// 저장된 UTC 시각을 사용자가 설정한 시간대와 하루 기준으로 변환하는 유일한 지점
+#[derive(Debug, Clone, Copy)]
+pub struct TimeDisplay {
+    timezone: Tz,
+    day_boundary: DayBoundary,
+}
+
+impl Default for TimeDisplay {
+    fn default() -> Self {
+        TimeDisplay { timezone: chrono_tz::Asia::Seoul, day_boundary: DayBoundary::Midnight }
+    }
+}
+
+impl TimeDisplay {
+    pub fn new(timezone_name: &str) -> Result<Self, String> {
+        let timezone = timezone_name.trim().parse::<Tz>()
+            .map_err(|e| format!("Invalid timezone '{}': {}", timezone_name, e))?;
+        Ok(TimeDisplay { timezone, day_boundary: DayBoundary::Midnight })
+    }
+
+    pub fn with_day_boundary(mut self, day_boundary: DayBoundary) -> Self {
+        self.day_boundary = day_boundary;
+        self
+    }
+
+    pub fn timezone_name(&self) -> &'static str {
+        self.timezone.name()
+    }
+
+    pub fn to_display(&self, utc: DateTime<Utc>) -> DateTime<Tz> {
+        utc.with_timezone(&self.timezone)
+    }
+
+    pub fn format(&self, utc: DateTime<Utc>) -> String {
+        self.to_display(utc).format(DISPLAY_FORMAT).to_string()
+    }
+
+    // 표시 시간대의 달력 날짜
+    pub fn date_of(&self, utc: DateTime<Utc>) -> NaiveDate {
+        self.to_display(utc).date_naive()
+    }
+
+    // 하루 기준을 적용한 통계 날짜 (다운타임 기준이면 다운타임 이전 런은 전날로 집계)
+    pub fn day_of(&self, utc: DateTime<Utc>) -> NaiveDate {
+        match self.day_boundary {
+            DayBoundary::Midnight => self.date_of(utc),
+            DayBoundary::UtcCutoff(cutoff) => {
+                let since_midnight = Duration::seconds(cutoff.num_seconds_from_midnight() as i64);
+                (utc - since_midnight).date_naive()
+            }
+        }
+    }
+
+    // 통계 날짜 하루의 UTC 범위 [시작, 끝)
+    pub fn day_range_utc(&self, date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        match self.day_boundary {
+            DayBoundary::Midnight => {
+                let start = self.from_display(date.and_time(NaiveTime::MIN));
+                let end = self.from_display((date + Duration::days(1)).and_time(NaiveTime::MIN));
+                (start, end)
+            }
+            DayBoundary::UtcCutoff(cutoff) => {
+                let start = Utc.from_utc_datetime(&date.and_time(cutoff));
+                (start, start + Duration::days(1))
+            }
+        }
+    }
+
+    // 표시 시간대의 벽시계 시각을 UTC로 변환 (서머타임으로 없는 시각은 그 이후 첫 시각)
+    pub fn from_display(&self, naive: NaiveDateTime) -> DateTime<Utc> {
+        let mut candidate = naive;
+        loop {
+            if let Some(dt) = self.timezone.from_local_datetime(&candidate).earliest() {
+                return dt.with_timezone(&Utc);
+            }
+            candidate += Duration::minutes(30);
+        }
+    }
+
+    // "YYYY-MM-DD HH:MM:SS" 표시 시각 또는 RFC3339 시각을 UTC로 변환
+    pub fn parse_display(&self, value: &str) -> Result<DateTime<Utc>, String> {
+        if let Ok(dt) = DateTime::parse_from_rfc3339(value.trim()) {
+            return Ok(dt.with_timezone(&Utc));
+        }
+        let naive = NaiveDateTime::parse_from_str(value.trim(), DISPLAY_FORMAT)
+            .map_err(|e| format!("Failed to parse time '{}': {}", value, e))?;
+        Ok(self.from_display(naive))
+    }
+
+    // 결과 창에서 받은 런 시작/종료 시각을 UTC로 변환
+    // 전체 시각을 우선 사용하고, 구버전 창의 "HH:MM:SS"는 표시 시간대 기준으로 날짜를 추정
+    pub fn resolve_run_times(&self, start_time: &str, end_time: &str, now: DateTime<Utc>) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+        let now_display = self.to_display(now).naive_local();
+
+        let start_naive = match self.parse_display(start_time) {
+            Ok(dt) => self.to_display(dt).naive_local(),
+            Err(_) => {
+                let time = NaiveTime::parse_from_str(start_time.trim(), "%H:%M:%S")
+                    .map_err(|e| format!("Failed to parse start_time '{}': {}", start_time, e))?;
+                // 자정 이후에 입력된 결과는 전날 시작한 런
+                let candidate = now_display.date().and_time(time);
+                if candidate > now_display { candidate - Duration::days(1) } else { candidate }
+            }
+        };
+
+        let mut end_naive = match self.parse_display(end_time) {
+            Ok(dt) => self.to_display(dt).naive_local(),
+            Err(_) => {
+                let time = NaiveTime::parse_from_str(end_time.trim(), "%H:%M:%S")
+                    .map_err(|e| format!("Failed to parse end_time '{}': {}", end_time, e))?;
+                start_naive.date().and_time(time)
+            }
+        };
+        // 자정을 넘긴 런은 종료 날짜를 다음 날로
+        while end_naive < start_naive {
+            end_naive += Duration::days(1);
+        }
+
+        Ok((self.from_display(start_naive), self.from_display(end_naive)))
+    }
+}
+
+pub fn format_utc(utc: DateTime<Utc>) -> String {
+    utc.format(UTC_STORAGE_FORMAT).to_string()
+}
+
+pub fn parse_utc(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value.trim(), UTC_STORAGE_FORMAT).ok()
+        .map(|naive| Utc.from_utc_datetime(&naive))
+        .or_else(|| DateTime::parse_from_rfc3339(value.trim()).ok().map(|dt| dt.with_timezone(&Utc)))
+}
+
+// 이전 버전이 저장한 "YYYY-MM-DD HH:MM:SS" KST 시각을 UTC로 변환
+pub fn parse_legacy_kst(value: &str) -> Option<DateTime<Utc>> {
+    let kst = FixedOffset::east_opt(LEGACY_KST_OFFSET_SECONDS)?;
+    let naive = NaiveDateTime::parse_from_str(value.trim(), DISPLAY_FORMAT).ok()?;
+    kst.from_local_datetime(&naive).single().map(|dt| dt.with_timezone(&Utc))
+}
+
+pub fn parse_eve_log_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value.trim(), EVE_LOG_TIMESTAMP_FORMAT).ok()
+        .map(|naive| Utc.from_utc_datetime(&naive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        parse_utc(value).unwrap()
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn parses_day_boundaries() {
+        assert_eq!(DayBoundary::parse("").unwrap(), DayBoundary::Midnight);
+        assert_eq!(DayBoundary::parse("Midnight").unwrap(), DayBoundary::Midnight);
+        assert_eq!(DayBoundary::parse("downtime").unwrap().to_config_string(), "downtime");
+        assert_eq!(DayBoundary::parse("11:00").unwrap().to_config_string(), "downtime");
+        assert_eq!(DayBoundary::parse("06:30").unwrap().to_config_string(), "06:30");
+        assert!(DayBoundary::parse("25:00").is_err());
+    }
+
+    #[test]
+    fn midnight_day_follows_display_timezone() {
+        let seoul = TimeDisplay::new("Asia/Seoul").unwrap();
+        // 15:00 UTC = 다음 날 00:00 KST
+        assert_eq!(seoul.day_of(utc("2024-05-01T14:59:59Z")), date("2024-05-01"));
+        assert_eq!(seoul.day_of(utc("2024-05-01T15:00:00Z")), date("2024-05-02"));
+        assert_eq!(seoul.day_range_utc(date("2024-05-02")), (utc("2024-05-01T15:00:00Z"), utc("2024-05-02T15:00:00Z")));
+    }
+
+    #[test]
+    fn downtime_day_ignores_display_timezone() {
+        for timezone in ["Asia/Seoul", "America/New_York", "UTC"] {
+            let display = TimeDisplay::new(timezone).unwrap().with_day_boundary(DayBoundary::parse("downtime").unwrap());
+            assert_eq!(display.day_of(utc("2024-05-02T10:59:59Z")), date("2024-05-01"), "{}", timezone);
+            assert_eq!(display.day_of(utc("2024-05-02T11:00:00Z")), date("2024-05-02"), "{}", timezone);
+            assert_eq!(display.day_range_utc(date("2024-05-02")), (utc("2024-05-02T11:00:00Z"), utc("2024-05-03T11:00:00Z")));
+        }
+
+        let custom = TimeDisplay::default().with_day_boundary(DayBoundary::parse("06:30").unwrap());
+        assert_eq!(custom.day_of(utc("2024-01-01T06:29:00Z")), date("2023-12-31"));
+        assert_eq!(custom.day_range_utc(date("2024-01-01")).0, utc("2024-01-01T06:30:00Z"));
+    }
+
+    #[test]
+    fn midnight_day_range_spans_dst_changes() {
+        let new_york = TimeDisplay::new("America/New_York").unwrap();
+        // 서머타임 시작일은 23시간, 종료일은 25시간
+        let (start, end) = new_york.day_range_utc(date("2024-03-10"));
+        assert_eq!((start, end), (utc("2024-03-10T05:00:00Z"), utc("2024-03-11T04:00:00Z")));
+        assert_eq!(end - start, Duration::hours(23));
+        let (start, end) = new_york.day_range_utc(date("2024-11-03"));
+        assert_eq!(end - start, Duration::hours(25));
+        assert_eq!(new_york.day_of(utc("2024-11-04T04:59:59Z")), date("2024-11-03"));
+        assert_eq!(new_york.day_of(utc("2024-11-04T05:00:00Z")), date("2024-11-04"));
+
+        // 자정이 없는 날(00:00 → 01:00)은 그 이후 첫 시각부터
+        let santiago = TimeDisplay::new("America/Santiago").unwrap();
+        assert_eq!(santiago.day_range_utc(date("2024-09-08")).0, utc("2024-09-08T04:00:00Z"));
+        assert_eq!(santiago.day_range_utc(date("2024-09-07")).1, utc("2024-09-08T04:00:00Z"));
+    }
+
+    #[test]
+    fn every_instant_falls_in_its_day_range() {
+        let displays = [
+            TimeDisplay::new("America/New_York").unwrap(),
+            TimeDisplay::new("Europe/Berlin").unwrap().with_day_boundary(DayBoundary::parse("downtime").unwrap()),
+        ];
+        for display in displays {
+            let mut instant = utc("2024-03-09T00:00:00Z");
+            while instant < utc("2024-03-12T00:00:00Z") {
+                let (start, end) = display.day_range_utc(display.day_of(instant));
+                assert!(start <= instant && instant < end, "{} not in [{}, {})", instant, start, end);
+                instant += Duration::minutes(30);
+            }
+        }
+    }
+}
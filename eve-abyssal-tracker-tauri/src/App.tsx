@@ -14,9 +14,18 @@ import { usePopup } from "./hooks/usePopup";
 import { useAbyssalData } from "./hooks/useAbyssalData";
 import { useUpdater } from "./hooks/useUpdater";
 import { useAppInit } from "./hooks/useAppInit";
+import { AbyssalData } from "./types";
 
 type ViewType = 'daily-summary' | 'daily-list' | 'overall' | 'settings';
 
+// 캐시된 시세 중 가장 오래된 시세의 경과 시간 (시간 단위)
+const oldestPriceAgeHours = (data: AbyssalData): number | null => {
+  const ages = Object.values(data.price_freshness)
+    .map(freshness => freshness.age_seconds)
+    .filter((age): age is number => age !== null);
+  return ages.length > 0 ? Math.floor(Math.max(...ages) / 3600) : null;
+};
+
 function App() {
   const [activeView, setActiveView] = useState<ViewType>('daily-list');
   const [isDailySubMenuOpen, setIsDailySubMenuOpen] = useState(true);
@@ -192,6 +201,22 @@ function App() {
         </nav>
         
         <main className="main-content">
+          {activeView !== 'settings' && abyssalData && (abyssalData.offline || abyssalData.unpriced_items.length > 0) && (
+            <div className="price-status-banner">
+              {abyssalData.offline && (
+                <span>
+                  <i className="fas fa-plug"></i> 오프라인 모드: 캐시된 시세 사용
+                  {oldestPriceAgeHours(abyssalData) !== null && ` (가장 오래된 시세 ${oldestPriceAgeHours(abyssalData)}시간 전)`}
+                </span>
+              )}
+              {abyssalData.unpriced_items.length > 0 && (
+                <span title={abyssalData.unpriced_items.join('\n')}>
+                  <i className="fas fa-exclamation-triangle"></i> 시세 없음 {abyssalData.unpriced_items.length}종 (0 ISK로 계산): {abyssalData.unpriced_items.slice(0, 3).join(', ')}
+                  {abyssalData.unpriced_items.length > 3 && ' ...'}
+                </span>
+              )}
+            </div>
+          )}
           {(activeView === 'daily-list') && abyssalData && (
             <StatsDisplay 
               data={abyssalData}
@@ -10,6 +10,7 @@ interface DailyStatsDisplayProps {
   df: RunData[];
   daily_stats: DailyStats;
   item_buy_price_cache: { [key: string]: number };
+  item_english_names?: { [itemName: string]: string }; // 현지화된 아이템 이름 → 영어 이름
   selectedDate: string;
   setSelectedDate: (date: string) => void;
   onDataUpdate?: () => void; // 전체 데이터 업데이트 콜백
@@ -20,6 +21,7 @@ interface DailyStatsDisplayProps {
 const DailyStatsDisplay: React.FC<DailyStatsDisplayProps> = ({
   daily_stats,
   item_buy_price_cache,
+  item_english_names = {},
   selectedDate,
   setSelectedDate,
   onRunDeleted,
@@ -82,8 +84,7 @@ const DailyStatsDisplay: React.FC<DailyStatsDisplayProps> = ({
     try {
       // CSV에서만 삭제 (API 호출 없이) - 확인창 없이 바로 삭제
       await invoke('delete_abyssal_run_command', {
-        startTimeKst: run['시작시각(KST)'],
-        endTimeKst: run['종료시각(KST)']
+        runId: run['런 ID']
       });
       
       console.log('[INFO] Run deleted from CSV successfully');
@@ -199,7 +200,7 @@ const DailyStatsDisplay: React.FC<DailyStatsDisplayProps> = ({
                 <div className="run-header" onClick={() => toggleRunExpansion(i)}>
                   <div className="run-meta">
                     <div className="run-time-badge">
-                      {run['시작시각(KST)'].split(' ')[1]?.substring(0, 5)}
+                      {run['시작시각'].split(' ')[1]?.substring(0, 5)}
                     </div>
                     <div className="ship-class-badge">
                       <ShipClassIcon 
@@ -247,14 +248,14 @@ const DailyStatsDisplay: React.FC<DailyStatsDisplayProps> = ({
                         <div className="timeline-icon">🚀</div>
                         <div className="timeline-content">
                           <div className="timeline-title">시작</div>
-                          <div className="timeline-time">{run['시작시각(KST)']}</div>
+                          <div className="timeline-time">{run['시작시각']}</div>
                         </div>
                       </div>
                       <div className="timeline-item">
                         <div className="timeline-icon">🏁</div>
                         <div className="timeline-content">
                           <div className="timeline-title">완료</div>
-                          <div className="timeline-time">{run['종료시각(KST)']}</div>
+                          <div className="timeline-time">{run['종료시각']}</div>
                         </div>
                       </div>
                       <div className="timeline-item">
@@ -290,7 +291,12 @@ const DailyStatsDisplay: React.FC<DailyStatsDisplayProps> = ({
                                   size={24}
                                   className="loot-item-icon"
                                 />
-                                <div className="loot-item-name">{item['아이템 이름']}</div>
+                                <div className="loot-item-name">
+                                  {item['아이템 이름']}
+                                  {item_english_names[item['아이템 이름']] && (
+                                    <div className="loot-item-name-en">{item_english_names[item['아이템 이름']]}</div>
+                                  )}
+                                </div>
                                 <div className="loot-item-stats">
                                   <span className="quantity">×{item['개수']}</span>
                                   <span className="divider">|</span>
@@ -188,7 +188,7 @@ const DailySummary: React.FC<DailySummaryProps> = ({
               </defs>
               <CartesianGrid strokeDasharray="3 3" stroke="var(--border-primary)" />
               <XAxis 
-                dataKey="시작시각(KST)" 
+                dataKey="시작시각" 
                 stroke="var(--text-muted)" 
                 tick={{ fill: 'var(--text-muted)', fontSize: 12 }}
                 tickFormatter={(value) => value.split(' ')[1]?.substring(0, 5) || ''}
@@ -3,11 +3,13 @@ import React, { useState, useEffect, useCallback } from 'react';
 import './Settings.css';
 import { revealItemInDir } from '@tauri-apps/plugin-opener';
 import { invoke } from "@tauri-apps/api/core";
-import { LocationInfo } from '../types';
+import { ChatLogHeader, LocationInfo, LogPatternValidation } from '../types';
 
 interface GeneralConfig {
   log_path: string;
   character_name: string;
+  timezone: string;
+  day_boundary: string;
 }
 
 interface TrackerConfig {
@@ -16,9 +18,52 @@ interface TrackerConfig {
   overall_stats_path: string;
 }
 
+interface PriceConfig {
+  providers: string;
+  trade_hub: string;
+  market_scope: string;
+  loot_basis: string;
+  filament_basis: string;
+  valuation: string;
+  offline: boolean;
+}
+
+const PRICE_BASIS_OPTIONS: { value: string; label: string }[] = [
+  { value: 'buy_max', label: '최고 매수가 (buy max)' },
+  { value: 'sell_min', label: '최저 매도가 (sell min)' },
+  { value: 'buy_weighted_average', label: '매수 가중 평균' },
+  { value: 'sell_weighted_average', label: '매도 가중 평균' },
+  { value: 'buy_median', label: '매수 중앙값' },
+  { value: 'sell_median', label: '매도 중앙값' },
+  { value: 'buy_percentile', label: '매수 상위 5% 평균' },
+  { value: 'sell_percentile', label: '매도 하위 5% 평균' },
+  { value: 'split', label: '매수/매도 중간값 (split)' },
+];
+
+interface HttpSettings {
+  esi_base_url: string;
+  fuzzwork_base_url: string;
+  image_base_url: string;
+  github_base_url: string;
+  timeout_seconds: number;
+  proxy: string;
+  contact: string;
+  retries: number;
+  max_parallel_requests: number;
+}
+
+const HTTP_URL_FIELDS: { name: keyof HttpSettings; label: string; placeholder: string }[] = [
+  { name: 'esi_base_url', label: 'ESI', placeholder: 'https://esi.evetech.net/latest' },
+  { name: 'fuzzwork_base_url', label: 'Fuzzwork', placeholder: 'https://market.fuzzwork.co.uk' },
+  { name: 'image_base_url', label: '이미지 서버', placeholder: 'https://images.evetech.net' },
+  { name: 'github_base_url', label: 'GitHub (업데이트)', placeholder: 'https://github.com' },
+];
+
 interface AppConfig {
   general: GeneralConfig;
   tracker: TrackerConfig;
+  prices: PriceConfig;
+  http: HttpSettings;
 }
 
 interface SettingsProps {
@@ -31,13 +76,43 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
     general: {
       log_path: '',
       character_name: '',
+      timezone: 'Asia/Seoul',
+      day_boundary: 'midnight',
     },
     tracker: {
       abyssal_data_path: '',
       daily_stats_path: '',
       overall_stats_path: '',
     },
+    prices: {
+      providers: 'fuzzwork,esi_orders,esi_average,static',
+      trade_hub: 'jita',
+      market_scope: 'region',
+      loot_basis: 'buy_max',
+      filament_basis: 'sell_min',
+      valuation: 'historical',
+      offline: false,
+    },
+    http: {
+      esi_base_url: 'https://esi.evetech.net/latest',
+      fuzzwork_base_url: 'https://market.fuzzwork.co.uk',
+      image_base_url: 'https://images.evetech.net',
+      github_base_url: 'https://github.com',
+      timeout_seconds: 15,
+      proxy: '',
+      contact: '',
+      retries: 3,
+      max_parallel_requests: 8,
+    },
   });
+  const [priceOverrides, setPriceOverrides] = useState<{ [itemName: string]: number }>({});
+  const [newOverrideName, setNewOverrideName] = useState('');
+  const [newOverridePrice, setNewOverridePrice] = useState('');
+  const [staticTypesInfo, setStaticTypesInfo] = useState<{ type_count: number; built_at: string } | null>(null);
+  const [sdePath, setSdePath] = useState('');
+  const [updatingStaticTypes, setUpdatingStaticTypes] = useState(false);
+  const [patternTestPath, setPatternTestPath] = useState('');
+  const [patternValidation, setPatternValidation] = useState<LogPatternValidation | null>(null);
   const [loading, setLoading] = useState(true);
   const [saving, setSaving] = useState(false);
   const [isDirty, setIsDirty] = useState(false);
@@ -54,6 +129,7 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
     modified_time: string;
     monitoring: boolean;
   } | null>(null);
+  const [logFileHeader, setLogFileHeader] = useState<ChatLogHeader | null>(null);
 
 
   const loadConfig = useCallback(async () => {
@@ -70,6 +146,24 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
     }
   }, [triggerPopup]);
 
+  const loadPriceOverrides = useCallback(async () => {
+    try {
+      const result = await invoke("get_price_overrides") as { [itemName: string]: number };
+      setPriceOverrides(result);
+    } catch (e) {
+      console.error("Failed to load price overrides:", e);
+    }
+  }, []);
+
+  const loadStaticTypesInfo = useCallback(async () => {
+    try {
+      const result = await invoke("get_static_types_info") as { type_count: number; built_at: string };
+      setStaticTypesInfo(result);
+    } catch (e) {
+      console.error("Failed to load static type info:", e);
+    }
+  }, []);
+
   const loadLocationInfo = useCallback(async () => {
     try {
       const result = await invoke("get_location_info_command") as LocationInfo;
@@ -94,6 +188,9 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
     try {
       const result = await invoke("get_current_log_file_info") as { file_name: string; full_path: string; file_size: number; modified_time: string; monitoring: boolean } | null;
       setLogFileInfo(result);
+      setLogFileHeader(result
+        ? await invoke("get_chat_log_header_command", { filePath: result.full_path }) as ChatLogHeader
+        : null);
     } catch (e) {
       console.error("Failed to load log file info:", e);
       // 로그 파일 정보 로딩 실패는 조용히 처리
@@ -105,6 +202,14 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
     try {
       await invoke("set_log_path", { path: config.general.log_path });
       await invoke("set_character_name", { characterName: config.general.character_name });
+      await invoke("set_timezone", { timezone: config.general.timezone });
+      await invoke("set_day_boundary", { dayBoundary: config.general.day_boundary });
+      await invoke("set_price_providers", { providers: config.prices.providers });
+      await invoke("set_trade_hub", { tradeHub: config.prices.trade_hub, marketScope: config.prices.market_scope });
+      await invoke("set_price_basis", { lootBasis: config.prices.loot_basis, filamentBasis: config.prices.filament_basis });
+      await invoke("set_valuation_mode", { valuation: config.prices.valuation });
+      await invoke("set_offline_mode", { offline: !!config.prices.offline });
+      await invoke("set_http_settings", { settings: config.http });
       triggerPopup("설정 저장 완료", "설정이 성공적으로 저장되었습니다.", "info");
       setIsDirty(false);
       onSettingsSaved(); // Notify parent that settings were saved
@@ -116,6 +221,80 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
     }
   };
 
+  const handleAddPriceOverride = async () => {
+    const price = Number(newOverridePrice);
+    if (!newOverrideName.trim() || !Number.isFinite(price) || price < 0) {
+      triggerPopup("입력 오류", "아이템 이름과 0 이상의 가격을 입력하세요.", "warning");
+      return;
+    }
+    try {
+      await invoke("set_price_override", { itemName: newOverrideName.trim(), price });
+      setNewOverrideName('');
+      setNewOverridePrice('');
+      await loadPriceOverrides();
+    } catch (e) {
+      console.error("Failed to set price override:", e);
+      triggerPopup("수동 가격 저장 실패", `수동 가격 저장에 실패했습니다: ${e}`, "error");
+    }
+  };
+
+  const handleRemovePriceOverride = async (itemName: string) => {
+    try {
+      await invoke("set_price_override", { itemName, price: null });
+      await loadPriceOverrides();
+    } catch (e) {
+      console.error("Failed to remove price override:", e);
+      triggerPopup("수동 가격 삭제 실패", `수동 가격 삭제에 실패했습니다: ${e}`, "error");
+    }
+  };
+
+  const handleUpdateStaticTypes = async () => {
+    if (!sdePath.trim()) {
+      triggerPopup("입력 오류", "types.jsonl, groups.jsonl, categories.jsonl이 있는 SDE 폴더 경로를 입력하세요.", "warning");
+      return;
+    }
+    setUpdatingStaticTypes(true);
+    try {
+      const typeCount = await invoke("update_static_types", { sdePath: sdePath.trim() }) as number;
+      await loadStaticTypesInfo();
+      triggerPopup("타입 DB 업데이트", `${typeCount.toLocaleString()}개 타입을 불러왔습니다.`, "info");
+    } catch (e) {
+      console.error("Failed to update static types:", e);
+      triggerPopup("타입 DB 업데이트 실패", `SDE에서 타입 DB를 만들지 못했습니다: ${e}`, "error");
+    } finally {
+      setUpdatingStaticTypes(false);
+    }
+  };
+
+  const handleValidateLogPatterns = async () => {
+    const filePath = patternTestPath.trim() || logFileInfo?.full_path || '';
+    if (!filePath) {
+      triggerPopup("입력 오류", "패턴을 시험할 로그 파일 경로를 입력하세요.", "warning");
+      return;
+    }
+    try {
+      const result = await invoke("validate_log_patterns", { filePath }) as LogPatternValidation;
+      setPatternValidation(result);
+    } catch (e) {
+      console.error("Failed to validate log patterns:", e);
+      triggerPopup("패턴 검증 실패", `로그 패턴을 검증하지 못했습니다: ${e}`, "error");
+    }
+  };
+
+  const handleReloadLogPatterns = async () => {
+    try {
+      const result = await invoke("reload_log_patterns") as { file_path: string; languages: string[]; errors: string[] };
+      if (result.errors.length > 0) {
+        triggerPopup("로그 패턴 적용", `일부 항목을 무시했습니다:\n${result.errors.join('\n')}`, "warning");
+      } else {
+        triggerPopup("로그 패턴 적용", `${result.languages.join(', ')} 패턴을 적용했습니다. 새로 여는 로그 파일부터 반영됩니다.`, "info");
+      }
+    } catch (e) {
+      console.error("Failed to reload log patterns:", e);
+      triggerPopup("패턴 적용 실패", `로그 패턴을 다시 불러오지 못했습니다: ${e}`, "error");
+    }
+  };
+
   const handleOpenCsvFolder = async () => {
     try {
       const dataPath = await invoke("get_csv_data_path") as string;
@@ -163,6 +342,8 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
 
   useEffect(() => {
     loadConfig();
+    loadPriceOverrides();
+    loadStaticTypesInfo();
     loadLocationInfo();
     loadAbyssalWindowState();
     loadLogFileInfo();
@@ -176,7 +357,7 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
     return () => {
       clearInterval(intervalId);
     };
-  }, [loadConfig, loadLocationInfo, loadAbyssalWindowState, loadLogFileInfo]);
+  }, [loadConfig, loadPriceOverrides, loadLocationInfo, loadAbyssalWindowState, loadLogFileInfo]);
 
   const handleChange = (e: React.ChangeEvent<HTMLInputElement | HTMLSelectElement>) => {
     const { name, value } = e.target;
@@ -190,13 +371,37 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
     setIsDirty(true);
   };
 
+  const handlePriceChange = (e: React.ChangeEvent<HTMLInputElement | HTMLSelectElement>) => {
+    const { name } = e.target;
+    const value = e.target.type === 'checkbox' ? (e.target as HTMLInputElement).checked : e.target.value;
+    setConfig(prevConfig => ({
+      ...prevConfig,
+      prices: {
+        ...prevConfig.prices,
+        [name]: value,
+      },
+    }));
+    setIsDirty(true);
+  };
+
+  const handleHttpChange = (e: React.ChangeEvent<HTMLInputElement>) => {
+    const { name, value, type } = e.target;
+    setConfig(prevConfig => ({
+      ...prevConfig,
+      http: {
+        ...prevConfig.http,
+        [name]: type === 'number' ? Math.max(0, parseInt(value, 10) || 0) : value,
+      },
+    }));
+    setIsDirty(true);
+  };
+
   const formatLastUpdated = (lastUpdated: string | null): string => {
     if (!lastUpdated) return '정보 없음';
     try {
+      // 백엔드는 UTC 시각을 보내므로 설정된 시간대로 표시
       const date = new Date(lastUpdated);
-      // 한국시간(KST = UTC+9)으로 변환
-      const kstDate = new Date(date.getTime() + (9 * 60 * 60 * 1000));
-      return kstDate.toLocaleString('ko-KR', {
+      return date.toLocaleString('ko-KR', {
         year: 'numeric',
         month: '2-digit',
         day: '2-digit',
@@ -204,7 +409,7 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
         minute: '2-digit',
         second: '2-digit',
         hour12: false,
-        timeZone: 'Asia/Seoul'
+        timeZone: config.general.timezone || 'Asia/Seoul'
       });
     } catch {
       return '정보 없음';
@@ -273,6 +478,43 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
               />
             </div>
 
+            <div className="config-field">
+              <label className="field-label" htmlFor="timezone">
+                <span className="label-text">🕒 표시 시간대</span>
+                <span className="label-hint">통계와 내보내기에 사용할 시간대 (예: Asia/Seoul, Europe/Berlin, UTC)</span>
+              </label>
+              <input
+                type="text"
+                id="timezone"
+                name="timezone"
+                value={config.general.timezone || ''}
+                onChange={handleChange}
+                placeholder="Asia/Seoul"
+                className="field-input"
+              />
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="day_boundary">
+                <span className="label-text">📅 하루 기준</span>
+                <span className="label-hint">midnight: 표시 시간대 자정, downtime: EVE 다운타임(11:00 UTC), HH:MM: 지정한 UTC 시각</span>
+              </label>
+              <input
+                type="text"
+                id="day_boundary"
+                name="day_boundary"
+                list="day_boundary_options"
+                value={config.general.day_boundary || ''}
+                onChange={handleChange}
+                placeholder="midnight"
+                className="field-input"
+              />
+              <datalist id="day_boundary_options">
+                <option value="midnight" />
+                <option value="downtime" />
+              </datalist>
+            </div>
+
             <div className="config-field">
               <label className="field-label">
                 <span className="label-text">📊 CSV 데이터 폴더</span>
@@ -291,6 +533,318 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
           </div>
         </div>
 
+        {/* Price Configuration */}
+        <div className="config-section">
+          <div className="section-header">
+            <div className="section-icon">💰</div>
+            <div className="section-info">
+              <h2 className="section-title">시세 설정</h2>
+              <p className="section-description">드롭과 입장료 계산에 사용할 시세 출처</p>
+            </div>
+          </div>
+
+          <div className="config-grid">
+            <div className="config-field">
+              <label className="field-label" htmlFor="providers">
+                <span className="label-text">📈 시세 제공자 우선순위</span>
+                <span className="label-hint">쉼표로 구분, 앞쪽이 실패하거나 시세가 없으면 다음 제공자 사용 (fuzzwork, esi_orders, esi_average, static)</span>
+              </label>
+              <input
+                type="text"
+                id="providers"
+                name="providers"
+                value={config.prices.providers || ''}
+                onChange={handlePriceChange}
+                placeholder="fuzzwork,esi_orders,esi_average,static"
+                className="field-input"
+              />
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="offline">
+                <span className="label-text">🔌 오프라인 모드</span>
+                <span className="label-hint">ESI/Fuzzwork에 접속하지 않고 마지막으로 받은 시세와 type_id만 사용 (만료된 시세 포함)</span>
+              </label>
+              <input
+                type="checkbox"
+                id="offline"
+                name="offline"
+                checked={!!config.prices.offline}
+                onChange={handlePriceChange}
+              />
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="trade_hub">
+                <span className="label-text">🏪 거래 허브</span>
+                <span className="label-hint">드롭을 판매하고 필라멘트를 구매하는 시장</span>
+              </label>
+              <select
+                id="trade_hub"
+                name="trade_hub"
+                value={config.prices.trade_hub || 'jita'}
+                onChange={handlePriceChange}
+                className="field-input"
+              >
+                <option value="jita">Jita (The Forge)</option>
+                <option value="amarr">Amarr (Domain)</option>
+                <option value="dodixie">Dodixie (Sinq Laison)</option>
+                <option value="rens">Rens (Heimatar)</option>
+                <option value="hek">Hek (Metropolis)</option>
+              </select>
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="market_scope">
+                <span className="label-text">📍 시세 범위</span>
+                <span className="label-hint">허브가 속한 지역 전체 주문 또는 허브 정거장 주문만 사용</span>
+              </label>
+              <select
+                id="market_scope"
+                name="market_scope"
+                value={config.prices.market_scope || 'region'}
+                onChange={handlePriceChange}
+                className="field-input"
+              >
+                <option value="region">지역 전체</option>
+                <option value="station">허브 정거장만</option>
+              </select>
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="loot_basis">
+                <span className="label-text">💎 드롭 가치 기준</span>
+                <span className="label-hint">획득 아이템 가치를 계산할 시세 값</span>
+              </label>
+              <select
+                id="loot_basis"
+                name="loot_basis"
+                value={config.prices.loot_basis || 'buy_max'}
+                onChange={handlePriceChange}
+                className="field-input"
+              >
+                {PRICE_BASIS_OPTIONS.map(option => (
+                  <option key={option.value} value={option.value}>{option.label}</option>
+                ))}
+              </select>
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="filament_basis">
+                <span className="label-text">🎫 입장료 기준</span>
+                <span className="label-hint">필라멘트 구매 비용을 계산할 시세 값</span>
+              </label>
+              <select
+                id="filament_basis"
+                name="filament_basis"
+                value={config.prices.filament_basis || 'sell_min'}
+                onChange={handlePriceChange}
+                className="field-input"
+              >
+                {PRICE_BASIS_OPTIONS.map(option => (
+                  <option key={option.value} value={option.value}>{option.label}</option>
+                ))}
+              </select>
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="valuation">
+                <span className="label-text">📅 런 평가 시세</span>
+                <span className="label-hint">실수익과 ISK/h를 런 당시 시세로 볼지 현재 시세로 볼지 선택</span>
+              </label>
+              <select
+                id="valuation"
+                name="valuation"
+                value={config.prices.valuation || 'historical'}
+                onChange={handlePriceChange}
+                className="field-input"
+              >
+                <option value="historical">당시 시세 (런 날짜에 가장 가까운 기록)</option>
+                <option value="current">현재 시세</option>
+              </select>
+            </div>
+
+            <div className="config-field">
+              <label className="field-label">
+                <span className="label-text">✍️ 수동 가격</span>
+                <span className="label-hint">거래가 적은 아이템은 시세 대신 직접 입력한 가격 사용 (드롭과 입장료 모두 적용)</span>
+              </label>
+              {Object.entries(priceOverrides).sort(([a], [b]) => a.localeCompare(b)).map(([itemName, price]) => (
+                <div key={itemName} className="location-item">
+                  <span className="location-label">{itemName}</span>
+                  <span className="location-value">{price.toLocaleString()} ISK</span>
+                  <button
+                    onClick={() => handleRemovePriceOverride(itemName)}
+                    className="control-button danger small"
+                  >
+                    <span className="button-text">삭제</span>
+                  </button>
+                </div>
+              ))}
+              <input
+                type="text"
+                value={newOverrideName}
+                onChange={e => setNewOverrideName(e.target.value)}
+                placeholder="아이템 이름 (예: Abyssal Damage Control)"
+                className="field-input"
+              />
+              <input
+                type="number"
+                min="0"
+                value={newOverridePrice}
+                onChange={e => setNewOverridePrice(e.target.value)}
+                placeholder="가격 (ISK)"
+                className="field-input"
+              />
+              <button
+                onClick={handleAddPriceOverride}
+                className="control-button secondary small"
+              >
+                <span className="button-icon">➕</span>
+                <span className="button-text">수동 가격 추가</span>
+              </button>
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="sde_path">
+                <span className="label-text">🗂️ 정적 타입 DB</span>
+                <span className="label-hint">
+                  {staticTypesInfo && staticTypesInfo.type_count > 0
+                    ? `${staticTypesInfo.type_count.toLocaleString()}개 타입 (${formatLastUpdated(staticTypesInfo.built_at || null)}) - 이름 조회에 ESI 대신 사용`
+                    : '비어 있음 - 모든 이름을 ESI로 조회'}
+                </span>
+              </label>
+              <input
+                type="text"
+                id="sde_path"
+                value={sdePath}
+                onChange={e => setSdePath(e.target.value)}
+                placeholder="SDE JSONL 폴더 경로 (types.jsonl, groups.jsonl, categories.jsonl)"
+                className="field-input"
+              />
+              <button
+                onClick={handleUpdateStaticTypes}
+                disabled={updatingStaticTypes}
+                className="control-button secondary small"
+              >
+                <span className="button-icon">🔄</span>
+                <span className="button-text">{updatingStaticTypes ? '업데이트 중...' : 'SDE에서 업데이트'}</span>
+              </button>
+            </div>
+          </div>
+        </div>
+
+        {/* Network Configuration */}
+        <div className="config-section">
+          <div className="section-header">
+            <div className="section-icon">🌐</div>
+            <div className="section-info">
+              <h2 className="section-title">네트워크 설정</h2>
+              <p className="section-description">ESI, Fuzzwork, 이미지 서버, GitHub 요청에 공통으로 적용되는 HTTP 설정</p>
+            </div>
+          </div>
+
+          <div className="config-grid">
+            <div className="config-field">
+              <label className="field-label" htmlFor="contact">
+                <span className="label-text">✉️ 연락처</span>
+                <span className="label-hint">ESI 요청 User-Agent에 포함 (캐릭터 이름이나 이메일)</span>
+              </label>
+              <input
+                type="text"
+                id="contact"
+                name="contact"
+                value={config.http.contact || ''}
+                onChange={handleHttpChange}
+                placeholder="예: Character Name 또는 me@example.com"
+                className="field-input"
+              />
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="proxy">
+                <span className="label-text">🛡️ 프록시</span>
+                <span className="label-hint">비워 두면 시스템 설정 사용</span>
+              </label>
+              <input
+                type="text"
+                id="proxy"
+                name="proxy"
+                value={config.http.proxy || ''}
+                onChange={handleHttpChange}
+                placeholder="http://proxy.example.com:8080"
+                className="field-input"
+              />
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="timeout_seconds">
+                <span className="label-text">⏱️ 타임아웃 (초)</span>
+                <span className="label-hint">요청 하나의 최대 대기 시간</span>
+              </label>
+              <input
+                type="number"
+                id="timeout_seconds"
+                name="timeout_seconds"
+                min={1}
+                value={config.http.timeout_seconds}
+                onChange={handleHttpChange}
+                className="field-input"
+              />
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="retries">
+                <span className="label-text">🔁 재시도 횟수</span>
+                <span className="label-hint">연결 실패, 타임아웃, 요청 제한(420/429), 서버 오류(5xx) 시 재시도</span>
+              </label>
+              <input
+                type="number"
+                id="retries"
+                name="retries"
+                min={0}
+                value={config.http.retries}
+                onChange={handleHttpChange}
+                className="field-input"
+              />
+            </div>
+
+            <div className="config-field">
+              <label className="field-label" htmlFor="max_parallel_requests">
+                <span className="label-text">🚦 최대 동시 요청 수</span>
+                <span className="label-hint">ESI 에러 한도에 가까워지면 자동으로 요청을 멈추고 기다림</span>
+              </label>
+              <input
+                type="number"
+                id="max_parallel_requests"
+                name="max_parallel_requests"
+                min={1}
+                value={config.http.max_parallel_requests}
+                onChange={handleHttpChange}
+                className="field-input"
+              />
+            </div>
+
+            {HTTP_URL_FIELDS.map(field => (
+              <div className="config-field" key={field.name}>
+                <label className="field-label" htmlFor={field.name}>
+                  <span className="label-text">🔗 {field.label} 주소</span>
+                  <span className="label-hint">프록시 서버나 테스트용 목 서버로 변경 가능</span>
+                </label>
+                <input
+                  type="text"
+                  id={field.name}
+                  name={field.name}
+                  value={config.http[field.name] as string}
+                  onChange={handleHttpChange}
+                  placeholder={field.placeholder}
+                  className="field-input"
+                />
+              </div>
+            ))}
+          </div>
+        </div>
+
         {/* Monitor Control */}
         <div className="config-section">
           <div className="section-header">
@@ -395,6 +949,18 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
                       <span className="location-label">🔄 마지막 수정</span>
                       <span className="location-value">{logFileInfo?.modified_time || '정보 없음'}</span>
                     </div>
+                    {logFileHeader && (
+                      <>
+                        <div className="location-item">
+                          <span className="location-label">💬 채널</span>
+                          <span className="location-value">{logFileHeader.channel_name || '정보 없음'}</span>
+                        </div>
+                        <div className="location-item">
+                          <span className="location-label">🚀 세션 시작</span>
+                          <span className="location-value">{formatLastUpdated(logFileHeader.session_started)}</span>
+                        </div>
+                      </>
+                    )}
                   </div>
                 ) : (
                   <p className="card-description">
@@ -411,7 +977,70 @@ const Settings: React.FC<SettingsProps> = ({ onSettingsSaved, triggerPopup }) =>
               </div>
             </div>
 
-
+            <div className="control-card">
+              <div className="card-header">
+                <div className="card-icon">🧩</div>
+                <div className="card-title">로그 패턴</div>
+              </div>
+              <div className="card-content">
+                <p className="card-description">
+                  데이터 폴더의 log_patterns.json을 고쳐 클라이언트 문구 변경에 대응할 수 있습니다. 적용 전에 로그 파일로 시험해 보세요.
+                </p>
+                <input
+                  type="text"
+                  value={patternTestPath}
+                  onChange={e => setPatternTestPath(e.target.value)}
+                  placeholder={logFileInfo?.full_path || '시험할 로그 파일 경로'}
+                  className="field-input"
+                />
+                {patternValidation && (
+                  <div className="location-info">
+                    <div className="location-item">
+                      <span className="location-label">🌐 감지된 언어</span>
+                      <span className="location-value">{patternValidation.detected_language}{patternValidation.filename_matched ? '' : ' (파일 이름 불일치)'}</span>
+                    </div>
+                    <div className="location-item">
+                      <span className="location-label">👤 캐릭터</span>
+                      <span className="location-value">{patternValidation.listener || '찾을 수 없음'}</span>
+                    </div>
+                    <div className="location-item">
+                      <span className="location-label">🔀 성계 변경</span>
+                      <span className="location-value">
+                        {patternValidation.system_change_count}회 (어비셜 {patternValidation.unknown_system_count}회) / {patternValidation.lines_scanned}줄
+                      </span>
+                    </div>
+                    {patternValidation.errors.map(error => (
+                      <div key={error} className="location-item">
+                        <span className="location-label">⚠️ 무시된 항목</span>
+                        <span className="location-value">{error}</span>
+                      </div>
+                    ))}
+                    {patternValidation.matches.slice(0, 5).map(match => (
+                      <div key={match.line_number} className="location-item">
+                        <span className="location-label">#{match.line_number}</span>
+                        <span className="location-value" title={match.line}>
+                          {match.timestamp || '시각 없음'} · {match.system_name || '성계 이름 없음'}{match.unknown_system ? ' (어비셜)' : ''}
+                        </span>
+                      </div>
+                    ))}
+                  </div>
+                )}
+                <button
+                  onClick={handleValidateLogPatterns}
+                  className="control-button secondary small"
+                >
+                  <span className="button-icon">🧪</span>
+                  <span className="button-text">패턴 시험</span>
+                </button>
+                <button
+                  onClick={handleReloadLogPatterns}
+                  className="control-button primary small"
+                >
+                  <span className="button-icon">🔄</span>
+                  <span className="button-text">패턴 다시 불러오기</span>
+                </button>
+              </div>
+            </div>
           </div>
         </div>
 
@@ -30,7 +30,7 @@ const StatsDisplay: React.FC<StatsDisplayProps> = ({ data, initialView, selected
     );
   }
 
-  const { df, daily_stats, overall_stats, item_buy_price_cache } = data;
+  const { df, daily_stats, overall_stats, item_buy_price_cache, item_english_names } = data;
 
   return (
     <div className="dashboard-container">      
@@ -40,6 +40,7 @@ const StatsDisplay: React.FC<StatsDisplayProps> = ({ data, initialView, selected
             df={df}
             daily_stats={daily_stats}
             item_buy_price_cache={item_buy_price_cache}
+            item_english_names={item_english_names}
             selectedDate={selectedDate}
             setSelectedDate={setSelectedDate}
             onRunDeleted={onRunDeleted}
@@ -205,13 +205,14 @@ export const ItemIcon: React.FC<ItemIconProps> = ({ typeId, itemName, size = 32,
   const handleImageError = React.useCallback(() => {
     if (retryCount < 1 && actualTypeId) {
       // 한 번만 bp로 재시도
-      const bpUrl = `https://images.evetech.net/types/${actualTypeId}/bp`;
+      // 백엔드에서 받은 이미지 서버 주소를 그대로 사용
+      const bpUrl = iconSrc.replace(/\/icon$/, '/bp');
       setIconSrc(bpUrl);
       setRetryCount(1);
     } else {
       setError(true);
     }
-  }, [actualTypeId, retryCount]);
+  }, [actualTypeId, retryCount, iconSrc]);
 
   if (loading) {
     return (
@@ -2,6 +2,12 @@ import { useState, useCallback, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import type { RunData, AbyssalData, LoadingStep } from "../types";
 
+// 총 수익 / 총 시간 (백엔드의 weighted_iskph와 동일)
+const weightedIskph = (runs: RunData[]) => {
+  const totalTime = runs.reduce((sum, run) => sum + run['런 소요(분)'], 0);
+  return totalTime > 0 ? runs.reduce((sum, run) => sum + run['실수익'], 0) / (totalTime / 60) : 0;
+};
+
 export const useAbyssalData = (triggerPopup: (title: string, message: string, type?: "info" | "warning" | "error") => void) => {
   const [abyssalData, setAbyssalData] = useState<AbyssalData | null>(null);
   const [dataLoading, setDataLoading] = useState(false);
@@ -91,16 +97,12 @@ export const useAbyssalData = (triggerPopup: (title: string, message: string, ty
 
     const newData = { ...abyssalData };
     
-    newData.df = newData.df.filter(run => 
-      !(run['시작시각(KST)'] === deletedRun['시작시각(KST)'] && 
-        run['종료시각(KST)'] === deletedRun['종료시각(KST)'])
-    );
+    newData.df = newData.df.filter(run => run['런 ID'] !== deletedRun['런 ID']);
     
     const runDate = deletedRun['날짜'];
     if (newData.daily_stats[runDate]) {
       const filteredRuns = newData.daily_stats[runDate].runs.filter(run =>
-        !(run['시작시각(KST)'] === deletedRun['시작시각(KST)'] && 
-          run['종료시각(KST)'] === deletedRun['종료시각(KST)'])
+        run['런 ID'] !== deletedRun['런 ID']
       );
       
       if (filteredRuns.length === 0) {
@@ -115,6 +117,7 @@ export const useAbyssalData = (triggerPopup: (title: string, message: string, ty
           avg_isk,
           avg_time,
           avg_iskph,
+          weighted_iskph: weightedIskph(filteredRuns),
         };
       }
     }
@@ -123,6 +126,8 @@ export const useAbyssalData = (triggerPopup: (title: string, message: string, ty
       newData.overall_stats.avg_isk = newData.df.reduce((sum, run) => sum + run['실수익'], 0) / newData.df.length;
       newData.overall_stats.avg_time = newData.df.reduce((sum, run) => sum + run['런 소요(분)'], 0) / newData.df.length;
       newData.overall_stats.avg_iskph = newData.df.reduce((sum, run) => sum + run['ISK/h'], 0) / newData.df.length;
+      newData.overall_stats.weighted_iskph = weightedIskph(newData.df);
+      newData.overall_stats.distributions = undefined;
       
       const tierWeatherGroups: { [key: string]: RunData[] } = {};
       newData.df.forEach(run => {
@@ -143,6 +148,7 @@ export const useAbyssalData = (triggerPopup: (title: string, message: string, ty
           avg_isk: runs.reduce((sum, run) => sum + run['실수익'], 0) / runs.length,
           avg_time: runs.reduce((sum, run) => sum + run['런 소요(분)'], 0) / runs.length,
           avg_iskph: runs.reduce((sum, run) => sum + run['ISK/h'], 0) / runs.length,
+          weighted_iskph: weightedIskph(runs),
           total_entry_cost: 0, // TODO: 실제 필라멘트 비용 계산 로직 필요
         };
       });
@@ -151,6 +157,7 @@ export const useAbyssalData = (triggerPopup: (title: string, message: string, ty
             avg_isk: 0,
             avg_time: 0,
             avg_iskph: 0,
+            weighted_iskph: 0,
             tier_weather_stats: [],
         };
     }
@@ -1,8 +1,11 @@
 // 공통 타입 정의
 
 export interface RunData {
-  '시작시각(KST)': string;
-  '종료시각(KST)': string;
+  '런 ID': string;
+  '시작시각': string;
+  '종료시각': string;
+  '시작시각(UTC)': string;
+  '종료시각(UTC)': string;
   '런 소요(분)': number;
   '어비셜 종류': string;
   '함급'?: number; // 옵셔널로 처리
@@ -10,37 +13,92 @@ export interface RunData {
   'ISK/h': number;
   '획득 아이템': string;
   '날짜': string;
+  '평가 기준'?: string; // current 또는 historical
+  '실수익(현재가)'?: number;
+  'ISK/h(현재가)'?: number;
+  '실수익(당시가)'?: number;
+  'ISK/h(당시가)'?: number;
 }
 
+// 분포 통계 (백분위는 선형 보간, 표준편차는 표본 기준)
+export interface Distribution {
+  median: number;
+  std_dev: number;
+  p10: number;
+  p25: number;
+  p75: number;
+  p90: number;
+}
+
+export interface RunDistributions {
+  profit: Distribution;
+  time: Distribution; // 분
+  iskph: Distribution;
+}
+
+// distributions는 런 삭제 후 로컬에서 다시 계산한 통계에는 없음 (다음 분석 때 갱신)
 export interface DailyStats {
   [date: string]: {
     runs: RunData[];
     avg_isk: number;
     avg_time: number;
-    avg_iskph: number;
+    avg_iskph: number; // 런별 ISK/h의 평균
+    weighted_iskph: number; // 총 수익 / 총 시간
+    distributions?: RunDistributions;
   };
 }
 
+export interface TierWeatherStats {
+  tier: string;
+  weather: string;
+  runs_count: number;
+  avg_isk: number;
+  avg_time: number;
+  avg_iskph: number;
+  weighted_iskph: number;
+  total_entry_cost: number;
+  distributions?: RunDistributions;
+}
+
 export interface OverallStats {
   avg_isk: number;
   avg_time: number;
   avg_iskph: number;
-  tier_weather_stats: {
-    tier: string;
-    weather: string;
-    runs_count: number;
-    avg_isk: number;
-    avg_time: number;
-    avg_iskph: number;
-    total_entry_cost: number;
-  }[];
+  weighted_iskph: number;
+  distributions?: RunDistributions;
+  tier_weather_stats: TierWeatherStats[];
+}
+
+// 주/월/지정 기간 집계
+export interface PeriodStats {
+  start_date: string;
+  end_date: string;
+  runs_count: number;
+  total_profit: number;
+  total_time: number; // 분
+  weighted_iskph: number; // 총 수익 / 총 시간
+  distributions: RunDistributions;
+  tier_weather_stats: TierWeatherStats[];
 }
 
 export interface AbyssalData {
   df: RunData[];
   daily_stats: DailyStats;
+  weekly_stats: { [week: string]: PeriodStats };
+  monthly_stats: { [month: string]: PeriodStats };
   overall_stats: OverallStats;
   item_buy_price_cache: { [key: string]: number };
+  offline: boolean; // 오프라인 모드로 캐시된 시세만 사용
+  price_freshness: { [itemName: string]: PriceFreshness };
+  unpriced_items: string[]; // 시세가 없어 0 ISK로 계산된 아이템
+  item_english_names: { [itemName: string]: string }; // 현지화된 아이템 이름 → 영어 이름
+}
+
+// 아이템 시세의 출처와 조회 시각
+export interface PriceFreshness {
+  source: string; // fuzzwork, esi_orders, esi_average, static, override
+  fetched_at: number | null; // unix 초
+  age_seconds: number | null;
 }
 
 export interface LocationInfo {
@@ -49,6 +107,35 @@ export interface LocationInfo {
   last_updated: string | null;
 }
 
+// 채팅 로그 맨 앞의 헤더 (get_chat_log_header_command)
+export interface ChatLogHeader {
+  channel_id: string | null;
+  channel_name: string | null;
+  listener: string | null;
+  session_started: string | null; // UTC
+}
+
+// validate_log_patterns 결과
+export interface LogPatternMatch {
+  line_number: number;
+  line: string;
+  system_name: string | null;
+  timestamp: string | null;
+  unknown_system: boolean;
+}
+
+export interface LogPatternValidation {
+  pattern_file: string;
+  errors: string[];
+  filename_matched: boolean;
+  detected_language: string;
+  listener: string | null;
+  lines_scanned: number;
+  system_change_count: number;
+  unknown_system_count: number;
+  matches: LogPatternMatch[];
+}
+
 export interface LoadingStep {
   id: string;
   name: string;